@@ -0,0 +1,191 @@
+use crate::application::dns::{Class, DNSLayer, Edns, Name, OpCode, Question, RCode, ResourceRecord, Type};
+use crate::error::ParseError;
+
+/// Builds a DNS layer step by step, keeping the section counts of the header in line with the sections.
+/// Start with [DNSLayer::query], [DNSLayer::try_query] or [DNSLayer::response_to].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DNSLayerBuilder {
+    layer: DNSLayer
+}
+
+impl DNSLayerBuilder {
+    /// Starts a query for the given name and type in the IN class.
+    /// The name is constructed with [Name::new], which panics if it is not a valid name.
+    /// Use [DNSLayerBuilder::try_query] for names from untrusted input.
+    pub fn query(name: &str, qtype: Type) -> Self {
+        DNSLayerBuilder::query_name(Name::new(name), qtype)
+    }
+
+    /// Starts a query for the given name in presentation format and type in the IN class.
+    /// Fails if the name is not a valid name, see [Name::from_str](std::str::FromStr::from_str).
+    pub fn try_query(name: &str, qtype: Type) -> Result<Self, ParseError> {
+        Ok(DNSLayerBuilder::query_name(name.parse()?, qtype))
+    }
+
+    fn query_name(name: Name, qtype: Type) -> Self {
+        let mut layer = DNSLayer::new();
+        layer.add_question(Question::new(name, qtype, Class::IN, false));
+
+        DNSLayerBuilder { layer }
+    }
+
+    /// Starts a response to the given query, copying its ID, opcode, questions and the RD and CD bits.
+    /// If the query has an OPT record, the response gets one with the default UDP payload size.
+    pub fn response_to(query: &DNSLayer) -> Self {
+        let mut layer = DNSLayer::new();
+
+        layer.header.id = query.header.id;
+        layer.header.set_query(false);
+        layer.header.set_opcode(query.header.get_opcode());
+        layer.header.set_recursion_desired(query.header.is_recursion_desired());
+        layer.header.set_checking_disabled(query.header.is_checking_disabled());
+
+        for question in query.questions() {
+            layer.add_question(question.clone());
+        }
+
+        // A response to a query with an OPT record carries one, too, and echoes the DNSSEC OK bit
+        // [RFC 6891 §7](https://datatracker.ietf.org/doc/html/rfc6891#section-7).
+        if let Some(edns) = query.edns() {
+            layer.edns_or_default().set_dnssec_ok(edns.is_dnssec_ok());
+        }
+
+        DNSLayerBuilder { layer }
+    }
+
+    pub fn id(mut self, id: u16) -> Self {
+        self.layer.header.id = id;
+        self
+    }
+
+    /// Sets the opcode, see [DNSHeader::set_opcode](crate::application::dns::DNSHeader::set_opcode).
+    pub fn opcode(mut self, opcode: OpCode) -> Self {
+        self.layer.header.set_opcode(opcode);
+        self
+    }
+
+    pub fn authoritative(mut self, value: bool) -> Self {
+        self.layer.header.set_authoritative_answer(value);
+        self
+    }
+
+    pub fn truncated(mut self, value: bool) -> Self {
+        self.layer.header.set_truncated(value);
+        self
+    }
+
+    pub fn recursion_desired(mut self, value: bool) -> Self {
+        self.layer.header.set_recursion_desired(value);
+        self
+    }
+
+    pub fn recursion_available(mut self, value: bool) -> Self {
+        self.layer.header.set_recursion_available(value);
+        self
+    }
+
+    pub fn authentic_data(mut self, value: bool) -> Self {
+        self.layer.header.set_authentic_data(value);
+        self
+    }
+
+    pub fn checking_disabled(mut self, value: bool) -> Self {
+        self.layer.header.set_checking_disabled(value);
+        self
+    }
+
+    /// Sets the response code, adding an OPT record if it does not fit into the header.
+    /// Panics if the code is greater than 4095, see [DNSLayer::set_response_code].
+    pub fn response_code(mut self, rcode: RCode) -> Self {
+        self.layer.set_response_code(rcode);
+        self
+    }
+
+    /// Adds an OPT record advertising the given UDP payload size, or updates the existing one.
+    pub fn edns(mut self, udp_payload_size: u16) -> Self {
+        self.edns_or_default().udp_payload_size = udp_payload_size;
+        self
+    }
+
+    /// Sets the DNSSEC OK bit, adding an OPT record if necessary.
+    pub fn dnssec_ok(mut self, value: bool) -> Self {
+        self.edns_or_default().set_dnssec_ok(value);
+        self
+    }
+
+    fn edns_or_default(&mut self) -> &mut Edns {
+        self.layer.edns_or_default()
+    }
+
+    pub fn question(mut self, question: Question) -> Self {
+        self.layer.add_question(question);
+        self
+    }
+
+    pub fn answer(mut self, answer: ResourceRecord) -> Self {
+        self.layer.add_answer(answer);
+        self
+    }
+
+    pub fn authority(mut self, authority: ResourceRecord) -> Self {
+        self.layer.add_authority(authority);
+        self
+    }
+
+    pub fn additional(mut self, additional: ResourceRecord) -> Self {
+        self.layer.add_additional(additional);
+        self
+    }
+
+    pub fn build(self) -> DNSLayer {
+        self.layer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::{rdata::RData, Class, DNSLayer, Name, OpCode, RCode, ResourceRecord, Type};
+    use crate::error::ParseErrorKind;
+    use crate::Raw;
+
+    use crate::application::dns::edns::DEFAULT_UDP_PAYLOAD_SIZE;
+
+    #[test]
+    fn test_builder() {
+        let query = DNSLayer::query("example.com", Type::A).id(0xBEEF).recursion_desired(true).checking_disabled(true).edns(4096).dnssec_ok(true).build();
+        assert_eq!(query.raw(), b"\xBE\xEF\x01\x10\x00\x01\x00\x00\x00\x00\x00\x01\
+                                  \x07example\x03com\x00\x00\x01\x00\x01\
+                                  \x00\x00\x29\x10\x00\x00\x00\x80\x00\x00\x00");
+        assert!(query.header().is_query() && query.header().is_recursion_desired());
+        assert_eq!(query.header().get_opcode(), OpCode::StandardQuery);
+
+        let answer = ResourceRecord::new(Name::new("example.com"), Type::A, Class::IN, 300, RData::A("192.0.2.1".parse().unwrap()));
+        let response = DNSLayer::response_to(&query).recursion_available(true).answer(answer.clone()).build();
+        assert_eq!(response.header().id, 0xBEEF);
+        assert!(!response.header().is_query());
+        assert!(response.header().is_recursion_desired() && response.header().is_recursion_available() && response.header().is_checking_disabled());
+        assert_eq!(response.questions(), query.questions());
+        assert_eq!(response.header().answers_count, 1);
+        assert_eq!(DNSLayer::from_bytes(&response.raw()).unwrap(), response);
+
+        // Extended response codes need an OPT record.
+        let response = DNSLayer::response_to(&query).response_code(RCode::BADVERS).build();
+        assert_eq!(response.header().flags & 0x000F, 0);
+        assert_eq!(response.edns().unwrap().udp_payload_size, DEFAULT_UDP_PAYLOAD_SIZE);
+        assert_eq!(response.header().additional_records_count, 1);
+        assert_eq!(response.get_response_code(), RCode::BADVERS);
+
+        let notify = DNSLayer::query("example.com", Type::SOA).opcode(OpCode::Notify).authoritative(true).build();
+        assert_eq!(notify.header().get_opcode(), OpCode::Notify);
+        assert!(notify.header().is_authoritative_answer());
+
+        // Names from untrusted input are checked instead of panicking.
+        assert_eq!(DNSLayer::try_query("example.com.", Type::A).unwrap().build(), DNSLayer::query("example.com", Type::A).build());
+        let err = DNSLayer::try_query(&"a".repeat(64), Type::A).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::BadLength(64));
+        assert!(DNSLayer::try_query("a..b", Type::A).is_err());
+
+        let unknown = DNSLayer::query("example.com", Type::A).opcode(OpCode::Unknown(15)).build();
+        assert_eq!(DNSLayer::response_to(&unknown).build().header().get_opcode(), OpCode::Unknown(15));
+    }
+}
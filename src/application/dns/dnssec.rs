@@ -0,0 +1,330 @@
+use std::cmp::Ordering;
+
+use byteorder::{ByteOrder, NetworkEndian};
+use ring::{digest, signature};
+
+use crate::application::dns::{name::Label, resource_record::RDATA_TOO_LONG, Name, ResourceRecord};
+use crate::application::dns::rdata::{Algorithm, DigestType, RData, AFSDB, DNSKEY, DS, MINFO, MX, RP, RRSIG, RT, SOA, SRV};
+use crate::Raw;
+
+/// The only protocol value of a DNSKEY which DNSSEC accepts. [RFC 4034 §2.1.2](https://datatracker.ietf.org/doc/html/rfc4034#section-2.1.2)
+const DNSSEC_PROTOCOL: u8 = 3;
+
+/// An error produced when DNSSEC data cannot be verified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DnssecError {
+    /// The signature algorithm is not supported by this library.
+    UnsupportedAlgorithm(Algorithm),
+    /// The digest type of a DS record is not supported by this library.
+    UnsupportedDigestType(DigestType),
+    /// There are no records to verify.
+    EmptyRRset,
+    /// The records do not share owner, class and type, or their type is not the one covered by the signature.
+    RRsetMismatch,
+    /// The signature claims more labels than the owner name of the records has.
+    TooManyLabels,
+    /// The key is not the one the signature or DS record refers to, or it may not sign zone data.
+    KeyMismatch,
+    /// The public key is malformed for its algorithm.
+    BadPublicKey,
+    /// The signature does not match the data.
+    BadSignature,
+    /// The digest of a DS record does not match the key.
+    BadDigest
+}
+
+impl std::error::Error for DnssecError {}
+
+impl std::fmt::Display for DnssecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use DnssecError::*;
+        match self {
+            UnsupportedAlgorithm(algorithm) => write!(f, "unsupported algorithm {}", u8::from(*algorithm)),
+            UnsupportedDigestType(digest_type) => write!(f, "unsupported digest type {}", u8::from(*digest_type)),
+            EmptyRRset => write!(f, "the RRset is empty"),
+            RRsetMismatch => write!(f, "the records do not form the RRset covered by the signature"),
+            TooManyLabels => write!(f, "the signature has more labels than the owner name"),
+            KeyMismatch => write!(f, "the key does not match the signature"),
+            BadPublicKey => write!(f, "malformed public key"),
+            BadSignature => write!(f, "the signature does not verify"),
+            BadDigest => write!(f, "the digest does not match the key")
+        }
+    }
+}
+
+/// Returns the record data in its canonical form, in which the names of the types listed in
+/// [RFC 4034 §6.2](https://datatracker.ietf.org/doc/html/rfc4034#section-6.2) as amended by
+/// [RFC 6840 §5.1](https://datatracker.ietf.org/doc/html/rfc6840#section-5.1) are lowercase.
+pub fn canonical_rdata(rdata: &RData) -> RData {
+    match rdata {
+        RData::AFSDB(afsdb) => RData::AFSDB(AFSDB::new(afsdb.subtype, afsdb.name.to_lowercase())),
+        RData::CNAME(name) => RData::CNAME(name.to_lowercase()),
+        RData::DNAME(name) => RData::DNAME(name.to_lowercase()),
+        RData::MB(name) => RData::MB(name.to_lowercase()),
+        RData::MD(name) => RData::MD(name.to_lowercase()),
+        RData::MF(name) => RData::MF(name.to_lowercase()),
+        RData::MG(name) => RData::MG(name.to_lowercase()),
+        RData::MINFO(minfo) => RData::MINFO(MINFO::new(minfo.rmailbx.to_lowercase(), minfo.emailbx.to_lowercase())),
+        RData::MR(name) => RData::MR(name.to_lowercase()),
+        RData::MX(mx) => RData::MX(MX::new(mx.preference, mx.host.to_lowercase())),
+        RData::NAPTR(naptr) => {
+            let mut naptr = naptr.clone();
+            naptr.replacement = naptr.replacement.to_lowercase();
+            RData::NAPTR(naptr)
+        },
+        RData::NS(name) => RData::NS(name.to_lowercase()),
+        RData::PTR(name) => RData::PTR(name.to_lowercase()),
+        RData::RP(rp) => RData::RP(RP::new(rp.mbox.to_lowercase(), rp.txt.to_lowercase())),
+        RData::RRSIG(rrsig) => RData::RRSIG(RRSIG { signer_name: rrsig.signer_name.to_lowercase(), ..rrsig.clone() }),
+        RData::RT(rt) => RData::RT(RT::new(rt.preference, rt.host.to_lowercase())),
+        RData::SOA(soa) => RData::SOA(SOA { mname: soa.mname.to_lowercase(), rname: soa.rname.to_lowercase(), ..soa.clone() }),
+        RData::SRV(srv) => RData::SRV(SRV::new(srv.priority, srv.weight, srv.port, srv.target.to_lowercase())),
+        other => other.clone()
+    }
+}
+
+/// Computes the data which the given signature covers: the signature's own fields without the signature itself,
+/// followed by the records of the RRset in canonical form and order
+/// [RFC 4034 §3.1.8.1](https://datatracker.ietf.org/doc/html/rfc4034#section-3.1.8.1).
+/// The records are written with the original TTL of the signature, and with a wildcard owner name
+/// if the signature has fewer labels than the owner name.
+/// Panics if the data of a record is longer than 65535 bytes, which no record can be.
+pub fn signed_data(rrset: &[&ResourceRecord], rrsig: &RRSIG) -> Result<Vec<u8>, DnssecError> {
+    let first = rrset.first().ok_or(DnssecError::EmptyRRset)?;
+    if rrset.iter().any(|rr| rr.rtype != rrsig.type_covered || rr.class != first.class || rr.name != first.name) {
+        return Err(DnssecError::RRsetMismatch);
+    }
+
+    let owner = signed_owner(&first.name, rrsig.labels)?;
+
+    let mut data = RRSIG { signer_name: rrsig.signer_name.to_lowercase(), signature: Vec::new(), ..rrsig.clone() }.raw();
+
+    // Records are sorted by their canonical record data, and duplicates are only signed once.
+    let mut rdatas: Vec<Vec<u8>> = rrset.iter().map(|rr| canonical_rdata(&rr.rdata).raw()).collect();
+    rdatas.sort();
+    rdatas.dedup();
+
+    for rdata in rdatas {
+        let mut fixed = [0; 10];
+        NetworkEndian::write_u16(&mut fixed[0..2], first.rtype.into());
+        NetworkEndian::write_u16(&mut fixed[2..4], u16::from(first.class));
+        NetworkEndian::write_u32(&mut fixed[4..8], rrsig.original_ttl);
+        NetworkEndian::write_u16(&mut fixed[8..10], u16::try_from(rdata.len()).expect(RDATA_TOO_LONG));
+
+        data.append(&mut owner.raw());
+        data.extend_from_slice(&fixed);
+        data.extend_from_slice(&rdata);
+    }
+
+    Ok(data)
+}
+
+/// Returns the lowercase owner name as it is signed, which is the wildcard the name was expanded from
+/// if the signature has fewer labels than the name [RFC 4035 §5.3.2](https://datatracker.ietf.org/doc/html/rfc4035#section-5.3.2).
+fn signed_owner(owner: &Name, labels: u8) -> Result<Name, DnssecError> {
+    let owner = owner.to_lowercase();
+    let mut owner_labels = owner.labels().clone();
+
+    // A wildcard label is not counted in the labels field of the signature.
+    if owner.is_wildcard() {
+        owner_labels.remove(0);
+    }
+
+    match usize::from(labels).cmp(&owner_labels.len()) {
+        Ordering::Greater => Err(DnssecError::TooManyLabels),
+        Ordering::Equal => Ok(owner),
+        Ordering::Less => {
+            let mut wildcard = vec![Label::new("*").expect("an asterisk is a valid label")];
+            wildcard.extend_from_slice(&owner_labels[owner_labels.len() - usize::from(labels)..]);
+            // The wildcard replaces at least one label of the owner name, so it is never longer than the owner name.
+            Ok(Name::from_labels(wildcard).expect("the wildcard is shorter than the owner name"))
+        }
+    }
+}
+
+/// Verifies the signature over the given RRset with the given key.
+/// The caller is responsible for checking that the key belongs to the signer name of the signature
+/// and that the signature is valid at the current time, see [RRSIG::is_valid_at].
+/// RSA keys shorter than 1024 bits are not supported, and signatures made with them do not verify.
+pub fn verify_rrsig(rrset: &[&ResourceRecord], rrsig: &RRSIG, dnskey: &DNSKEY) -> Result<(), DnssecError> {
+    if dnskey.algorithm != rrsig.algorithm || dnskey.key_tag() != rrsig.key_tag || dnskey.protocol != DNSSEC_PROTOCOL || !dnskey.is_zone_key() {
+        return Err(DnssecError::KeyMismatch);
+    }
+
+    let data = signed_data(rrset, rrsig)?;
+    verify_signature(dnskey, &data, &rrsig.signature)
+}
+
+/// Verifies a signature over the given data with the public key of the given DNSKEY.
+fn verify_signature(dnskey: &DNSKEY, data: &[u8], sig: &[u8]) -> Result<(), DnssecError> {
+    let key = &dnskey.public_key;
+
+    let result = match dnskey.algorithm {
+        Algorithm::RSASHA256 | Algorithm::RSASHA512 => {
+            let (e, n) = split_rsa_key(key)?;
+            let parameters = match dnskey.algorithm {
+                Algorithm::RSASHA256 => &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
+                _ => &signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY
+            };
+
+            signature::RsaPublicKeyComponents { n, e }.verify(parameters, data, sig)
+        },
+        Algorithm::ECDSAP256SHA256 | Algorithm::ECDSAP384SHA384 => {
+            let (parameters, length) = match dnskey.algorithm {
+                Algorithm::ECDSAP256SHA256 => (&signature::ECDSA_P256_SHA256_FIXED, 64),
+                _ => (&signature::ECDSA_P384_SHA384_FIXED, 96)
+            };
+            if key.len() != length {
+                return Err(DnssecError::BadPublicKey);
+            }
+
+            // DNSKEY records hold the bare coordinates of the point, without the marker of the uncompressed form.
+            let mut point = vec![0x04];
+            point.extend_from_slice(key);
+
+            signature::UnparsedPublicKey::new(parameters, point).verify(data, sig)
+        },
+        Algorithm::ED25519 => {
+            if key.len() != 32 {
+                return Err(DnssecError::BadPublicKey);
+            }
+
+            signature::UnparsedPublicKey::new(&signature::ED25519, key).verify(data, sig)
+        },
+        other => return Err(DnssecError::UnsupportedAlgorithm(other))
+    };
+
+    result.map_err(|_| DnssecError::BadSignature)
+}
+
+/// Splits an RSA public key into its exponent and modulus [RFC 3110 §2](https://datatracker.ietf.org/doc/html/rfc3110#section-2).
+fn split_rsa_key(key: &[u8]) -> Result<(&[u8], &[u8]), DnssecError> {
+    // Exponents longer than 255 bytes have their length in the two bytes after a zero byte.
+    let (exponent_length, start) = match key {
+        [0, high, low, ..] => (usize::from(*high) << 8 | usize::from(*low), 3),
+        [length, ..] => (usize::from(*length), 1),
+        [] => return Err(DnssecError::BadPublicKey)
+    };
+
+    let exponent = key.get(start..start + exponent_length).ok_or(DnssecError::BadPublicKey)?;
+    let modulus = &key[start + exponent_length..];
+    if exponent.is_empty() || modulus.is_empty() {
+        return Err(DnssecError::BadPublicKey);
+    }
+
+    Ok((exponent, modulus))
+}
+
+/// Computes the digest of a DS record for the key with the given owner name
+/// [RFC 4034 §5.1.4](https://datatracker.ietf.org/doc/html/rfc4034#section-5.1.4).
+pub fn ds_digest(owner: &Name, dnskey: &DNSKEY, digest_type: DigestType) -> Result<Vec<u8>, DnssecError> {
+    let algorithm = match digest_type {
+        DigestType::SHA1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        DigestType::SHA256 => &digest::SHA256,
+        DigestType::SHA384 => &digest::SHA384,
+        other => return Err(DnssecError::UnsupportedDigestType(other))
+    };
+
+    let mut data = owner.to_lowercase().raw();
+    data.append(&mut dnskey.raw());
+
+    Ok(digest::digest(algorithm, &data).as_ref().to_vec())
+}
+
+/// Constructs the DS record which refers to the key with the given owner name.
+pub fn ds(owner: &Name, dnskey: &DNSKEY, digest_type: DigestType) -> Result<DS, DnssecError> {
+    Ok(DS::new(dnskey.key_tag(), dnskey.algorithm, digest_type, ds_digest(owner, dnskey, digest_type)?))
+}
+
+/// Verifies that the DS record refers to the key with the given owner name.
+pub fn verify_ds(ds: &DS, owner: &Name, dnskey: &DNSKEY) -> Result<(), DnssecError> {
+    if ds.algorithm != dnskey.algorithm || ds.key_tag != dnskey.key_tag() {
+        return Err(DnssecError::KeyMismatch);
+    }
+
+    if ds_digest(owner, dnskey, ds.digest_type)? != ds.digest {
+        return Err(DnssecError::BadDigest);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::{Class, Name, ResourceRecord, Type};
+    use crate::application::dns::rdata::{Algorithm, DigestType, RData, DNSKEY, MX, RRSIG};
+
+    use crate::Raw;
+
+    use super::{canonical_rdata, ds, signed_data, verify_ds, verify_rrsig, DnssecError};
+
+    #[test]
+    fn test_dnssec() {
+        // ECDSA P-256 and P-384, from the examples of RFC 6605
+        let p256 = DNSKEY::new(257, 3, Algorithm::ECDSAP256SHA256, hex::decode("1A88C88615D437FBB8BF9E1942A1929F28562706AE6C2BD399E7B1BFB6D1E9E75B92B4AA42917AE1C61B701EF035C3FE7BE3009CBAFE5A2F71316C902DCF0D00").unwrap());
+        let p384 = DNSKEY::new(257, 3, Algorithm::ECDSAP384SHA384, hex::decode("C4A61A36159D18E7C9FA73EB2FCFDAAE4C1FD8463730327E484ACA8AF0554AE9B5C3F7A0B17BD2003B4D261C9E9B94423A9810E8AF17D43452124ADB610F8E07EBFCFEE5F8E4D07063CAE9EB917A1A5BABF08FE695536017A5BFA93237EE6E34").unwrap());
+        assert_eq!((p256.key_tag(), p384.key_tag()), (55648, 10771));
+
+        let a = ResourceRecord::new(Name::new("www.example.net"), Type::A, Class::IN, 3600, RData::A("192.0.2.1".parse().unwrap()));
+        let p256_sig = RRSIG::new(Type::A, Algorithm::ECDSAP256SHA256, 3, 3600, 0x4C88B137, 0x4C63C737, 55648, Name::new("example.net"),
+            hex::decode("AB1EB02D8AA687E97DA0229337AA8873E6F0EB26BE289F28333D183F5D3B7A95C0C869ADFB748DAEE3C5286EED6682C12E5533186BACED9C26C167A9EBAE950B").unwrap());
+        let p384_sig = RRSIG::new(Type::A, Algorithm::ECDSAP384SHA384, 3, 3600, 0x4C88B4E9, 0x4C63CAE9, 10771, Name::new("example.net"),
+            hex::decode("FCBE610CA22F183C88D5F700457DF3EB9AAB98FB15CFBDD00F532BE4212A3A22CFF79871428BAEAE81827993AFCC56B1B13F0696BEF885B6AF44A6B224DBB2742BB35934923DDCFBC27A972F96DD709CEEB1D9C8D1148C44EC71C068A959C266").unwrap());
+        assert_eq!(verify_rrsig(&[&a], &p256_sig, &p256), Ok(()));
+        assert_eq!(verify_rrsig(&[&a], &p384_sig, &p384), Ok(()));
+
+        let ds256 = ds(&Name::new("example.net"), &p256, DigestType::SHA256).unwrap();
+        assert_eq!(ds256.digest, hex::decode("B4C8C1FE2E7477127B27115656AD6256F424625BF5C1E2770CE6D6E37DF61D17").unwrap());
+        let ds384 = ds(&Name::new("example.net"), &p384, DigestType::SHA384).unwrap();
+        assert_eq!(ds384.digest, hex::decode("72D7B62976CE06438E9C0BF319013CF801F09ECC84B8D7E9495F27E305C6A9B0563A9B5F4D288405C3008A946DF983D6").unwrap());
+
+        // Ed25519, from the examples of RFC 8080. Owner names are compared and signed in lowercase.
+        let ed25519 = DNSKEY::new(257, 3, Algorithm::ED25519, hex::decode("974D96A22D224BC01ADB915091477D44CCD91C9A41A11430010117D52C59240E").unwrap());
+        let mx = ResourceRecord::new(Name::new("EXAMPLE.com"), Type::MX, Class::IN, 3600, RData::MX(MX::new(10, Name::new("Mail.Example.com"))));
+        let ed25519_sig = RRSIG::new(Type::MX, Algorithm::ED25519, 2, 3600, 1440021600, 1438207200, 3613, Name::new("example.com"),
+            hex::decode("A0BF64AC9BA7EF17C138859C1878BB99A839FE1759ACA5B0D798CF1AB1E98D079102F4DDB3368F0FE40BB377F1F00E0CDDEDB799167D56B6E932783072BA8D02").unwrap());
+        assert_eq!(verify_rrsig(&[&mx, &mx], &ed25519_sig, &ed25519), Ok(()));
+
+        let ds = ds(&Name::new("example.com"), &ed25519, DigestType::SHA256).unwrap();
+        assert_eq!(ds.digest, hex::decode("3AA5AB37EFCE57F737FC1627013FEE07BDF241BD10F3B1964AB55C78E79A304B").unwrap());
+        assert_eq!(verify_ds(&ds, &Name::new("Example.COM"), &ed25519), Ok(()));
+        assert_eq!(verify_ds(&ds, &Name::new("example.net"), &ed25519), Err(DnssecError::BadDigest));
+
+        // RSA/SHA-256, from the examples of RFC 5702. Its key of 512 bits is too short to be supported,
+        // but the data it signs must still hash to the digest within the signature.
+        let rsa256 = DNSKEY::new(256, 3, Algorithm::RSASHA256, hex::decode("03010001C15C1AC6B1C5D822BAE1A60A45489B2E21F7D0AA4FB8F0637A5EC4F19C9D416D476161DFA069A27730B6467870082DBDDE10B3C3E4C54769EA9FC395498E6DD9").unwrap());
+        let a = ResourceRecord::new(Name::new("www.example.net"), Type::A, Class::IN, 3600, RData::A("192.0.2.91".parse().unwrap()));
+        let rsa256_sig = RRSIG::new(Type::A, Algorithm::RSASHA256, 3, 3600, 0x70DBD880, 0x386D4380, 9033, Name::new("example.net"),
+            hex::decode("91108E1FABBB974406CBDAA90BD975B0B9DC25C38A14B27B1A18943A26EEE2D798A79544F519DCAE24A164DCFCE66C2532034469C1582BF94FB4F89560FE1BC2").unwrap());
+        assert_eq!(rsa256.key_tag(), 9033);
+        let digest = ring::digest::digest(&ring::digest::SHA256, &signed_data(&[&a], &rsa256_sig).unwrap());
+        assert_eq!(digest.as_ref(), hex::decode("1F3CB0B430354865876F3A70A5D34ED10A4346FD67869ACA7B824BF9218D90CC").unwrap());
+        assert_eq!(verify_rrsig(&[&a], &rsa256_sig, &rsa256), Err(DnssecError::BadSignature));
+
+        // RSA/SHA-512, from the examples of RFC 5702
+        let rsa = DNSKEY::new(256, 3, Algorithm::RSASHA512, hex::decode("03010001D1E8353396F9EB7CE8AB89396443A75A6776FC1BE98F379D25575F22C0DC32EBA1139490DE97D0EEA99D69E3250BA35FF03286A143C63D77B4E9C3F6EFF591383B715EAB92532B643B49090E4F0A5FC3ED034FB2056C32ED49D90F20A4CA73B4D14BA4F073D5B5978C3B5A47B6500656773C5635D994D29DCA2B7DF7069A138B").unwrap());
+        let rsa_sig = RRSIG::new(Type::A, Algorithm::RSASHA512, 3, 3600, 0x70DBD880, 0x386D4380, 3740, Name::new("example.net"),
+            hex::decode("B6C6F8C278D15039C1D41522FADE933135D38635671BE782916AA3BEF8E1CD02F5774611A0E7B409BC6B54361DD310EDB2E25169E530D5EA7DE0FCC45B3AF48866206415A6FF3A6AFBD7CEB9A8182517C3A9F45E281CCCC1E38B0E235AF223F983DBCC869BAFE8E3E5BE95F09BD49AF595C86CD9753011A4D1FB613E0C5D0CBC").unwrap());
+        assert_eq!(rsa.key_tag(), 3740);
+        assert_eq!(verify_rrsig(&[&a], &rsa_sig, &rsa), Ok(()));
+
+        // Records expanded from a wildcard are signed with the wildcard as owner name.
+        let expanded = ResourceRecord::new(Name::new("a.b.example.net"), Type::A, Class::IN, 3600, RData::A("192.0.2.1".parse().unwrap()));
+        let wildcard = ResourceRecord::new(Name::new("*.example.net"), Type::A, Class::IN, 300, RData::A("192.0.2.1".parse().unwrap()));
+        let wildcard_sig = RRSIG { labels: 2, ..p256_sig.clone() };
+        assert_eq!(signed_data(&[&expanded], &wildcard_sig), signed_data(&[&wildcard], &wildcard_sig));
+        assert_eq!(signed_data(&[&expanded], &RRSIG { labels: 5, ..p256_sig.clone() }), Err(DnssecError::TooManyLabels));
+
+        // Tampered data and mismatched records
+        let tampered = ResourceRecord::new(Name::new("www.example.net"), Type::A, Class::IN, 3600, RData::A("192.0.2.2".parse().unwrap()));
+        assert_eq!(verify_rrsig(&[&tampered], &p256_sig, &p256), Err(DnssecError::BadSignature));
+        assert_eq!(verify_rrsig(&[&a, &mx], &p256_sig, &p256), Err(DnssecError::RRsetMismatch));
+        assert_eq!(verify_rrsig(&[], &p256_sig, &p256), Err(DnssecError::EmptyRRset));
+        assert_eq!(verify_rrsig(&[&a], &p256_sig, &p384), Err(DnssecError::KeyMismatch));
+
+        // The names within record data are lowercased, but other fields keep their case.
+        let naptr = RData::parse_with_origin(Type::NAPTR, "100 10 \"S\" \"SIP+D2U\" \"\" _SIP._UDP.EXAMPLE.", &Name::root()).unwrap();
+        let canonical = RData::parse_with_origin(Type::NAPTR, "100 10 \"S\" \"SIP+D2U\" \"\" _sip._udp.example.", &Name::root()).unwrap();
+        assert_eq!(canonical_rdata(&naptr).raw(), canonical.raw());
+    }
+}
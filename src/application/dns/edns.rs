@@ -0,0 +1,458 @@
+use std::mem::size_of;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::application::dns::{flags, read_slice, read_u8, read_u16, read_u32, DNSHeader, MessageEncoder, RCode, Type};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::Raw;
+
+/// Mask of the DNSSEC OK bit in the flags of the OPT record.
+const DNSSEC_OK: u16 = 0b1000_0000_0000_0000;
+
+/// The UDP payload size advertised when EDNS is needed but was not configured,
+/// which avoids IP fragmentation on virtually all paths.
+pub const DEFAULT_UDP_PAYLOAD_SIZE: u16 = 1232;
+
+/// The EDNS(0) information carried by the OPT pseudo-record in the additional section of a message.
+/// [RFC 6891](https://datatracker.ietf.org/doc/html/rfc6891)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edns {
+    /// The largest UDP payload the sender can reassemble, stored in the class field of the OPT record.
+    pub udp_payload_size: u16,
+
+    /// The upper 8 bits of the 12-bit extended response code.
+    pub extended_rcode: u8,
+
+    /// The EDNS version of the sender. Only version 0 is defined.
+    pub version: u8,
+
+    /// The flags of the OPT record, of which only the DNSSEC OK bit is defined.
+    pub flags: u16,
+
+    pub options: Vec<EdnsOption>
+}
+
+impl Edns {
+    /// Constructs version 0 EDNS information without any options.
+    pub fn new(udp_payload_size: u16) -> Self {
+        Edns { udp_payload_size, extended_rcode: 0, version: 0, flags: 0, options: Vec::new() }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Edns::from_message(bytes, 0).map(|(edns, _)| edns)
+    }
+
+    /// Parses an OPT record starting at `offset` in the given DNS message.
+    /// Returns the EDNS information together with the number of bytes the record occupies.
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        if read_u8(message, offset, "Edns.name")? != 0 {
+            return Err(ParseError::new("DNS", "Edns.name", offset, ParseErrorKind::Invalid("the owner of an OPT record must be the root")));
+        }
+
+        let rtype = read_u16(message, offset + 1, "Edns.type")?;
+        if Type::from(rtype) != Type::OPT {
+            return Err(ParseError::new("DNS", "Edns.type", offset + 1, ParseErrorKind::UnknownType(rtype)));
+        }
+
+        let udp_payload_size = read_u16(message, offset + 3, "Edns.udp_payload_size")?;
+        let ttl = read_u32(message, offset + 5, "Edns.ttl")?;
+        let rlength = usize::from(read_u16(message, offset + 9, "Edns.rlength")?);
+        let data = read_slice(message, offset + 11, rlength, "Edns.options")?;
+
+        let mut options = Vec::new();
+        let mut i = 0;
+        while i < data.len() {
+            let (option, size) = EdnsOption::from_bytes(&data[i..]).map_err(|e| e.offset_by(offset + 11 + i))?;
+            options.push(option);
+            i += size;
+        }
+
+        let edns = Edns {
+            udp_payload_size,
+            extended_rcode: (ttl >> 24) as u8,
+            version: (ttl >> 16) as u8,
+            flags: ttl as u16,
+            options
+        };
+
+        Ok((edns, 11 + rlength))
+    }
+
+    /// Writes the OPT record to the given message encoder.
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write(&self.raw());
+    }
+
+    /// Returns whether the sender can handle DNSSEC records. [RFC 3225](https://datatracker.ietf.org/doc/html/rfc3225)
+    pub fn is_dnssec_ok(&self) -> bool {
+        self.flags & DNSSEC_OK != 0
+    }
+
+    /// Sets the DNSSEC OK bit to the specified value.
+    pub fn set_dnssec_ok(&mut self, value: bool) {
+        self.flags = (self.flags & !DNSSEC_OK) | (DNSSEC_OK * value as u16);
+    }
+
+    /// Combines the upper 8 bits stored in the OPT record with the lower 4 bits in the flags of the header.
+    pub fn response_code(&self, header: &DNSHeader) -> RCode {
+        ((u16::from(self.extended_rcode) << 4) | (header.flags & flags::RCODE)).into()
+    }
+
+    /// Returns the first option with the given code.
+    pub fn option(&self, code: u16) -> Option<&EdnsOption> {
+        self.options.iter().find(|option| option.code() == code)
+    }
+
+    fn options_size(&self) -> usize {
+        self.options.iter().map(|option| option.raw_size()).sum()
+    }
+}
+
+impl Raw for Edns {
+    /// Panics if the options are longer than 65535 bytes, which no OPT record can be.
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 11];
+
+        NetworkEndian::write_u16(&mut bytes[1..3], Type::OPT.into());
+        NetworkEndian::write_u16(&mut bytes[3..5], self.udp_payload_size);
+        bytes[5] = self.extended_rcode;
+        bytes[6] = self.version;
+        NetworkEndian::write_u16(&mut bytes[7..9], self.flags);
+        NetworkEndian::write_u16(&mut bytes[9..11], u16::try_from(self.options_size()).expect("The options of an OPT record may be at most 65535 bytes long."));
+
+        for option in &self.options {
+            bytes.append(&mut option.raw());
+        }
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        11 + self.options_size()
+    }
+}
+
+/// An option within the OPT record, identified by a code assigned by
+/// [IANA](https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-11).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EdnsOption {
+    /// An identifier of the name server which answered. [RFC 5001](https://datatracker.ietf.org/doc/html/rfc5001)
+    Nsid(Vec<u8>),
+    /// The network of the client on whose behalf a resolver queries. [RFC 7871](https://datatracker.ietf.org/doc/html/rfc7871)
+    ClientSubnet(ClientSubnet),
+    /// A client cookie, followed by a server cookie of 8 to 32 bytes in responses. [RFC 7873](https://datatracker.ietf.org/doc/html/rfc7873)
+    Cookie {
+        client: [u8; 8],
+        server: Option<Vec<u8>>
+    },
+    /// The idle timeout of a TCP connection in units of 100 milliseconds, which is absent in queries.
+    /// [RFC 7828](https://datatracker.ietf.org/doc/html/rfc7828)
+    Keepalive(Option<u16>),
+    /// Bytes which pad the message to a less revealing size. [RFC 7830](https://datatracker.ietf.org/doc/html/rfc7830)
+    Padding(Vec<u8>),
+    /// Additional information about the cause of an error. [RFC 8914](https://datatracker.ietf.org/doc/html/rfc8914)
+    /// An option whose extra text is not UTF-8 is parsed as [EdnsOption::Unknown].
+    ExtendedError {
+        info_code: u16,
+        extra_text: String
+    },
+    /// An option which is not parsed by this library, kept verbatim.
+    Unknown {
+        code: u16,
+        data: Vec<u8>
+    }
+}
+
+impl EdnsOption {
+    /// Parses an option starting at the beginning of the given bytes.
+    /// Returns the option together with the number of bytes it occupies.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ParseError> {
+        let code = read_u16(bytes, 0, "EdnsOption.code")?;
+        let length = usize::from(read_u16(bytes, 2, "EdnsOption.length")?);
+        let data = read_slice(bytes, 4, length, "EdnsOption.data")?;
+
+        let bad_length = || ParseError::new("DNS", "EdnsOption.length", 2, ParseErrorKind::BadLength(length));
+
+        let option = match code {
+            3 => EdnsOption::Nsid(data.to_vec()),
+            8 => EdnsOption::ClientSubnet(ClientSubnet::from_bytes(data).map_err(|e| e.offset_by(4))?),
+            10 => {
+                if length != 8 && !(16..=40).contains(&length) {
+                    return Err(bad_length());
+                }
+
+                EdnsOption::Cookie {
+                    client: data[..8].try_into().unwrap(),
+                    server: if length > 8 { Some(data[8..].to_vec()) } else { None }
+                }
+            },
+            11 => match length {
+                0 => EdnsOption::Keepalive(None),
+                2 => EdnsOption::Keepalive(Some(NetworkEndian::read_u16(data))),
+                _ => return Err(bad_length())
+            },
+            12 => EdnsOption::Padding(data.to_vec()),
+            15 => {
+                let info_code = read_u16(data, 0, "EdnsOption.info_code").map_err(|e| e.offset_by(4))?;
+
+                // Extra text which is not UTF-8 is kept verbatim rather than failing the whole message.
+                match String::from_utf8(data[2..].to_vec()) {
+                    Ok(extra_text) => EdnsOption::ExtendedError { info_code, extra_text },
+                    Err(_) => EdnsOption::Unknown { code, data: data.to_vec() }
+                }
+            },
+            _ => EdnsOption::Unknown { code, data: data.to_vec() }
+        };
+
+        Ok((option, 4 + length))
+    }
+
+    /// Returns the option code.
+    pub fn code(&self) -> u16 {
+        match self {
+            EdnsOption::Nsid(_) => 3,
+            EdnsOption::ClientSubnet(_) => 8,
+            EdnsOption::Cookie { .. } => 10,
+            EdnsOption::Keepalive(_) => 11,
+            EdnsOption::Padding(_) => 12,
+            EdnsOption::ExtendedError { .. } => 15,
+            EdnsOption::Unknown { code, .. } => *code
+        }
+    }
+
+    /// Returns the option data without the code and length.
+    fn data(&self) -> Vec<u8> {
+        match self {
+            EdnsOption::Nsid(data) | EdnsOption::Padding(data) | EdnsOption::Unknown { data, .. } => data.clone(),
+            EdnsOption::ClientSubnet(subnet) => subnet.raw(),
+            EdnsOption::Cookie { client, server } => {
+                let mut data = client.to_vec();
+                data.extend(server.iter().flatten());
+                data
+            },
+            EdnsOption::Keepalive(timeout) => timeout.map(u16::to_be_bytes).map_or(Vec::new(), |t| t.to_vec()),
+            EdnsOption::ExtendedError { info_code, extra_text } => {
+                let mut data = info_code.to_be_bytes().to_vec();
+                data.extend_from_slice(extra_text.as_bytes());
+                data
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for EdnsOption {
+    /// Formats the option as dig does in its OPT pseudo-section, e.g. `COOKIE: 0123456789ABCDEF`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EdnsOption::Nsid(id) => write!(f, "NSID: {}", hex::encode_upper(id)),
+            EdnsOption::ClientSubnet(subnet) => write!(f, "CLIENT-SUBNET: {}", subnet),
+            EdnsOption::Cookie { client, server } => {
+                write!(f, "COOKIE: {}{}", hex::encode_upper(client), hex::encode_upper(server.as_deref().unwrap_or_default()))
+            },
+            EdnsOption::Keepalive(Some(timeout)) => write!(f, "TCP-KEEPALIVE: {}.{} secs", timeout / 10, timeout % 10),
+            EdnsOption::Keepalive(None) => write!(f, "TCP-KEEPALIVE"),
+            EdnsOption::Padding(padding) => write!(f, "PADDING: {} bytes", padding.len()),
+            EdnsOption::ExtendedError { info_code, extra_text } if extra_text.is_empty() => write!(f, "EDE: {}", info_code),
+            EdnsOption::ExtendedError { info_code, extra_text } => write!(f, "EDE: {} ({})", info_code, extra_text),
+            EdnsOption::Unknown { code, data } => write!(f, "OPT={}: {}", code, hex::encode_upper(data))
+        }
+    }
+}
+
+impl Raw for EdnsOption {
+    /// Panics if the data is longer than 65535 bytes, which no option can be.
+    fn raw(&self) -> Vec<u8> {
+        let data = self.data();
+        let mut bytes = vec![0; 2 * size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.code());
+        NetworkEndian::write_u16(&mut bytes[2..4], u16::try_from(data.len()).expect("The data of an EDNS option may be at most 65535 bytes long."));
+        bytes.extend_from_slice(&data);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 * size_of::<u16>() + self.data().len()
+    }
+}
+
+/// The Client Subnet option. Only the first `source_prefix_length` bits of the address are transmitted,
+/// the rest are written as zero [RFC 7871 §6](https://datatracker.ietf.org/doc/html/rfc7871#section-6).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClientSubnet {
+    /// The number of significant bits of the address, set by the client.
+    pub source_prefix_length: u8,
+
+    /// The number of bits of the address the answer is valid for, set by the server.
+    pub scope_prefix_length: u8,
+
+    pub address: IpAddr
+}
+
+impl ClientSubnet {
+    pub fn new(address: IpAddr, source_prefix_length: u8) -> Self {
+        ClientSubnet { source_prefix_length, scope_prefix_length: 0, address }
+    }
+
+    /// Parses the data of a Client Subnet option.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let family = read_u16(bytes, 0, "ClientSubnet.family")?;
+        let source_prefix_length = read_u8(bytes, 2, "ClientSubnet.source_prefix_length")?;
+        let scope_prefix_length = read_u8(bytes, 3, "ClientSubnet.scope_prefix_length")?;
+
+        let address_length = match family {
+            1 => 4,
+            2 => 16,
+            _ => return Err(ParseError::new("DNS", "ClientSubnet.family", 0, ParseErrorKind::Invalid("unknown address family")))
+        };
+
+        // The address is truncated to the bytes which hold the prefix.
+        let prefix_bytes = bytes.get(4..).unwrap_or_default();
+        if usize::from(source_prefix_length) > address_length * 8 || prefix_bytes.len() != usize::from(source_prefix_length).div_ceil(8) {
+            return Err(ParseError::new("DNS", "ClientSubnet.address", 4, ParseErrorKind::BadLength(prefix_bytes.len())));
+        }
+        if prefix_bytes.last().is_some_and(|last| last & !prefix_mask(source_prefix_length) != 0) {
+            return Err(ParseError::new("DNS", "ClientSubnet.address", 3 + prefix_bytes.len(), ParseErrorKind::Invalid("address bits beyond the source prefix length must be zero")));
+        }
+
+        let mut octets = [0u8; 16];
+        octets[..prefix_bytes.len()].copy_from_slice(prefix_bytes);
+
+        let address = match family {
+            1 => IpAddr::V4(Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3])),
+            _ => IpAddr::V6(Ipv6Addr::from(octets))
+        };
+
+        Ok(ClientSubnet { source_prefix_length, scope_prefix_length, address })
+    }
+}
+
+/// Returns the mask of the bits of the last address byte which belong to a prefix of the given length.
+fn prefix_mask(prefix_length: u8) -> u8 {
+    match prefix_length % 8 {
+        0 => 0xFF,
+        bits => 0xFF << (8 - bits)
+    }
+}
+
+impl std::fmt::Display for ClientSubnet {
+    /// Formats the subnet as address, source prefix length and scope prefix length, e.g. `192.0.2.0/24/0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}/{}", self.address, self.source_prefix_length, self.scope_prefix_length)
+    }
+}
+
+impl Raw for ClientSubnet {
+    fn raw(&self) -> Vec<u8> {
+        let (family, octets) = match self.address {
+            IpAddr::V4(v4) => (1u16, v4.octets().to_vec()),
+            IpAddr::V6(v6) => (2u16, v6.octets().to_vec())
+        };
+
+        let mut bytes = family.to_be_bytes().to_vec();
+        bytes.push(self.source_prefix_length);
+        bytes.push(self.scope_prefix_length);
+        bytes.extend_from_slice(&octets[..usize::from(self.source_prefix_length).div_ceil(8).min(octets.len())]);
+        if let Some(last) = bytes.last_mut().filter(|_| self.source_prefix_length > 0) {
+            *last &= prefix_mask(self.source_prefix_length);
+        }
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.raw().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use crate::application::dns::{rdata::RData, Class, DNSHeader, DNSLayer, Name, RCode, ResourceRecord, Type};
+    use crate::error::ParseErrorKind;
+    use crate::Raw;
+
+    use super::{ClientSubnet, Edns, EdnsOption};
+
+    #[test]
+    fn test_edns() {
+        // An OPT record with DO set, a cookie, a client subnet, keepalive and an unknown option.
+        let bytes = b"\x00\x00\x29\x04\xD0\x01\x00\x80\x00\x00\x21\
+                      \x00\x0A\x00\x08\x01\x02\x03\x04\x05\x06\x07\x08\
+                      \x00\x08\x00\x07\x00\x01\x18\x00\xC0\x00\x02\
+                      \x00\x0B\x00\x00\
+                      \xFD\xE9\x00\x02\xAB\xCD";
+
+        let edns = Edns::from_bytes(bytes).unwrap();
+        assert_eq!(edns.udp_payload_size, 1232);
+        assert_eq!(edns.version, 0);
+        assert!(edns.is_dnssec_ok());
+        assert_eq!(edns.response_code(&DNSHeader::new()), RCode::BADVERS);
+        assert_eq!(edns.options, vec![
+            EdnsOption::Cookie { client: [1, 2, 3, 4, 5, 6, 7, 8], server: None },
+            EdnsOption::ClientSubnet(ClientSubnet::new("192.0.2.0".parse::<IpAddr>().unwrap(), 24)),
+            EdnsOption::Keepalive(None),
+            EdnsOption::Unknown { code: 65001, data: vec![0xAB, 0xCD] }
+        ]);
+        assert_eq!(edns.option(8), Some(&edns.options[1]));
+        assert_eq!(edns.raw(), bytes);
+
+        let mut edns = Edns::new(1232);
+        edns.set_dnssec_ok(true);
+        edns.options.push(EdnsOption::ExtendedError { info_code: 18, extra_text: String::from("prohibited") });
+        edns.options.push(EdnsOption::Nsid(b"ns1".to_vec()));
+        edns.options.push(EdnsOption::Padding(vec![0; 3]));
+        assert_eq!(Edns::from_bytes(&edns.raw()).unwrap(), edns);
+
+        // Malformed options
+        let err = Edns::from_bytes(b"\x00\x00\x29\x04\xD0\x00\x00\x00\x00\x00\x06\x00\x0A\x00\x02\x01\x02").unwrap_err();
+        assert_eq!((err.offset, err.kind), (13, ParseErrorKind::BadLength(2)));
+        let err = Edns::from_bytes(b"\x00\x00\x29\x04\xD0\x00\x00\x00\x00\x00\x08\x00\x08\x00\x04\x00\x01\x18\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (19, ParseErrorKind::BadLength(0)));
+
+        // Address bits past the source prefix length are not sent and must not be received.
+        let subnet = ClientSubnet::new("192.0.2.77".parse::<IpAddr>().unwrap(), 20);
+        assert_eq!(subnet.raw(), b"\x00\x01\x14\x00\xC0\x00\x00");
+        assert_eq!(ClientSubnet::from_bytes(b"\x00\x01\x14\x00\xC0\x00\x00").unwrap().address, "192.0.0.0".parse::<IpAddr>().unwrap());
+        let err = ClientSubnet::from_bytes(b"\x00\x01\x14\x00\xC0\x00\x02").unwrap_err();
+        assert_eq!((err.offset, err.kind), (6, ParseErrorKind::Invalid("address bits beyond the source prefix length must be zero")));
+
+        // Extended errors whose extra text is not UTF-8 are kept verbatim.
+        let bytes = b"\x00\x00\x29\x04\xD0\x00\x00\x00\x00\x00\x07\x00\x0F\x00\x03\x00\x12\xFF";
+        let unknown = Edns::from_bytes(bytes).unwrap();
+        assert_eq!(unknown.options, vec![EdnsOption::Unknown { code: 15, data: vec![0x00, 0x12, 0xFF] }]);
+        assert_eq!(unknown.raw(), bytes);
+
+        // The OPT record is kept apart from the additional records and written before a trailing TSIG.
+        let mut layer = DNSLayer::new();
+        layer.header_mut().flags |= 0x0007;
+        layer.add_additional(ResourceRecord::new(Name::new("key.example"), Type::TSIG, Class::IN, 0, RData::Unknown { rtype: Type::TSIG, bytes: vec![1, 2] }));
+        assert_eq!(layer.set_edns(Some(edns.clone())), None);
+        assert_eq!(layer.header().additional_records_count, 2);
+        assert_eq!(layer.get_response_code(), RCode::YXRRSET);
+        layer.edns_mut().unwrap().extended_rcode = 1;
+        assert_eq!(layer.get_response_code(), RCode::BADCOOKIE);
+
+        let bytes = layer.raw();
+        assert_eq!(&bytes[12..15], b"\x00\x00\x29");
+        let parsed = DNSLayer::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.additional().len(), 1);
+        assert_eq!(parsed, layer);
+
+        // Only one OPT record is allowed.
+        let mut twice = bytes[..12 + edns.raw_size()].to_vec();
+        twice[11] = 2;
+        twice.extend_from_slice(&edns.raw());
+        assert_eq!(DNSLayer::from_bytes(&twice).unwrap_err().kind, ParseErrorKind::Invalid("a message must not contain more than one OPT record"));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 65535 bytes")]
+    fn test_edns_option_too_long() {
+        // The length of the option must not wrap around.
+        EdnsOption::Padding(vec![0; 70000]).raw();
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::application::dns::{name::Label, Name};
+use crate::Raw;
+
+/// Compression pointers are 14 bits wide, so only names starting before this offset can be pointed to.
+const MAX_POINTER_OFFSET: usize = 0x3FFF;
+
+/// Options which control how a DNS message is serialized.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncodeOptions {
+    /// Whether names are compressed by pointing to earlier occurrences of the same suffix.
+    /// Disable this to reproduce uncompressed input byte for byte.
+    pub compress_names: bool,
+
+    /// Whether the section counts of the header are written as they are instead of being derived from the sections.
+    /// Enable this to emit deliberately inconsistent counts, e.g. for fuzzing.
+    pub preserve_counts: bool
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        EncodeOptions { compress_names: true, preserve_counts: false }
+    }
+}
+
+/// Serializes the parts of a DNS message into a single buffer.
+/// The encoder remembers the offset of every name suffix it has written,
+/// so that later names can be compressed [RFC 1035 §4.1.4](https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.4).
+#[derive(Debug, Clone)]
+pub struct MessageEncoder {
+    bytes: Vec<u8>,
+    options: EncodeOptions,
+    /// Maps the labels of previously written name suffixes, which compare without regard to case, to their offsets in the message.
+    suffixes: HashMap<Vec<Label>, u16>
+}
+
+impl MessageEncoder {
+    /// Constructs an empty encoder with the given options.
+    pub fn new(options: EncodeOptions) -> Self {
+        MessageEncoder { bytes: Vec::new(), options, suffixes: HashMap::new() }
+    }
+
+    /// Returns the bytes written so far.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes written so far, consuming the encoder.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Appends raw bytes to the message.
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Appends a u16 in network byte order to the message.
+    pub fn write_u16(&mut self, value: u16) {
+        let mut buf = [0; 2];
+        NetworkEndian::write_u16(&mut buf, value);
+        self.write(&buf);
+    }
+
+    /// Appends a u32 in network byte order to the message.
+    pub fn write_u32(&mut self, value: u32) {
+        let mut buf = [0; 4];
+        NetworkEndian::write_u32(&mut buf, value);
+        self.write(&buf);
+    }
+
+    /// Overwrites the u16 at `offset` with the given value, e.g. to fill in a length once it is known.
+    pub fn patch_u16(&mut self, offset: usize, value: u16) {
+        NetworkEndian::write_u16(&mut self.bytes[offset..offset + 2], value);
+    }
+
+    /// Appends a name to the message, replacing its longest previously written suffix with a pointer
+    /// if compression is enabled.
+    pub fn write_name(&mut self, name: &Name) {
+        if !self.options.compress_names {
+            self.write_name_uncompressed(name);
+            return;
+        }
+
+        let labels = name.labels();
+        for i in 0..labels.len() {
+            let suffix = labels[i..].to_vec();
+
+            if let Some(&pointer) = self.suffixes.get(&suffix) {
+                self.write_u16(0xC000 | pointer);
+                return;
+            }
+
+            if self.bytes.len() <= MAX_POINTER_OFFSET {
+                self.suffixes.insert(suffix, self.bytes.len() as u16);
+            }
+
+            self.bytes.append(&mut labels[i].raw());
+        }
+
+        self.bytes.push(0);
+    }
+
+    /// Appends a name to the message without compressing it.
+    /// Used for names within record data of types for which compression is not allowed
+    /// [RFC 3597 §4](https://datatracker.ietf.org/doc/html/rfc3597#section-4).
+    pub fn write_name_uncompressed(&mut self, name: &Name) {
+        self.bytes.append(&mut name.raw());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::Name;
+
+    use super::{EncodeOptions, MessageEncoder};
+
+    #[test]
+    fn test_message_encoder() {
+        let mut encoder = MessageEncoder::new(EncodeOptions::default());
+        encoder.write(&[0xAA, 0xBB]);
+        encoder.write_name(&Name::new("example.com"));
+        encoder.write_name(&Name::new("WWW.EXAMPLE.com"));
+        encoder.write_name(&Name::new("example.org"));
+        encoder.write_name_uncompressed(&Name::new("example.com"));
+        assert_eq!(encoder.bytes(), b"\xAA\xBB\x07example\x03com\x00\x03WWW\xC0\x02\x07example\x03org\x00\x07example\x03com\x00");
+
+        let mut encoder = MessageEncoder::new(EncodeOptions { compress_names: false, ..Default::default() });
+        encoder.write_name(&Name::new("example.com"));
+        encoder.write_name(&Name::new("example.com"));
+        assert_eq!(encoder.into_bytes(), b"\x07example\x03com\x00\x07example\x03com\x00");
+    }
+}
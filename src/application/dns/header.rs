@@ -2,10 +2,13 @@ use std::mem::size_of;
 
 use byteorder::{ByteOrder, NetworkEndian};
 
+use crate::error::ParseError;
 use crate::Raw;
 
+use super::read_slice;
+
 /// Flag masks for the flags field of DNSHeader.
-mod flags {
+pub mod flags {
     pub const QUERY: u16 = 0b1000_0000_0000_0000;
     pub const OPCODE: u16 = 0b0111_1000_0000_0000;
     pub const AUTHORITATIVE: u16 = 0b0000_0100_0000_0000;
@@ -41,17 +44,19 @@ impl DNSHeader {
         DNSHeader { id, flags, questions_count, answers_count, name_servers_count, additional_records_count }
     }
 
-    /// Parses an array of bytes into a DNS header.
+    /// Parses the bytes at the start of the given slice into a DNS header.
     /// The size of the header is fixed, so we can use size_of::<u16>() * 6 to calculate it - a total of 12 bytes.
-    pub fn from_bytes(bytes: [u8; size_of::<u16>() * 6]) -> Self {
-        DNSHeader { 
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let bytes = read_slice(bytes, 0, size_of::<u16>() * 6, "DNSHeader")?;
+
+        Ok(DNSHeader { 
             id: NetworkEndian::read_u16(&bytes[0..2]),
             flags: NetworkEndian::read_u16(&bytes[2..4]),
             questions_count: NetworkEndian::read_u16(&bytes[4..6]),
             answers_count: NetworkEndian::read_u16(&bytes[6..8]),
             name_servers_count: NetworkEndian::read_u16(&bytes[8..10]),
             additional_records_count: NetworkEndian::read_u16(&bytes[10..])
-        }
+        })
     }
 
     /// Checks the header flags to see if the DNS packet is a query or response packet.
@@ -63,7 +68,8 @@ impl DNSHeader {
 
     /// Retrieves the DNS opcode from the flags field.
     pub fn get_opcode(&self) -> OpCode {
-        ((self.flags & flags::OPCODE) >> flags::OPCODE.trailing_zeros()).into()
+        // The opcode takes 4 bits, so it always fits in a byte.
+        (((self.flags & flags::OPCODE) >> flags::OPCODE.trailing_zeros()) as u8).into()
     }
 
     /// Returns whether or not the packet is an authoritative answer.
@@ -86,11 +92,90 @@ impl DNSHeader {
         self.flags & flags::RECURSION_AVAILABLE != 0
     }
 
+    /// Returns whether or not the server considers all answer and authority data to be authenticated by DNSSEC.
+    /// [RFC 4035 §3.2.3](https://datatracker.ietf.org/doc/html/rfc4035#section-3.2.3)
+    pub fn is_authentic_data(&self) -> bool {
+        self.flags & flags::AUTHENTIC_DATA != 0
+    }
+
+    /// Returns whether or not the resolver is asked not to perform DNSSEC validation.
+    /// [RFC 4035 §3.2.2](https://datatracker.ietf.org/doc/html/rfc4035#section-3.2.2)
+    pub fn is_checking_disabled(&self) -> bool {
+        self.flags & flags::CHECKING_DISABLED != 0
+    }
+
+    /// Returns whether or not the reserved Z bit is set, which must be zero in all queries and responses.
+    pub fn is_reserved(&self) -> bool {
+        self.flags & flags::RESERVED != 0
+    }
+
     /// Returns the response code which the DNS server issued.
     pub fn get_response_code(&self) -> RCode {
         (self.flags & flags::RCODE).into()
     }
 
+    /// Sets or clears the bits of the given mask in the flags field.
+    fn set_flag(&mut self, mask: u16, value: bool) {
+        self.flags = (self.flags & !mask) | (mask * value as u16);
+    }
+
+    /// Marks the packet as a query if `value` is true, otherwise as a response.
+    pub fn set_query(&mut self, value: bool) {
+        self.set_flag(flags::QUERY, !value);
+    }
+
+    /// Stores the opcode in the flags field.
+    /// Panics if the number of an unknown opcode is greater than 15, as it would not fit in the 4 bits of the field.
+    pub fn set_opcode(&mut self, opcode: OpCode) {
+        let code = u8::from(opcode);
+        assert!(code <= 15, "DNS opcodes may be at most 15.");
+
+        self.flags = (self.flags & !flags::OPCODE) | (u16::from(code) << flags::OPCODE.trailing_zeros());
+    }
+
+    pub fn set_authoritative_answer(&mut self, value: bool) {
+        self.set_flag(flags::AUTHORITATIVE, value);
+    }
+
+    pub fn set_truncated(&mut self, value: bool) {
+        self.set_flag(flags::TRUNCATED, value);
+    }
+
+    pub fn set_recursion_desired(&mut self, value: bool) {
+        self.set_flag(flags::RECURSION_DESIRED, value);
+    }
+
+    pub fn set_recursion_available(&mut self, value: bool) {
+        self.set_flag(flags::RECURSION_AVAILABLE, value);
+    }
+
+    pub fn set_authentic_data(&mut self, value: bool) {
+        self.set_flag(flags::AUTHENTIC_DATA, value);
+    }
+
+    pub fn set_checking_disabled(&mut self, value: bool) {
+        self.set_flag(flags::CHECKING_DISABLED, value);
+    }
+
+    pub fn set_reserved(&mut self, value: bool) {
+        self.set_flag(flags::RESERVED, value);
+    }
+
+    /// Stores the lower 4 bits of the response code in the flags field.
+    /// Extended response codes also need the upper bits in an OPT record, see [DNSLayer::set_response_code](super::DNSLayer::set_response_code).
+    /// Panics if the number of an unknown response code is greater than 4095, as it would not fit in the 12 bits of an extended response code.
+    pub fn set_response_code(&mut self, rcode: RCode) {
+        let code = u16::from(rcode);
+        assert!(code <= 0xFFF, "DNS response codes may be at most 4095.");
+
+        self.flags = (self.flags & !flags::RCODE) | (code & flags::RCODE);
+    }
+}
+
+impl Default for DNSHeader {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Raw for DNSHeader {
@@ -116,21 +201,23 @@ impl Raw for DNSHeader {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum OpCode {
     /// Normal query
-    StandardQuery = 0,
+    StandardQuery,
     /// Inverse query (query a name by IP)
-    InverseQuery = 1,
+    InverseQuery,
     /// Server status request
-    ServerStatusRequest = 2,
+    ServerStatusRequest,
     /// Notify query
-    Notify = 4,
+    Notify,
     /// Update query [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    Update = 5,
-    /// Reserved opcode for future use
-    Reserved,
+    Update,
+    /// DNS stateful operations [RFC 8490](https://datatracker.ietf.org/doc/html/rfc8490)
+    DSO,
+    /// An opcode which is not known to this library, identified by its number of at most 15.
+    Unknown(u8),
 }
 
-impl From<u16> for OpCode {
-    fn from(code: u16) -> Self {
+impl From<u8> for OpCode {
+    fn from(code: u8) -> Self {
         use OpCode::*;
         match code {
             0 => StandardQuery,
@@ -138,7 +225,41 @@ impl From<u16> for OpCode {
             2 => ServerStatusRequest,
             4 => Notify,
             5 => Update,
-            _ => Reserved,
+            6 => DSO,
+            _ => Unknown(code),
+        }
+    }
+}
+
+impl std::fmt::Display for OpCode {
+    /// Formats the opcode by its mnemonic, or as `OPCODE` followed by its number if it has none.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use OpCode::*;
+        let mnemonic = match self {
+            StandardQuery => "QUERY",
+            InverseQuery => "IQUERY",
+            ServerStatusRequest => "STATUS",
+            Notify => "NOTIFY",
+            Update => "UPDATE",
+            DSO => "DSO",
+            Unknown(code) => return write!(f, "OPCODE{}", code)
+        };
+
+        write!(f, "{}", mnemonic)
+    }
+}
+
+impl From<OpCode> for u8 {
+    fn from(opcode: OpCode) -> Self {
+        use OpCode::*;
+        match opcode {
+            StandardQuery => 0,
+            InverseQuery => 1,
+            ServerStatusRequest => 2,
+            Notify => 4,
+            Update => 5,
+            DSO => 6,
+            Unknown(code) => code,
         }
     }
 }
@@ -147,38 +268,44 @@ impl From<u16> for OpCode {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum RCode {
     /// No error condition
-    NoError = 0,
+    NoError,
     /// Format error - The name server was unable to interpret the query.
-    FormatError = 1,
+    FormatError,
     /// Server failure - The name server was unable to process this query due to a problem with the name server.
-    ServerFailure = 2,
+    ServerFailure,
     /// Name Error - Meaningful only for responses from an authoritative name server,  
     /// this code signifies that the domain name referenced in the query does not exist.
-    NameError = 3,
+    NameError,
     /// Not Implemented - The name server does not support the requested kind of query.
-    NotImplemented = 4,
+    NotImplemented,
     /// Refused - The name server refuses to perform the specified operation for policy reasons.  
     /// For example, a name server may not wish to provide the information to the particular requester,   
     /// or a name server may not wish to perform a particular operation (e.g., zone transfer) for particular data.
-    Refused = 5,
+    Refused,
     /// Some name that ought not to exist, does exist.
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    YXDOMAIN = 6,
+    YXDOMAIN,
     /// Some RRset that ought not to exist, does exist.
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    YXRRSET = 7,
+    YXRRSET,
     /// Some RRset that ought to exist, does not exist.
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    NXRRSET = 8,
+    NXRRSET,
     /// The server is not authoritative for the zone named in the Zone Section.
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    NOTAUTH = 9,
+    NOTAUTH,
     /// A name used in the Prerequisite or Update Section is not within the zone denoted by the Zone Section.
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    NOTZONE = 10,
+    NOTZONE,
+    /// The EDNS version of the request is not supported by the server.  
+    /// Only representable with the extended RCODE of an OPT record. [RFC 6891](https://datatracker.ietf.org/doc/html/rfc6891)
+    BADVERS,
+    /// The server cookie of the request is missing or invalid.  
+    /// Only representable with the extended RCODE of an OPT record. [RFC 7873](https://datatracker.ietf.org/doc/html/rfc7873)
+    BADCOOKIE,
 
-    /// Reserved for future use.
-    Reserved,
+    /// A response code which is not known to this library, identified by its number.
+    Unknown(u16),
 }
 
 impl From<u16> for RCode {
@@ -196,7 +323,114 @@ impl From<u16> for RCode {
             8 => NXRRSET,
             9 => NOTAUTH,
             10 => NOTZONE,
-            _ => RCode::Reserved,
+            16 => BADVERS,
+            23 => BADCOOKIE,
+            _ => Unknown(code),
+        }
+    }
+}
+
+impl std::fmt::Display for RCode {
+    /// Formats the response code by its mnemonic, or as `RCODE` followed by its number if it has none.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use RCode::*;
+        let mnemonic = match self {
+            NoError => "NOERROR",
+            FormatError => "FORMERR",
+            ServerFailure => "SERVFAIL",
+            NameError => "NXDOMAIN",
+            NotImplemented => "NOTIMP",
+            Refused => "REFUSED",
+            YXDOMAIN => "YXDOMAIN",
+            YXRRSET => "YXRRSET",
+            NXRRSET => "NXRRSET",
+            NOTAUTH => "NOTAUTH",
+            NOTZONE => "NOTZONE",
+            BADVERS => "BADVERS",
+            BADCOOKIE => "BADCOOKIE",
+            Unknown(code) => return write!(f, "RCODE{}", code)
+        };
+
+        write!(f, "{}", mnemonic)
+    }
+}
+
+impl From<RCode> for u16 {
+    fn from(rcode: RCode) -> Self {
+        use RCode::*;
+        match rcode {
+            NoError => 0,
+            FormatError => 1,
+            ServerFailure => 2,
+            NameError => 3,
+            NotImplemented => 4,
+            Refused => 5,
+            YXDOMAIN => 6,
+            YXRRSET => 7,
+            NXRRSET => 8,
+            NOTAUTH => 9,
+            NOTZONE => 10,
+            BADVERS => 16,
+            BADCOOKIE => 23,
+            Unknown(code) => code,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flags, DNSHeader, OpCode, RCode};
+
+    #[test]
+    fn test_dns_header_flags() {
+        let mut header = DNSHeader::new();
+        assert!(header.is_query());
+
+        header.set_query(false);
+        header.set_opcode(OpCode::Update);
+        header.set_authoritative_answer(true);
+        header.set_truncated(true);
+        header.set_recursion_desired(true);
+        header.set_recursion_available(true);
+        header.set_reserved(true);
+        header.set_authentic_data(true);
+        header.set_checking_disabled(true);
+        header.set_response_code(RCode::NOTZONE);
+        assert_eq!(header.flags, 0xAFFA);
+        assert!(!header.is_query() && header.is_authoritative_answer() && header.is_truncated() && header.is_reserved());
+        assert!(header.is_recursion_desired() && header.is_recursion_available() && header.is_authentic_data() && header.is_checking_disabled());
+        assert_eq!(header.get_opcode(), OpCode::Update);
+        assert_eq!(header.get_response_code(), RCode::NOTZONE);
+
+        header.set_reserved(false);
+        header.set_truncated(false);
+        assert_eq!(header.flags, 0xADBA);
+
+        // Unknown codes keep their numbers.
+        header.set_opcode(OpCode::Unknown(9));
+        header.set_response_code(RCode::Unknown(13));
+        assert_eq!(header.get_opcode(), OpCode::Unknown(9));
+        assert_eq!(header.get_response_code(), RCode::Unknown(13));
+        assert_eq!(header.flags & flags::RCODE, 13);
+        assert_eq!(u16::from(RCode::from(3841)), 3841);
+
+        // Only the bits of the header are touched by an extended response code.
+        header.set_response_code(RCode::BADVERS);
+        assert_eq!(header.get_response_code(), RCode::NoError);
+        assert_eq!(header.get_opcode(), OpCode::Unknown(9));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 15")]
+    fn test_dns_header_opcode_too_large() {
+        // The opcode must not be masked into another one, e.g. 16 into a standard query.
+        DNSHeader::new().set_opcode(OpCode::Unknown(16));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 4095")]
+    fn test_dns_header_rcode_too_large() {
+        // The response code must not be masked into another one, e.g. 4096 into no error.
+        DNSHeader::new().set_response_code(RCode::Unknown(4096));
+    }
+}
@@ -3,18 +3,58 @@ pub mod question;
 pub mod resource_record;
 pub mod name;
 pub mod rdata;
+pub mod encoder;
+pub mod edns;
+pub mod builder;
+pub mod zone;
+pub mod tcp;
+pub mod xfr;
+pub mod update;
+mod presentation;
+#[cfg(feature = "dnssec")]
+pub mod dnssec;
 
 use std::mem::size_of;
 
+use byteorder::{ByteOrder, NetworkEndian};
 pub use header::*;
-use num_enum::TryFromPrimitive;
 pub use question::*;
 pub use resource_record::*;
 pub use name::Name;
+pub use encoder::{EncodeOptions, MessageEncoder};
+pub use edns::{ClientSubnet, Edns, EdnsOption};
+pub use builder::DNSLayerBuilder;
+pub use zone::{Zone, ZoneError};
+pub use tcp::{parse_tcp_stream, TcpDecoder};
+pub use xfr::{axfr_messages, ixfr_messages, IxfrDiff, Transfer, XfrError, XfrReader};
+pub use update::Update;
+use rdata::{RData, RRSIG};
 
+use crate::error::{ParseError, ParseErrorKind};
 use crate::packet::{Layer, LayerType};
 use crate::Raw;
 
+/// Returns `len` bytes of `bytes` starting at `offset`, or a truncation error attributed to `field`.
+pub(crate) fn read_slice<'a>(bytes: &'a [u8], offset: usize, len: usize, field: &'static str) -> Result<&'a [u8], ParseError> {
+    bytes.get(offset..offset + len)
+         .ok_or_else(|| ParseError::truncated("DNS", field, offset, len, bytes.len().saturating_sub(offset)))
+}
+
+/// Reads a single byte at `offset`.
+pub(crate) fn read_u8(bytes: &[u8], offset: usize, field: &'static str) -> Result<u8, ParseError> {
+    read_slice(bytes, offset, 1, field).map(|b| b[0])
+}
+
+/// Reads a u16 in network byte order at `offset`.
+pub(crate) fn read_u16(bytes: &[u8], offset: usize, field: &'static str) -> Result<u16, ParseError> {
+    read_slice(bytes, offset, size_of::<u16>(), field).map(NetworkEndian::read_u16)
+}
+
+/// Reads a u32 in network byte order at `offset`.
+pub(crate) fn read_u32(bytes: &[u8], offset: usize, field: &'static str) -> Result<u32, ParseError> {
+    read_slice(bytes, offset, size_of::<u32>(), field).map(NetworkEndian::read_u32)
+}
+
 /// A struct representing the DNS layer of a packet.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DNSLayer {
@@ -22,64 +62,118 @@ pub struct DNSLayer {
     questions: Vec<Question>,
     answers: Vec<ResourceRecord>,
     authority: Vec<ResourceRecord>,
-    additional: Vec<ResourceRecord>
+    additional: Vec<ResourceRecord>,
+    /// The OPT pseudo-record, which is kept apart from the other additional records.
+    edns: Option<Edns>
 }
 
 impl DNSLayer {
     /// Constructs a new DNS layer from the given values.
     pub fn new() -> Self{
         DNSLayer {
-            header: DNSHeader {
-                id: 0,
-                flags: 0,
-                questions_count: 0,
-                answers_count: 0,
-                name_servers_count: 0,
-                additional_records_count: 0
-            },
+            header: DNSHeader::new(),
             questions: Vec::new(),
             answers: Vec::new(),
             authority: Vec::new(),
-            additional: Vec::new()
+            additional: Vec::new(),
+            edns: None
         }
     }
 
+    /// Starts building a query for the given name and type.
+    /// Panics if the name is not valid, see [DNSLayerBuilder::query].
+    pub fn query(name: &str, qtype: Type) -> DNSLayerBuilder {
+        DNSLayerBuilder::query(name, qtype)
+    }
+
+    /// Starts building a query for the given name and type, failing if the name is not valid.
+    pub fn try_query(name: &str, qtype: Type) -> Result<DNSLayerBuilder, ParseError> {
+        DNSLayerBuilder::try_query(name, qtype)
+    }
+
+    /// Starts building a response to the given query.
+    pub fn response_to(query: &DNSLayer) -> DNSLayerBuilder {
+        DNSLayerBuilder::response_to(query)
+    }
+
     /// Constructs a new DNS layer from the given bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        let header = DNSHeader::from_bytes(bytes[0..size_of::<DNSHeader>()].try_into().unwrap());
+    /// The section counts of the header are kept as read, even if the message does not match them:
+    /// a section ends early if the message ends, and any records after the counted ones are parsed as additional records.
+    /// [DNSLayer::validate] reports such mismatches.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let header = DNSHeader::from_bytes(bytes)?;
 
-        let mut questions: Vec<Question> = Vec::with_capacity(header.questions_count as usize);
-        let mut answers: Vec<ResourceRecord> = Vec::with_capacity(header.answers_count as usize);
-        let mut authority: Vec<ResourceRecord> = Vec::with_capacity(header.name_servers_count as usize);
-        let mut additional: Vec<ResourceRecord> = Vec::with_capacity(header.additional_records_count as usize);
+        // The counts are not trusted to size the sections, since every entry takes up at least a few bytes
+        // of the message and a short message may claim thousands of them.
+        let mut questions: Vec<Question> = Vec::new();
+        let mut answers: Vec<ResourceRecord> = Vec::new();
+        let mut authority: Vec<ResourceRecord> = Vec::new();
+        let mut additional: Vec<ResourceRecord> = Vec::new();
 
+        // The prerequisite and update sections of dynamic updates are the answer and authority sections.
+        let update = header.get_opcode() == OpCode::Update;
         let mut start: usize = header.raw_size();
         
-        for i in 0..header.questions_count {
-            let q = Question::from_bytes(&bytes[start..]);
-            start += q.raw_size();
+        for _ in 0..header.questions_count {
+            if start == bytes.len() {
+                break;
+            }
+
+            let (q, size) = Question::from_message(bytes, start)?;
+            start += size;
             questions.push(q);
         }
 
-        for i in 0..header.answers_count {
-            let a = ResourceRecord::from_bytes(&bytes[start..]);
-            start += a.raw_size();
+        for _ in 0..header.answers_count {
+            if start == bytes.len() {
+                break;
+            }
+
+            let (a, size) = ResourceRecord::from_message_with(bytes, start, update)?;
+            start += size;
             answers.push(a);
         }
 
-        for i in 0..header.name_servers_count {
-            let auth = ResourceRecord::from_bytes(&bytes[start..]);
-            start += auth.raw_size();
+        for _ in 0..header.name_servers_count {
+            if start == bytes.len() {
+                break;
+            }
+
+            let (auth, size) = ResourceRecord::from_message_with(bytes, start, update)?;
+            start += size;
             authority.push(auth);
         }
 
-        for i in 0..header.additional_records_count {
-            let add = ResourceRecord::from_bytes(&bytes[start..]);
-            start += add.raw_size();
+        // The additional section takes every remaining record, whatever its count.
+        let mut edns: Option<Edns> = None;
+        while start < bytes.len() {
+            // Peek at the type to fold the OPT pseudo-record into `edns`.
+            let (_, name_size) = Name::from_message(bytes, start)?;
+            if Type::from(read_u16(bytes, start + name_size, "ResourceRecord.rtype")?) == Type::OPT {
+                if edns.is_some() {
+                    return Err(ParseError::new("DNS", "Edns", start, ParseErrorKind::Invalid("a message must not contain more than one OPT record")));
+                }
+
+                let (opt, size) = Edns::from_message(bytes, start)?;
+                start += size;
+                edns = Some(opt);
+                continue;
+            }
+
+            let (add, size) = ResourceRecord::from_message(bytes, start)?;
+            start += size;
             additional.push(add);
         }
 
-        DNSLayer { header, questions, answers, authority, additional }
+        Ok(DNSLayer { header, questions, answers, authority, additional, edns })
+    }
+
+    pub fn header(&self) -> &DNSHeader {
+        &self.header
+    }
+
+    pub fn header_mut(&mut self) -> &mut DNSHeader {
+        &mut self.header
     }
 
     pub fn questions(&self) -> &[Question] {
@@ -119,46 +213,314 @@ impl DNSLayer {
     /// Adds a question to the packet.
     pub fn add_question(&mut self, question: Question) {
         self.questions.push(question);
-        self.header.questions_count += 1;
+        self.header.questions_count = self.header.questions_count.saturating_add(1);
     }
 
     /// Removes a question from the packet.
     /// Returns the removed question.
     pub fn remove_question(&mut self, index: u16) -> Question {
+        self.header.questions_count = self.header.questions_count.saturating_sub(1);
         self.questions.remove(index as usize)
     }
 
     /// Adds an answer to the packet.
     pub fn add_answer(&mut self, answer: ResourceRecord) {
         self.answers.push(answer);
-        self.header.answers_count += 1;
+        self.header.answers_count = self.header.answers_count.saturating_add(1);
     }
 
     /// Removes an answer from the packet.
     /// Returns the removed answer.
     pub fn remove_answer(&mut self, index: u16) -> ResourceRecord {
+        self.header.answers_count = self.header.answers_count.saturating_sub(1);
         self.answers.remove(index as usize)
     }
 
     pub fn add_authority(&mut self, authority: ResourceRecord) {
         self.authority.push(authority);
-        self.header.name_servers_count += 1;
+        self.header.name_servers_count = self.header.name_servers_count.saturating_add(1);
     }
 
     /// Returns the removed authority entry.
     pub fn remove_authority(&mut self, index: u16) -> ResourceRecord {
-        self.answers.remove(index as usize)
+        self.header.name_servers_count = self.header.name_servers_count.saturating_sub(1);
+        self.authority.remove(index as usize)
     }
 
     pub fn add_additional(&mut self, additional: ResourceRecord) {
         self.additional.push(additional);
-        self.header.additional_records_count += 1;
+        self.header.additional_records_count = self.header.additional_records_count.saturating_add(1);
     }
 
     /// Returns the removed additional resource record.
     pub fn remove_additional(&mut self, index: u16) -> ResourceRecord {
+        self.header.additional_records_count = self.header.additional_records_count.saturating_sub(1);
         self.additional.remove(index as usize)
     }
+
+    /// Returns the EDNS information of the OPT record, if the packet has one.
+    pub fn edns(&self) -> Option<&Edns> {
+        self.edns.as_ref()
+    }
+
+    pub fn edns_mut(&mut self) -> Option<&mut Edns> {
+        self.edns.as_mut()
+    }
+
+    /// Adds, replaces or removes the OPT record, adjusting the count of additional records.
+    /// Returns the previous EDNS information.
+    pub fn set_edns(&mut self, edns: Option<Edns>) -> Option<Edns> {
+        match (&self.edns, &edns) {
+            (None, Some(_)) => self.header.additional_records_count = self.header.additional_records_count.saturating_add(1),
+            (Some(_), None) => self.header.additional_records_count = self.header.additional_records_count.saturating_sub(1),
+            _ => {}
+        }
+
+        std::mem::replace(&mut self.edns, edns)
+    }
+
+    /// Returns the OPT record, adding one with the default UDP payload size if the packet has none.
+    pub(crate) fn edns_or_default(&mut self) -> &mut Edns {
+        if self.edns.is_none() {
+            self.set_edns(Some(Edns::new(edns::DEFAULT_UDP_PAYLOAD_SIZE)));
+        }
+
+        self.edns.as_mut().unwrap()
+    }
+
+    /// Sets the response code of the packet.
+    /// Codes which do not fit into the 4 bits of the header store their upper bits in the OPT record, which is added if necessary.
+    /// Panics if the number of an unknown response code is greater than 4095, see [DNSHeader::set_response_code].
+    pub fn set_response_code(&mut self, rcode: RCode) {
+        let code = u16::from(rcode);
+        // Checks that the code fits into 12 bits before any of it is stored.
+        self.header.set_response_code(rcode);
+
+        if code > flags::RCODE {
+            self.edns_or_default().extended_rcode = (code >> 4) as u8;
+        } else if let Some(edns) = &mut self.edns {
+            edns.extended_rcode = 0;
+        }
+    }
+
+    /// Returns the response code of the packet.
+    /// If the packet has an OPT record, this is the 12-bit extended response code, otherwise the one in the header.
+    pub fn get_response_code(&self) -> RCode {
+        match &self.edns {
+            Some(edns) => edns.response_code(&self.header),
+            None => self.header.get_response_code()
+        }
+    }
+
+    /// Follows the CNAME records among the answers, starting at the given name.
+    /// Returns the targets in the order they were followed, so the last one is the canonical name.
+    /// A chain which loops ends before the first repeated name.
+    pub fn cname_chain(&self, name: &Name) -> Vec<&Name> {
+        let mut chain: Vec<&Name> = Vec::new();
+        let mut current = name;
+
+        while let Some(target) = self.answers.iter().find_map(|rr| match &rr.rdata {
+            RData::CNAME(target) if rr.name == *current => Some(target),
+            _ => None
+        }) {
+            if target == name || chain.contains(&target) {
+                break;
+            }
+
+            chain.push(target);
+            current = target;
+        }
+
+        chain
+    }
+
+    /// Returns the records with the given owner name and type from all sections, which form an RRset.
+    /// Owner names are compared case-insensitively.
+    pub fn rrset(&self, name: &Name, rtype: Type) -> Vec<&ResourceRecord> {
+        self.answers.iter().chain(&self.authority).chain(&self.additional)
+            .filter(|rr| rr.rtype == rtype && rr.name == *name)
+            .collect()
+    }
+
+    /// Returns the signatures over the RRset with the given owner name and type.
+    pub fn rrsigs(&self, name: &Name, type_covered: Type) -> Vec<&RRSIG> {
+        self.rrset(name, Type::RRSIG).into_iter()
+            .filter_map(|rr| match &rr.rdata {
+                RData::RRSIG(rrsig) if rrsig.type_covered == type_covered => Some(rrsig),
+                _ => None
+            })
+            .collect()
+    }
+
+    /// Returns the header with the section counts derived from the sections of the layer.
+    /// The OPT record counts as an additional record.
+    fn counted_header(&self) -> DNSHeader {
+        let count = |len: usize| u16::try_from(len).unwrap_or(u16::MAX);
+
+        DNSHeader {
+            questions_count: count(self.questions.len()),
+            answers_count: count(self.answers.len()),
+            name_servers_count: count(self.authority.len()),
+            additional_records_count: count(self.additional.len() + self.edns.iter().count()),
+            ..self.header
+        }
+    }
+
+    /// Sets the section counts of the header to the number of entries in each section.
+    pub fn update_counts(&mut self) {
+        self.header = self.counted_header();
+    }
+
+    /// Compares the section counts of the header with the sections of the layer.
+    /// Returns every section whose count does not match, either because the header was modified directly
+    /// or because the parsed message contained more or fewer entries than its header claimed.
+    pub fn validate(&self) -> Result<(), Vec<CountMismatch>> {
+        let counted = self.counted_header();
+        let mismatches: Vec<CountMismatch> = [
+            (Section::Question, self.header.questions_count, counted.questions_count),
+            (Section::Answer, self.header.answers_count, counted.answers_count),
+            (Section::Authority, self.header.name_servers_count, counted.name_servers_count),
+            (Section::Additional, self.header.additional_records_count, counted.additional_records_count)
+        ].into_iter()
+            .filter(|(_, header, actual)| header != actual)
+            .map(|(section, header, actual)| CountMismatch { section, header, actual })
+            .collect();
+
+        if mismatches.is_empty() { Ok(()) } else { Err(mismatches) }
+    }
+
+    /// Serializes the DNS layer with the given options.
+    /// `raw()` is equivalent to calling this with the default options, which compress names and derive the section counts.
+    /// Panics if the data of a record is longer than 65535 bytes, which no record can be.
+    pub fn raw_with(&self, options: EncodeOptions) -> Vec<u8> {
+        let mut encoder = MessageEncoder::new(options);
+
+        let header = if options.preserve_counts { self.header } else { self.counted_header() };
+        encoder.write(&header.raw());
+        for q in &self.questions {
+            q.encode(&mut encoder);
+        }
+        for rr in self.answers.iter().chain(&self.authority) {
+            rr.encode(&mut encoder);
+        }
+
+        // A TSIG record must stay the last record of the message, so the OPT record goes before it.
+        let tsig = self.additional.last().filter(|rr| rr.rtype == Type::TSIG);
+        let others = &self.additional[..self.additional.len() - tsig.iter().count()];
+        for rr in others {
+            rr.encode(&mut encoder);
+        }
+        if let Some(edns) = &self.edns {
+            edns.encode(&mut encoder);
+        }
+        if let Some(rr) = tsig {
+            rr.encode(&mut encoder);
+        }
+
+        encoder.into_bytes()
+    }
+
+    /// Serializes the DNS layer for DNS over TCP, prefixed with its length as two bytes
+    /// [RFC 1035 §4.2.2](https://datatracker.ietf.org/doc/html/rfc1035#section-4.2.2).
+    /// Panics if the message is longer than 65535 bytes.
+    pub fn raw_tcp(&self) -> Vec<u8> {
+        self.raw_tcp_with(EncodeOptions::default())
+    }
+
+    /// Serializes the DNS layer for DNS over TCP with the given options, see [DNSLayer::raw_tcp].
+    pub fn raw_tcp_with(&self, options: EncodeOptions) -> Vec<u8> {
+        let message = self.raw_with(options);
+        let length = u16::try_from(message.len()).expect("DNS messages over TCP may be at most 65535 bytes long.");
+
+        let mut bytes = Vec::with_capacity(size_of::<u16>() + message.len());
+        bytes.extend_from_slice(&length.to_be_bytes());
+        bytes.extend_from_slice(&message);
+
+        bytes
+    }
+}
+
+/// The sections of a DNS message following the header.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Section {
+    Question,
+    Answer,
+    Authority,
+    Additional
+}
+
+/// A section whose count in the header differs from the number of entries in it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CountMismatch {
+    pub section: Section,
+    /// The count stored in the header.
+    pub header: u16,
+    /// The number of entries in the section, including the OPT record for the additional section.
+    pub actual: u16
+}
+
+impl std::fmt::Display for CountMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the header counts {} {:?} entries, but the section has {}", self.header, self.section, self.actual)
+    }
+}
+
+impl std::fmt::Display for DNSLayer {
+    /// Formats the message like dig does: the header with its flags and counts, the OPT pseudo-section
+    /// and every non-empty section with one record per line in master file format.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let header = &self.header;
+        writeln!(f, ";; ->>HEADER<<- opcode: {}, status: {}, id: {}", header.get_opcode(), self.get_response_code(), header.id)?;
+
+        let flags = [
+            (!header.is_query(), "qr"),
+            (header.is_authoritative_answer(), "aa"),
+            (header.is_truncated(), "tc"),
+            (header.is_recursion_desired(), "rd"),
+            (header.is_recursion_available(), "ra"),
+            (header.is_reserved(), "z"),
+            (header.is_authentic_data(), "ad"),
+            (header.is_checking_disabled(), "cd")
+        ];
+        write!(f, ";; flags:")?;
+        for (_, flag) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, " {}", flag)?;
+        }
+        writeln!(f, "; QUERY: {}, ANSWER: {}, AUTHORITY: {}, ADDITIONAL: {}",
+                 header.questions_count, header.answers_count, header.name_servers_count, header.additional_records_count)?;
+
+        if let Some(edns) = &self.edns {
+            writeln!(f, "\n;; OPT PSEUDOSECTION:")?;
+            writeln!(f, "; EDNS: version: {}, flags:{}; udp: {}", edns.version, if edns.is_dnssec_ok() { " do" } else { "" }, edns.udp_payload_size)?;
+            for option in &edns.options {
+                writeln!(f, "; {}", option)?;
+            }
+        }
+
+        if !self.questions.is_empty() {
+            writeln!(f, "\n;; QUESTION SECTION:")?;
+            for question in &self.questions {
+                writeln!(f, ";{}", question)?;
+            }
+        }
+
+        for (title, records) in [("ANSWER", &self.answers), ("AUTHORITY", &self.authority), ("ADDITIONAL", &self.additional)] {
+            if !records.is_empty() {
+                writeln!(f, "\n;; {} SECTION:", title)?;
+                for record in records {
+                    writeln!(f, "{}", record)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for DNSLayer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Layer for DNSLayer {
@@ -178,143 +540,509 @@ impl Layer for DNSLayer {
 
     /// The payload of the DNS packet is everything without the DNS header.
     fn get_payload(&self) -> Vec<u8> {
-        let mut bytes:Vec<u8> = Vec::with_capacity(self.raw_size() - size_of::<DNSHeader>());
-
-        for q in &self.questions {
-            bytes.append(&mut q.raw());
-        }
-        for rr in &self.answers {
-            bytes.append(&mut rr.raw());
-        }
-        for auth in &self.authority {
-            bytes.append(&mut auth.raw());
-        }
-        for add in &self.additional {
-            bytes.append(&mut add.raw());
-        }
-
-        bytes
+        self.raw().split_off(size_of::<DNSHeader>())
     }
 }
 
 impl Raw for DNSLayer {
+    /// Panics if the data of a record is longer than 65535 bytes, see [DNSLayer::raw_with].
     fn raw(&self) -> Vec<u8> {
-        let mut bytes:Vec<u8> = Vec::with_capacity(self.raw_size());
-
-        bytes.append(&mut self.header.raw()); // header
-        bytes.append(&mut self.get_payload()); // payload
-
-        bytes
+        self.raw_with(EncodeOptions::default())
     }
 
+    /// The size depends on how well the names compress, so this serializes the whole layer.
     fn raw_size(&self) -> usize {
-        let mut size = size_of::<DNSHeader>();
+        self.raw().len()
+    }
+}
 
-        for q in &self.questions {
-            size += q.raw_size();
+/// Defines the Type enum from a list of variants and their IANA codes,
+/// together with the conversions to and from the numeric code.
+macro_rules! types {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal,)*) => {
+        /// Possible Type values for a Question or resource record in a DNS packet, numbered as assigned by
+        /// [IANA](https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-4).
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum Type {
+            $($(#[$doc])* $variant,)*
+            /// A type which is not known to this library, identified by its numeric code.  
+            /// Parsing never produces this variant for a code which has a named variant.
+            Unknown(u16)
         }
-        for rr in &self.answers {
-            size += rr.raw_size();
+
+        impl From<u16> for Type {
+            fn from(code: u16) -> Self {
+                match code {
+                    $($code => Type::$variant,)*
+                    _ => Type::Unknown(code)
+                }
+            }
         }
-        for auth in &self.authority {
-            size += auth.raw_size();
+
+        impl From<Type> for u16 {
+            fn from(rtype: Type) -> Self {
+                match rtype {
+                    $(Type::$variant => $code,)*
+                    Type::Unknown(code) => code
+                }
+            }
         }
-        for add in &self.additional {
-            size += add.raw_size();
+
+        impl std::str::FromStr for Type {
+            type Err = ParseError;
+
+            /// Parses a type mnemonic, ignoring case, or the generic `TYPE` followed by a code.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s.eq_ignore_ascii_case(stringify!($variant)) {
+                    return Ok(Type::$variant);
+                })*
+
+                s.get(..4).filter(|prefix| prefix.eq_ignore_ascii_case("TYPE"))
+                    .and_then(|_| s[4..].parse::<u16>().ok())
+                    .map(Type::from)
+                    .ok_or_else(|| ParseError::new("DNS", "Type", 0, ParseErrorKind::Invalid("unknown type")))
+            }
         }
 
-        size
-    }
+        impl std::fmt::Display for Type {
+            /// Formats the type by its mnemonic, or as `TYPE` followed by its code if it has none
+            /// [RFC 3597 §5](https://datatracker.ietf.org/doc/html/rfc3597#section-5).
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                match self {
+                    $(Type::$variant => write!(f, stringify!($variant)),)*
+                    Type::Unknown(code) => write!(f, "TYPE{}", code)
+                }
+            }
+        }
+    };
 }
 
-/// Possible Type values for a Question in a DNS packet  
-#[repr(u16)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, TryFromPrimitive)]
-pub enum Type {
+types! {
     /// Represents an IPv4 address
-    A = 0x0001,
-    /// Represents an IPv6 address. [RFC 3596](https://tools.ietf.org/html/rfc3596)
-    AAAA,
-    /// For servers with ASD cells
-    AFSDB,
+    A = 1,
+    /// An authoritative name server
+    NS = 2,
+    /// A mail destination (Obsolete - use MX), [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MD = 3,
+    /// A mail forwarder (Obsolete - use MX), [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MF = 4,
+    /// The canonical name for an alias
+    CNAME = 5,
+    /// Start of zone authority.
+    SOA = 6,
+    /// A mailbox domain name, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MB = 7,
+    /// A mail group member, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MG = 8,
+    /// A mail rename domain name, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MR = 9,
+    /// Used to represent arbitrary data.
+    NULL = 10,
+    /// Used to describe the well known services supported by a particular protocol on a particular internet address.
+    WKS = 11,
+    /// A domain name pointer, mostly used for reverse lookups
+    PTR = 12,
     /// Used to acquire general information about a host.  
     /// The main use is for protocols such as FTP that can use special procedures
     /// when talking between machines or operating systems of the same type.
-    HINFO,
-    /// An ISDN (Integrated Service Digital Network) - a telephone number
-    ISDN,
-    ///  For xpressing location information. [RFC 1876](https://datatracker.ietf.org/doc/html/rfc1876)
-    LOC,
+    HINFO = 13,
     /// Used to acquire mailbox or mail list information.
-    MINFO,
+    MINFO = 14,
     /// MX is used to acquire mail exchange information
-    MX,
-    /// NSAP structure [RFC 1706](https://datatracker.ietf.org/doc/html/rfc1706)
-    NSAP,
-    /// Used to represent arbitrary data.
-    NULL,
-    /// Route-through binding for hosts that do not have their own direct wide area network addresses
-    RT,
+    MX = 15,
+    /// A text record.
+    TXT = 16,
     /// RP Responsible Person [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-2.2)
-    RP,
-    /// Start of zone authority.
-    SOA,
+    RP = 17,
+    /// For servers with ASD cells
+    AFSDB = 18,
+    /// An X.25 PSDN address [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-3.1)
+    X25 = 19,
+    /// An ISDN (Integrated Service Digital Network) - a telephone number
+    ISDN = 20,
+    /// Route-through binding for hosts that do not have their own direct wide area network addresses
+    RT = 21,
+    /// NSAP structure [RFC 1706](https://datatracker.ietf.org/doc/html/rfc1706)
+    NSAP = 22,
+    /// A security signature (Obsolete - see RRSIG), [RFC 2535](https://datatracker.ietf.org/doc/html/rfc2535)
+    SIG = 24,
+    /// A security key (Obsolete - see DNSKEY), [RFC 2535](https://datatracker.ietf.org/doc/html/rfc2535)
+    KEY = 25,
+    /// X.400 mail mapping information [RFC 2163](https://datatracker.ietf.org/doc/html/rfc2163)
+    PX = 26,
+    /// Represents an IPv6 address. [RFC 3596](https://tools.ietf.org/html/rfc3596)
+    AAAA = 28,
+    ///  For xpressing location information. [RFC 1876](https://datatracker.ietf.org/doc/html/rfc1876)
+    LOC = 29,
+    /// Next domain (Obsolete - see NSEC), [RFC 2535](https://datatracker.ietf.org/doc/html/rfc2535)
+    NXT = 30,
     /// Specifies the location of the server(s) for a specific protocol and domain.
-    SRV,
-    /// A text record.
-    TXT,
-    /// Used to describe the well known services supported by a particular protocol on a particular internet address.
-    WKS,
+    SRV = 33,
+    /// Naming authority pointer [RFC 3403](https://datatracker.ietf.org/doc/html/rfc3403)
+    NAPTR = 35,
+    /// Key exchanger [RFC 2230](https://datatracker.ietf.org/doc/html/rfc2230)
+    KX = 36,
+    /// A certificate or certificate revocation list [RFC 4398](https://datatracker.ietf.org/doc/html/rfc4398)
+    CERT = 37,
+    /// Redirection of a whole subtree [RFC 6672](https://datatracker.ietf.org/doc/html/rfc6672)
+    DNAME = 39,
+    /// The EDNS(0) pseudo-record [RFC 6891](https://datatracker.ietf.org/doc/html/rfc6891)
+    OPT = 41,
+    /// Lists of address prefixes [RFC 3123](https://datatracker.ietf.org/doc/html/rfc3123)
+    APL = 42,
+    /// Delegation signer [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    DS = 43,
+    /// SSH key fingerprint [RFC 4255](https://datatracker.ietf.org/doc/html/rfc4255)
+    SSHFP = 44,
+    /// IPsec key [RFC 4025](https://datatracker.ietf.org/doc/html/rfc4025)
+    IPSECKEY = 45,
+    /// Signature over an RRset [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    RRSIG = 46,
+    /// Next secure record [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    NSEC = 47,
+    /// Public key of a zone [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    DNSKEY = 48,
+    /// DHCP identifier [RFC 4701](https://datatracker.ietf.org/doc/html/rfc4701)
+    DHCID = 49,
+    /// Hashed next secure record [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    NSEC3 = 50,
+    /// Parameters for NSEC3 [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    NSEC3PARAM = 51,
+    /// TLS certificate association [RFC 6698](https://datatracker.ietf.org/doc/html/rfc6698)
+    TLSA = 52,
+    /// S/MIME certificate association [RFC 8162](https://datatracker.ietf.org/doc/html/rfc8162)
+    SMIMEA = 53,
+    /// Host identity protocol [RFC 8005](https://datatracker.ietf.org/doc/html/rfc8005)
+    HIP = 55,
+    /// Child copy of DS [RFC 7344](https://datatracker.ietf.org/doc/html/rfc7344)
+    CDS = 59,
+    /// Child copy of DNSKEY [RFC 7344](https://datatracker.ietf.org/doc/html/rfc7344)
+    CDNSKEY = 60,
+    /// OpenPGP key [RFC 7929](https://datatracker.ietf.org/doc/html/rfc7929)
+    OPENPGPKEY = 61,
+    /// Child-to-parent synchronization [RFC 7477](https://datatracker.ietf.org/doc/html/rfc7477)
+    CSYNC = 62,
+    /// Message digest over zone data [RFC 8976](https://datatracker.ietf.org/doc/html/rfc8976)
+    ZONEMD = 63,
+    /// General purpose service binding [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460)
+    SVCB = 64,
+    /// Service binding for HTTPS origins [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460)
+    HTTPS = 65,
+    /// Sender policy framework (Obsolete - use TXT), [RFC 7208](https://datatracker.ietf.org/doc/html/rfc7208)
+    SPF = 99,
+    /// A 48-bit extended unique identifier [RFC 7043](https://datatracker.ietf.org/doc/html/rfc7043)
+    EUI48 = 108,
+    /// A 64-bit extended unique identifier [RFC 7043](https://datatracker.ietf.org/doc/html/rfc7043)
+    EUI64 = 109,
+    /// Transaction key [RFC 2930](https://datatracker.ietf.org/doc/html/rfc2930)
+    TKEY = 249,
+    /// Transaction signature [RFC 8945](https://datatracker.ietf.org/doc/html/rfc8945)
+    TSIG = 250,
     /// A request for incremental transfer of a zone. [RFC 1995](https://tools.ietf.org/html/rfc1995)
-    IXFR,
+    IXFR = 251,
     /// A request for a transfer of an entire zone, [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    AXFR,
+    AXFR = 252,
     /// A request for mailbox-related records (MB, MG or MR), [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    MAILB,
+    MAILB = 253,
     /// A request for mail agent RRs (Obsolete - see MX), [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    MAILA,
+    MAILA = 254,
     /// A request for all records, [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    ANY,
+    ANY = 255,
+    /// A URI [RFC 7553](https://datatracker.ietf.org/doc/html/rfc7553)
+    URI = 256,
+    /// Certification authority authorization [RFC 8659](https://datatracker.ietf.org/doc/html/rfc8659)
+    CAA = 257,
+    /// DNSSEC trust authorities
+    TA = 32768,
+    /// DNSSEC lookaside validation (Obsolete - see DS), [RFC 8749](https://datatracker.ietf.org/doc/html/rfc8749)
+    DLV = 32769,
 }
 
 
 /// Possible Class values for a resource in a DNS packet  
-#[repr(u16)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, TryFromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Class {
     /// The Internet, [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    IN = 1,
+    IN,
     /// The CSNET class (Obsolete - used only for examples in some obsolete RFCs), [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    CS = 2,
+    CS,
     /// The CHAOS class, [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    CH = 3,
+    CH,
     /// Hesiod [Dyer 87], [RFC 1035](https://tools.ietf.org/html/rfc1035)
-    HS = 4,
+    HS,
     /// [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136)
-    NONE = 254,
+    NONE,
+    /// Any class, used in queries and dynamic updates, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    ANY,
+    /// A class which is not known to this library, identified by its numeric code.  
+    /// This includes the classes of multicast DNS records with the cache-flush bit (0x8000) set.
+    /// Parsing never produces this variant for a code which has a named variant.
+    Unknown(u16)
+}
+
+impl From<u16> for Class {
+    fn from(code: u16) -> Self {
+        match code {
+            1 => Class::IN,
+            2 => Class::CS,
+            3 => Class::CH,
+            4 => Class::HS,
+            254 => Class::NONE,
+            255 => Class::ANY,
+            _ => Class::Unknown(code)
+        }
+    }
+}
+
+impl From<Class> for u16 {
+    fn from(class: Class) -> Self {
+        match class {
+            Class::IN => 1,
+            Class::CS => 2,
+            Class::CH => 3,
+            Class::HS => 4,
+            Class::NONE => 254,
+            Class::ANY => 255,
+            Class::Unknown(code) => code
+        }
+    }
+}
+
+impl std::str::FromStr for Class {
+    type Err = ParseError;
+
+    /// Parses a class mnemonic, ignoring case, or the generic `CLASS` followed by a number.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let class = match s.to_ascii_uppercase().as_str() {
+            "IN" => Class::IN,
+            "CS" => Class::CS,
+            "CH" => Class::CH,
+            "HS" => Class::HS,
+            "NONE" => Class::NONE,
+            "ANY" => Class::ANY,
+            generic => {
+                let code = generic.strip_prefix("CLASS").and_then(|code| code.parse::<u16>().ok())
+                    .ok_or_else(|| ParseError::new("DNS", "Class", 0, ParseErrorKind::Invalid("unknown class")))?;
+                Class::from(code)
+            }
+        };
+
+        Ok(class)
+    }
+}
+
+impl std::fmt::Display for Class {
+    /// Formats the class by its mnemonic, or as `CLASS` followed by its code if it has none
+    /// [RFC 3597 §5](https://datatracker.ietf.org/doc/html/rfc3597#section-5).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mnemonic = match self {
+            Class::IN => "IN",
+            Class::CS => "CS",
+            Class::CH => "CH",
+            Class::HS => "HS",
+            Class::NONE => "NONE",
+            Class::ANY => "ANY",
+            Class::Unknown(code) => return write!(f, "CLASS{}", code)
+        };
+
+        write!(f, "{}", mnemonic)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Raw, application::dns::DNSHeader, packet::{Layer, LayerType}};
+    use crate::{Raw, error::ParseErrorKind, packet::{Layer, LayerType}};
+    use crate::application::dns::{rdata::{RData, MX, NULL}, Class, Edns, EdnsOption, EncodeOptions, Name, Question, RCode, ResourceRecord, Type};
 
-    use super::DNSLayer;
+    use super::{CountMismatch, DNSLayer, Section};
 
     #[test]
     fn test_dns_name() {
         std::env::set_var("RUST_BACKTRACE", "full");
 
         let bytes = b"\xd2\x10\x01\x00\x00\x01\x00\x00\x00\x00\x00\x00\x20\x61\x62\x62\x38\x31\x65\x38\x39\x33\x36\x35\x62\x62\x36\x32\x35\x30\x61\x38\x63\x31\x62\x32\x62\x63\x34\x66\x31\x66\x66\x31\x64\x09\x73\x61\x66\x65\x66\x72\x61\x6d\x65\x11\x67\x6f\x6f\x67\x6c\x65\x73\x79\x6e\x64\x69\x63\x61\x74\x69\x6f\x6e\x03\x63\x6f\x6d\x00\x00\x01\x00\x01";
-        let layer = DNSLayer::from_bytes(bytes);
+        let layer = DNSLayer::from_bytes(bytes).unwrap();
         assert_eq!(&layer.raw(), bytes);
 
-        let layer1 = DNSLayer::from_bytes(&layer.raw());
+        let layer1 = DNSLayer::from_bytes(&layer.raw()).unwrap();
         assert_eq!(layer1, layer);
 
         assert_eq!(layer.get_name(), "DNS");
         assert_eq!(layer.get_osi_level(), 7);
         assert_eq!(layer.get_type(), LayerType::DNSLayer);
-        assert_eq!(layer.get_payload(), b"\x20\x61\x62\x62\x38\x31\x65\x38\x39\x33\x36\x35\x62\x62\x36\x32\x35\x30\x61\x38\x63\x31\x62\x32\x62\x63\x34\x66\x31\x66\x66\x31\x64\x09\x73\x61\x66\x65\x66\x72\x61\x6d\x65\x11\x67\x6f\x6f\x67\x6c\x65\x73\x79\x6e\x64\x69\x63\x61\x74\x69\x6f\x6e\x03\x63\x6f\x6d\x00\x00\x01\x00\x01")
+        assert_eq!(layer.get_payload(), b"\x20\x61\x62\x62\x38\x31\x65\x38\x39\x33\x36\x35\x62\x62\x36\x32\x35\x30\x61\x38\x63\x31\x62\x32\x62\x63\x34\x66\x31\x66\x66\x31\x64\x09\x73\x61\x66\x65\x66\x72\x61\x6d\x65\x11\x67\x6f\x6f\x67\x6c\x65\x73\x79\x6e\x64\x69\x63\x61\x74\x69\x6f\x6e\x03\x63\x6f\x6d\x00\x00\x01\x00\x01");
+
+        // Truncated packets are reported with the offset of the field which could not be read.
+        let err = DNSLayer::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!((err.field, err.offset), ("Question.class", bytes.len() - 2));
+        assert_eq!(err.kind, ParseErrorKind::Truncated { needed: 2, available: 1 });
+
+        let err = DNSLayer::from_bytes(&bytes[..5]).unwrap_err();
+        assert_eq!((err.field, err.offset), ("DNSHeader", 0));
+    }
+
+    #[test]
+    fn test_dns_compression() {
+        let mut layer = DNSLayer::new();
+        layer.add_question(Question::new(Name::new("www.example.com"), Type::MX, Class::IN, false));
+        layer.add_answer(ResourceRecord::new(Name::new("www.example.com"), Type::MX, Class::IN, 300, RData::MX(MX::new(10, Name::new("mail.example.com")))));
+
+        let uncompressed = layer.raw_with(EncodeOptions { compress_names: false, ..Default::default() });
+        let compressed = layer.raw();
+        assert_eq!(uncompressed.len(), 12 + 21 + 27 + 20);
+        assert_eq!(compressed.len(), 12 + 21 + 12 + 9);
+
+        // The answer owner points at the question name, the mail host at "example.com" within it.
+        assert_eq!(&compressed[33..35], b"\xC0\x0C");
+        assert_eq!(&compressed[43..54], b"\x00\x09\x00\x0A\x04mail\xC0\x10");
 
+        let parsed = DNSLayer::from_bytes(&compressed).unwrap();
+        assert_eq!(parsed.raw(), compressed);
+        assert_eq!(parsed.raw_with(EncodeOptions { compress_names: false, ..Default::default() }), uncompressed);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dns_type() {
+        assert_eq!(Type::from(28), Type::AAAA);
+        assert_eq!(u16::from(Type::MX), 15);
+        assert_eq!(u16::from(Type::HTTPS), 65);
+        assert_eq!(Type::from(65280), Type::Unknown(65280));
+        assert_eq!(u16::from(Type::Unknown(65280)), 65280);
+
+        // Questions for unknown types round-trip.
+        let bytes = b"\x07example\x03com\x00\xFF\x00\x00\x01";
+        let q = Question::from_bytes(bytes).unwrap();
+        assert_eq!(q.qtype, Type::Unknown(0xFF00));
+        assert_eq!(&q.raw(), bytes);
+    }
+
+    #[test]
+    fn test_cname_chain() {
+        let mut layer = DNSLayer::new();
+        layer.add_answer(ResourceRecord::new(Name::new("www.example.com"), Type::CNAME, Class::IN, 300, RData::CNAME(Name::new("cdn.example.net"))));
+        layer.add_answer(ResourceRecord::new(Name::new("cdn.example.net"), Type::CNAME, Class::IN, 300, RData::CNAME(Name::new("edge.example.org"))));
+        layer.add_answer(ResourceRecord::new(Name::new("edge.example.org"), Type::A, Class::IN, 300, RData::A("192.0.2.1".parse().unwrap())));
+
+        assert_eq!(layer.cname_chain(&Name::new("www.example.com")), vec![&Name::new("cdn.example.net"), &Name::new("edge.example.org")]);
+        assert!(layer.cname_chain(&Name::new("edge.example.org")).is_empty());
+
+        // The chain survives a round trip with compression.
+        let parsed = DNSLayer::from_bytes(&layer.raw()).unwrap();
+        assert_eq!(parsed, layer);
+
+        // Loops are cut off.
+        layer.add_answer(ResourceRecord::new(Name::new("edge.example.org"), Type::CNAME, Class::IN, 300, RData::CNAME(Name::new("cdn.example.net"))));
+        assert_eq!(layer.cname_chain(&Name::new("www.example.com")), vec![&Name::new("cdn.example.net"), &Name::new("edge.example.org")]);
+    }
+
+    #[test]
+    fn test_dns_counts() {
+        let a = |address: &str| ResourceRecord::new(Name::new("example.com"), Type::A, Class::IN, 300, RData::A(address.parse().unwrap()));
+        let ns = ResourceRecord::new(Name::new("example.com"), Type::NS, Class::IN, 300, RData::NS(Name::new("ns.example.com")));
+
+        let mut layer = DNSLayer::new();
+        layer.add_question(Question::new(Name::new("example.com"), Type::A, Class::IN, false));
+        layer.add_answer(a("192.0.2.1"));
+        layer.add_answer(a("192.0.2.2"));
+        layer.add_authority(ns.clone());
+        layer.add_authority(ns);
+        assert_eq!(layer.validate(), Ok(()));
+
+        // Removing an authority record leaves the answers alone.
+        layer.remove_authority(1);
+        assert_eq!((layer.asnwers().len(), layer.authority().len()), (2, 1));
+        assert_eq!((layer.header().answers_count, layer.header().name_servers_count), (2, 1));
+        layer.remove_answer(0);
+        assert_eq!(layer.header().answers_count, 1);
+
+        // Counts changed behind the back of the layer are reported, but not serialized unless asked for.
+        layer.header_mut().answers_count = 5;
+        layer.header_mut().additional_records_count = 1;
+        assert_eq!(layer.validate(), Err(vec![
+            CountMismatch { section: Section::Answer, header: 5, actual: 1 },
+            CountMismatch { section: Section::Additional, header: 1, actual: 0 }
+        ]));
+        assert_eq!(&layer.raw()[4..12], b"\x00\x01\x00\x01\x00\x01\x00\x00");
+        assert_eq!(&layer.raw_with(EncodeOptions { preserve_counts: true, ..Default::default() })[4..12], b"\x00\x01\x00\x05\x00\x01\x00\x01");
+
+        let parsed = DNSLayer::from_bytes(&layer.raw()).unwrap();
+        assert_eq!(parsed.validate(), Ok(()));
+
+        layer.update_counts();
+        assert_eq!(layer.validate(), Ok(()));
+        assert_eq!(parsed, layer);
+
+        // Counts which are already at their maximum stay there.
+        layer.header_mut().answers_count = u16::MAX;
+        layer.header_mut().additional_records_count = u16::MAX;
+        layer.add_answer(a("192.0.2.3"));
+        layer.set_edns(Some(Edns::new(1232)));
+        assert_eq!((layer.header().answers_count, layer.header().additional_records_count), (u16::MAX, u16::MAX));
+    }
+
+    #[test]
+    fn test_dns_counts_mismatch() {
+        // A response for example.com A which claims two answers but contains one.
+        let response = b"\x12\x34\x81\x80\x00\x01\x00\x02\x00\x00\x00\x00\
+                         \x07example\x03com\x00\x00\x01\x00\x01\
+                         \xC0\x0C\x00\x01\x00\x01\x00\x00\x0E\x10\x00\x04\x5D\xB8\xD8\x22";
+        let layer = DNSLayer::from_bytes(response).unwrap();
+        assert_eq!((layer.header().answers_count, layer.asnwers().len()), (2, 1));
+        assert_eq!(layer.validate(), Err(vec![CountMismatch { section: Section::Answer, header: 2, actual: 1 }]));
+        assert_eq!(layer.raw_with(EncodeOptions { preserve_counts: true, ..Default::default() }), response);
+
+        // A record past the counted ones ends up in the additional section.
+        let mut response = response.to_vec();
+        response.extend_from_slice(b"\xC0\x0C\x00\x01\x00\x01\x00\x00\x0E\x10\x00\x04\xC0\x00\x02\x01");
+        response[7] = 1;
+        let layer = DNSLayer::from_bytes(&response).unwrap();
+        assert_eq!((layer.asnwers().len(), layer.additional().len()), (1, 1));
+        assert_eq!(layer.validate(), Err(vec![CountMismatch { section: Section::Additional, header: 0, actual: 1 }]));
+
+        // A message which ends within a record is still truncated.
+        let err = DNSLayer::from_bytes(&response[..response.len() - 1]).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Truncated { needed: 4, available: 3 });
+    }
+
+    #[test]
+    fn test_dns_display() {
+        let query = DNSLayer::query("example.com", Type::MX).id(4660).recursion_desired(true).dnssec_ok(true).build();
+        let mx = ResourceRecord::new(Name::new("example.com"), Type::MX, Class::IN, 300, RData::MX(MX::new(10, Name::new("mail.example.com"))));
+        let ns = ResourceRecord::new(Name::new("example.com"), Type::NS, Class::IN, 3600, RData::NS(Name::new("ns.example.com")));
+
+        assert_eq!(query.questions()[0].to_string(), "example.com. IN MX");
+        assert_eq!(mx.to_string(), "example.com. 300 IN MX 10 mail.example.com.");
+
+        let mut response = DNSLayer::response_to(&query).recursion_available(true).answer(mx).authority(ns).build();
+        response.edns_mut().unwrap().options.push(EdnsOption::ExtendedError { info_code: 18, extra_text: String::new() });
+        assert_eq!(response.to_string(), "\
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 4660
+;; flags: qr rd ra; QUERY: 1, ANSWER: 1, AUTHORITY: 1, ADDITIONAL: 1
+
+;; OPT PSEUDOSECTION:
+; EDNS: version: 0, flags: do; udp: 1232
+; EDE: 18
+
+;; QUESTION SECTION:
+;example.com. IN MX
+
+;; ANSWER SECTION:
+example.com. 300 IN MX 10 mail.example.com.
+
+;; AUTHORITY SECTION:
+example.com. 3600 IN NS ns.example.com.
+");
+
+        let response = DNSLayer::response_to(&query).response_code(RCode::BADCOOKIE).build();
+        assert!(response.to_string().starts_with(";; ->>HEADER<<- opcode: QUERY, status: BADCOOKIE, id: 4660\n;; flags: qr rd;"));
+        assert_eq!(Type::Unknown(65280).to_string(), "TYPE65280");
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 65535 bytes")]
+    fn test_dns_layer_record_too_long() {
+        // Record data which does not fit in rlength must not be written with a wrapped length.
+        let null = ResourceRecord::new(Name::new("example.com"), Type::NULL, Class::IN, 300, RData::NULL(NULL::new(vec![0; 70000])));
+        DNSLayer::query("example.com", Type::NULL).answer(null).build().raw();
+    }
+}
@@ -1,33 +1,107 @@
+use std::cmp::Ordering;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::error::{ParseError, ParseErrorKind};
 use crate::Raw;
 
-/// Represents a label from a DNS resource name.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+use super::{read_slice, read_u8, read_u16};
+use super::presentation::unescape;
+
+/// Represents a label from a DNS resource name, which may contain any bytes [RFC 2181 §11](https://datatracker.ietf.org/doc/html/rfc2181#section-11).
+/// Labels are compared without regard to the case of ASCII letters [RFC 4343](https://datatracker.ietf.org/doc/html/rfc4343).
+#[derive(Debug, Clone)]
 pub struct Label {
-    /// DNS name labels may be at most 255 in length.
-    length: u8, 
-    contents: String
+    /// Between 1 and [Label::MAX_LENGTH] bytes, as only the root label is empty and it is not stored.
+    contents: Vec<u8>
 }
 
 impl Label {
-    fn new(mut contents: String) -> Self {
-        contents = contents.replace('.', "");
-        Label { length: u8::try_from(contents.len()).expect("DNS name labels may be at most 255 in length."), contents }
+    /// The maximum length of a label in bytes [RFC 1035 §2.3.4](https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.4).
+    pub const MAX_LENGTH: usize = 63;
+
+    /// Constructs a label from its contents, which may contain any bytes including dots.
+    /// Fails if the contents are empty or longer than [Label::MAX_LENGTH] bytes.
+    pub fn new(contents: impl Into<Vec<u8>>) -> Result<Self, ParseError> {
+        let contents = contents.into();
+
+        if contents.is_empty() {
+            return Err(ParseError::new("DNS", "Label", 0, ParseErrorKind::Invalid("empty label")));
+        }
+        if contents.len() > Label::MAX_LENGTH {
+            return Err(ParseError::new("DNS", "Label", 0, ParseErrorKind::BadLength(contents.len())));
+        }
+
+        Ok(Label { contents })
     }
 
-    fn from_bytes(bytes: &[u8]) -> Self {
+    /// Parses a length-prefixed label from the start of the given bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let length = read_u8(bytes, 0, "Label.length")?;
 
-        if bytes.len() < 2 { panic!("Insufficient bytes to create DNS name label!"); }
+        // The two upper bits of the length byte denote the label type; only 0b00 is a plain label.
+        if length & 0xC0 != 0 {
+            return Err(ParseError::new("DNS", "Label.length", 0, ParseErrorKind::BadLabelType(length)));
+        }
 
-        Label { 
-            length: bytes[0], 
-            contents: String::from_utf8_lossy(&bytes[1..]).to_string() 
+        // The root label ends a name rather than being part of it.
+        if length == 0 {
+            return Err(ParseError::new("DNS", "Label.length", 0, ParseErrorKind::Invalid("empty label")));
         }
+
+        Ok(Label { contents: read_slice(bytes, 1, usize::from(length), "Label.contents")?.to_vec() })
+    }
+
+    /// Returns the contents of the label without any escaping.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.contents
+    }
+}
+
+impl PartialEq for Label {
+    fn eq(&self, other: &Self) -> bool {
+        self.contents.eq_ignore_ascii_case(&other.contents)
+    }
+}
+
+impl Eq for Label {}
+
+impl std::hash::Hash for Label {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.contents.len());
+        for byte in &self.contents {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+    }
+}
+
+impl PartialOrd for Label {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Label {
+    /// Compares the labels as strings of bytes with ASCII letters in lowercase, so that a label sorts before the labels it is a prefix of.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.contents.iter().map(u8::to_ascii_lowercase).cmp(other.contents.iter().map(u8::to_ascii_lowercase))
     }
 }
 
 impl std::fmt::Display for Label {
+    /// Formats the label in presentation format: dots and characters with a special meaning in master files
+    /// are escaped with a backslash, bytes which are not printable ASCII are written as `\DDD`
+    /// [RFC 4343 §2.1](https://datatracker.ietf.org/doc/html/rfc4343#section-2.1).
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.contents)
+        for &byte in &self.contents {
+            match byte {
+                b'.' | b'\\' | b'"' | b'(' | b')' | b';' | b'@' | b'$' => write!(f, "\\{}", byte as char)?,
+                0x21..=0x7E => write!(f, "{}", byte as char)?,
+                _ => write!(f, "\\{:03}", byte)?
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -35,54 +109,206 @@ impl Raw for Label {
     fn raw(&self) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(self.raw_size());
 
-        bytes.push(self.length);
-        bytes.extend_from_slice(self.contents.as_bytes());
+        bytes.push(self.contents.len() as u8);
+        bytes.extend_from_slice(&self.contents);
 
         bytes
     }
 
     fn raw_size(&self) -> usize {
         // Just the raw string's size plus one byte for its length
-        usize::from(self.length) + 1
+        self.contents.len() + 1
     }
 }
 
 /// A DNS resource name comprised of labels.
+/// Names are fully qualified, i.e. end with the root, unless they are relative names from [Name::parse_relative]
+/// or [Name::from_relative_labels], which still need an origin appended with [Name::append].
+/// Names in messages are always fully qualified, so a relative name is written as if it ended with the root.
+/// Names are equal if their labels are equal without regard to case and both or neither are fully qualified,
+/// and are ordered in the canonical order of DNSSEC.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Name {
-    labels: Vec<Label>
+    labels: Vec<Label>,
+    absolute: bool
 }
 
 impl Name {
-    /// Constructs a DNS resource name from a string.
+    /// The maximum length of a name in wire format, counting the length bytes of its labels and the root label
+    /// [RFC 1035 §2.3.4](https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.4).
+    pub const MAX_LENGTH: usize = 255;
+
+    /// Constructs a DNS resource name from a string of labels separated by dots, e.g. `www.example.com`.
+    /// The name is always fully qualified, so the trailing dot is optional and both `.` and the empty string are the root.
+    /// Panics if a label is empty or longer than 63 bytes, or if the name is longer than 255 bytes.
+    /// Use [Name::from_str] or [Name::parse_with_origin] for names with escapes, relative names and untrusted input.
     pub fn new(s: &str) -> Name {
-        // DNS names usually have 2-3 labels: example.com, other.example.com
-        let mut labels: Vec<Label> = Vec::with_capacity(3);
-        for l in s.split('.') {
-            labels.push(Label::new(l.to_string()));
+        let s = s.strip_suffix('.').unwrap_or(s);
+        if s.is_empty() {
+            return Name::root();
         }
 
-        Name { labels }
+        let labels = s.split('.').map(|l| Label::new(l).expect("DNS name labels must be between 1 and 63 bytes long.")).collect();
+        Name::from_labels(labels).expect("DNS names may be at most 255 bytes long.")
     }
 
     /// Constructs a DNS resource name from the given bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Self {
+    /// The name must be terminated by a null byte. Compression pointers are resolved relative to the start of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Name::from_message(bytes, 0).map(|(name, _)| name)
+    }
+
+    /// Constructs a DNS resource name starting at `offset` in the given DNS message,
+    /// following any compression pointers [RFC 1035 §4.1.4](https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.4).
+    /// Returns the name together with the number of bytes it occupies at `offset`.
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
         let mut labels: Vec<Label> = Vec::new();
 
+        let mut i = offset;
+        // The start of the run of labels currently being read. Every pointer must jump strictly before it,
+        // which guarantees that decoding terminates.
+        let mut run_start = offset;
+        // The size of the name at `offset`, which is fixed as soon as the first pointer is followed.
+        let mut size = None;
+
+        loop {
+            let length = read_u8(message, i, "Name")?;
+
+            if length == 0 {
+                break;
+            }
+
+            if length & 0xC0 == 0xC0 {
+                let target = usize::from(read_u16(message, i, "Name.pointer")? & 0x3FFF);
+
+                if target >= i {
+                    return Err(ParseError::new("DNS", "Name.pointer", i, ParseErrorKind::ForwardPointer(target)));
+                }
+                if target >= run_start {
+                    return Err(ParseError::new("DNS", "Name.pointer", i, ParseErrorKind::PointerLoop(target)));
+                }
+
+                size.get_or_insert_with(|| i + 2 - offset);
+                run_start = target;
+                i = target;
+                continue;
+            }
+
+            let label = Label::from_bytes(&message[i..]).map_err(|e| e.offset_by(i))?;
+            i += label.raw_size();
+            labels.push(label);
+
+            let length = Name::wire_length(&labels);
+            if length > Name::MAX_LENGTH {
+                return Err(ParseError::new("DNS", "Name", offset, ParseErrorKind::BadLength(length)));
+            }
+        }
+
+        Ok((Name { labels, absolute: true }, size.unwrap_or_else(|| i + 1 - offset)))
+    }
+
+    /// Returns the root name, which has no labels.
+    pub fn root() -> Self {
+        Name { labels: Vec::new(), absolute: true }
+    }
+
+    /// Parses a name in presentation format, in which labels may contain escaped dots and `\DDD` escapes.
+    /// A name which does not end with a dot is relative and gets `origin` appended, and `@` stands for `origin` itself
+    /// [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+    pub fn parse_with_origin(s: &str, origin: &Name) -> Result<Self, ParseError> {
+        if s == "@" {
+            return Ok(origin.clone());
+        }
+
+        let name = Name::parse_relative(s)?;
+        match name.absolute {
+            true => Ok(name),
+            false => name.append(origin)
+        }
+    }
+
+    /// Parses a name in presentation format like [Name::parse_with_origin], but keeps a name which does not end with a dot relative
+    /// instead of appending an origin to it.
+    pub fn parse_relative(s: &str) -> Result<Self, ParseError> {
+        let error = |offset: usize, kind: ParseErrorKind| ParseError::new("DNS", "Name", offset, kind);
+
+        match s {
+            "" => return Err(error(0, ParseErrorKind::Invalid("empty name"))),
+            "@" => return Err(error(0, ParseErrorKind::Invalid("@ stands for an origin, which a relative name does not have"))),
+            "." => return Ok(Name::root()),
+            _ => {}
+        }
+
+        let bytes = s.as_bytes();
+        let mut labels = Vec::new();
+        let mut start = 0;
         let mut i = 0;
 
-        while i < bytes.len() && bytes[i] != 0{
-            let contents = String::from_utf8_lossy(&bytes[i + 1..i + bytes[i] as usize + 1]).to_owned();
-            labels.push(Label::new(contents.to_string()));
-            i += bytes[i] as usize + 1;
+        loop {
+            // Escaped characters never end a label.
+            while i < bytes.len() && bytes[i] != b'.' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+
+            let contents = unescape(&s[start..i.min(bytes.len())], "Name").map_err(|e| e.offset_by(start))?;
+            if contents.is_empty() {
+                return Err(error(start, ParseErrorKind::Invalid("empty label")));
+            }
+            if contents.len() > Label::MAX_LENGTH {
+                return Err(error(start, ParseErrorKind::BadLength(contents.len())));
+            }
+            labels.push(Label { contents });
+
+            if i >= bytes.len() {
+                return Name::from_relative_labels(labels);
+            }
+
+            // Skips the dot; a dot at the very end makes the name absolute.
+            i += 1;
+            start = i;
+            if i == bytes.len() {
+                break;
+            }
         }
 
-        Name { labels }
+        Name::from_labels(labels)
     }
 
-    /// Constructs a DNS resource name from the given labels.
-    pub fn from_labels(labels: Vec<Label>) -> Self {
-        Name { labels }
+    /// Constructs a DNS resource name from the given labels, failing if the name is longer than [Name::MAX_LENGTH] bytes.
+    pub fn from_labels(labels: Vec<Label>) -> Result<Self, ParseError> {
+        let length = Name::wire_length(&labels);
+        if length > Name::MAX_LENGTH {
+            return Err(ParseError::new("DNS", "Name", 0, ParseErrorKind::BadLength(length)));
+        }
+
+        Ok(Name { labels, absolute: true })
+    }
+
+    /// Constructs a relative name from the given labels, failing if there are none
+    /// or if the name would be longer than [Name::MAX_LENGTH] bytes once fully qualified.
+    pub fn from_relative_labels(labels: Vec<Label>) -> Result<Self, ParseError> {
+        if labels.is_empty() {
+            return Err(ParseError::new("DNS", "Name", 0, ParseErrorKind::Invalid("a relative name must have at least one label")));
+        }
+
+        Ok(Name { absolute: false, ..Name::from_labels(labels)? })
+    }
+
+    /// Returns the name followed by the labels of `suffix`, e.g. to qualify a name which is relative to `suffix`.
+    /// The result is fully qualified if `suffix` is.
+    /// Fails if the result is longer than [Name::MAX_LENGTH] bytes.
+    pub fn append(&self, suffix: &Name) -> Result<Name, ParseError> {
+        let labels = self.labels.iter().chain(&suffix.labels).cloned().collect();
+        Ok(Name { absolute: suffix.absolute, ..Name::from_labels(labels)? })
+    }
+
+    /// Whether the name is fully qualified, i.e. ends with the root, rather than relative to an origin.
+    pub fn is_fqdn(&self) -> bool {
+        self.absolute
+    }
+
+    fn wire_length(labels: &[Label]) -> usize {
+        labels.iter().map(Raw::raw_size).sum::<usize>() + 1
     }
 
     /// Returns a reference to the labels of the DNS resource name.
@@ -90,18 +316,177 @@ impl Name {
         &self.labels
     }
 
-    /// Returns the length of the DNS name as a string including the "." separators.
+    /// Returns the length of the DNS name as a string including the "." separators, without escaping.
+    /// The root name has a length of 1 for its single dot.
     pub fn str_len(&self) -> usize {
         let mut length: usize = 0;
         for label in &self.labels {
-            length += usize::from(label.length) + 1; // +1 for the separator after each label.
+            length += label.contents.len() + 1; // +1 for the separator after each label.
+        }
+
+        // -1 because the last label does not actually have a separator following it.
+        length.saturating_sub(1).max(1)
+    }
+
+    /// Returns the name in presentation format as it is written in master files, e.g. `example.com.`,
+    /// which has a trailing dot if the name is fully qualified.
+    pub fn to_fqdn(&self) -> String {
+        match (self.labels.is_empty(), self.absolute) {
+            (true, _) => String::from("."),
+            (false, true) => format!("{}.", self),
+            (false, false) => self.to_string()
+        }
+    }
+
+    /// Constructs a name from a hostname which may contain Unicode characters, e.g. `bücher.example`.
+    /// The hostname is mapped as by [UTS #46](https://www.unicode.org/reports/tr46/), which also lowercases it,
+    /// and labels with characters outside of ASCII are encoded as A-labels with Punycode, e.g. `xn--bcher-kva`
+    /// [RFC 5891 §4](https://datatracker.ietf.org/doc/html/rfc5891#section-4).
+    #[cfg(feature = "idna")]
+    pub fn from_unicode(s: &str) -> Result<Name, ParseError> {
+        let ascii = idna::domain_to_ascii(s)
+            .map_err(|_| ParseError::new("DNS", "Name", 0, ParseErrorKind::Invalid("invalid internationalized name")))?;
+
+        // The mapped name contains no escapes, so it is split on dots like by Name::new.
+        let ascii = ascii.strip_suffix('.').unwrap_or(&ascii);
+        if ascii.is_empty() {
+            return Ok(Name::root());
+        }
+
+        Name::from_labels(ascii.split('.').map(Label::new).collect::<Result<_, _>>()?)
+    }
+
+    /// Returns the name for display with its A-labels decoded to Unicode, e.g. `bücher.example` for `xn--bcher-kva.example`.
+    /// Other labels, and A-labels which are not valid Punycode, are written in presentation format as by the [Display](std::fmt::Display) implementation.
+    #[cfg(feature = "idna")]
+    pub fn to_unicode(&self) -> String {
+        if self.labels.is_empty() {
+            return String::from(".");
+        }
+
+        let labels: Vec<String> = self.labels.iter()
+            .map(|label| match std::str::from_utf8(&label.contents) {
+                // A dot within a label would split it into several labels when decoding.
+                Ok(text) if text.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("xn--")) && !text.contains('.') => {
+                    match idna::domain_to_unicode(text) {
+                        (unicode, Ok(())) => unicode,
+                        (_, Err(_)) => label.to_string()
+                    }
+                },
+                _ => label.to_string()
+            })
+            .collect();
+
+        labels.join(".")
+    }
+
+    /// Returns a copy of the name with all ASCII letters in lowercase, which is the form used for DNSSEC
+    /// [RFC 4034 §6.2](https://datatracker.ietf.org/doc/html/rfc4034#section-6.2).
+    pub fn to_lowercase(&self) -> Name {
+        Name { labels: self.labels.iter().map(|l| Label { contents: l.contents.to_ascii_lowercase() }).collect(), absolute: self.absolute }
+    }
+
+    /// Compares two names in the canonical order of DNSSEC: label by label starting from the rightmost one,
+    /// with each label compared as lowercase bytes [RFC 4034 §6.1](https://datatracker.ietf.org/doc/html/rfc4034#section-6.1).
+    /// This is the order of the [Ord] implementation, which also puts relative names before fully qualified ones with the same labels.
+    pub fn canonical_cmp(&self, other: &Name) -> Ordering {
+        self.labels.iter().rev().cmp(other.labels.iter().rev())
+    }
+
+    /// Returns the number of labels of the name, not counting the empty label of the root.
+    pub fn label_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Whether the leftmost label is an asterisk, which makes the name a wildcard
+    /// [RFC 4592 §2.1.1](https://datatracker.ietf.org/doc/html/rfc4592#section-2.1.1).
+    pub fn is_wildcard(&self) -> bool {
+        self.labels.first().is_some_and(|label| label.contents == b"*")
+    }
+
+    /// Whether the name is equal to `other` or below it, e.g. www.example.com is a subdomain of example.com.
+    /// A relative name is only a subdomain of relative names and a fully qualified name only of fully qualified ones.
+    pub fn is_subdomain_of(&self, other: &Name) -> bool {
+        self.absolute == other.absolute && self.labels.len() >= other.labels.len() && self.labels[self.labels.len() - other.labels.len()..] == other.labels[..]
+    }
+
+    /// Returns the name without its leftmost label, or None for the root and relative names of a single label.
+    pub fn parent(&self) -> Option<Name> {
+        match self.labels.len() > usize::from(!self.absolute) {
+            true => Some(Name { labels: self.labels[1..].to_vec(), absolute: self.absolute }),
+            false => None
+        }
+    }
+
+    /// Returns the names at which a zone cut may lie above or at this name, from the root down to the name itself,
+    /// e.g. the root, com, example.com and www.example.com for www.example.com.
+    /// This is the order in which a resolver follows delegations to the zone of the name.
+    /// For relative names, the names start at the rightmost label instead of the root.
+    pub fn zone_cuts(&self) -> impl Iterator<Item = Name> + '_ {
+        (0..=self.labels.len() - usize::from(!self.absolute)).rev().map(|i| Name { labels: self.labels[i..].to_vec(), absolute: self.absolute })
+    }
+
+    /// Constructs the name under which PTR records for the given address are found,
+    /// e.g. 4.3.2.1.in-addr.arpa for 1.2.3.4 [RFC 1035 §3.5](https://datatracker.ietf.org/doc/html/rfc1035#section-3.5)
+    /// or the nibble form under ip6.arpa for IPv6 addresses [RFC 3596 §2.5](https://datatracker.ietf.org/doc/html/rfc3596#section-2.5).
+    pub fn from_ip(addr: IpAddr) -> Self {
+        let mut labels: Vec<Label> = match addr {
+            IpAddr::V4(v4) => v4.octets().iter().rev().map(|octet| Label { contents: octet.to_string().into_bytes() }).collect(),
+            IpAddr::V6(v6) => v6.octets().iter().rev()
+                                .flat_map(|octet| [octet & 0x0F, octet >> 4])
+                                .map(|nibble| Label { contents: format!("{:x}", nibble).into_bytes() })
+                                .collect()
+        };
+
+        let suffix = if addr.is_ipv4() { ["in-addr", "arpa"] } else { ["ip6", "arpa"] };
+        labels.extend(suffix.iter().map(|l| Label { contents: l.as_bytes().to_vec() }));
+
+        Name { labels, absolute: true }
+    }
+
+    /// Returns the address which this name refers to if it is a name for reverse lookups,
+    /// i.e. the inverse of [Name::from_ip].
+    pub fn to_ip(&self) -> Option<IpAddr> {
+        let labels: Vec<String> = self.labels.iter().map(|l| String::from_utf8(l.contents.to_ascii_lowercase()).ok()).collect::<Option<_>>()?;
+
+        match labels.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+            [d, c, b, a, "in-addr", "arpa"] => {
+                let octets = [a.parse().ok()?, b.parse().ok()?, c.parse().ok()?, d.parse().ok()?];
+                Some(IpAddr::V4(Ipv4Addr::from(octets)))
+            },
+            ref nibbles if nibbles.len() == 34 && nibbles[32..] == ["ip6", "arpa"] => {
+                let mut octets = [0u8; 16];
+                for (i, nibble) in nibbles[..32].iter().rev().enumerate() {
+                    if nibble.len() != 1 {
+                        return None;
+                    }
+                    octets[i / 2] |= (u8::from_str_radix(nibble, 16).ok()?) << (4 * (1 - i % 2));
+                }
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            },
+            _ => None
         }
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        length - 1 // -1 because the last label does not actually have a separator following it.
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other).then(self.absolute.cmp(&other.absolute))
     }
 }
 
 impl Raw for Name {
+    /// Writes the name in wire format, which ends with the root label even if the name is relative.
     fn raw(&self) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(self.raw_size());
 
@@ -124,9 +509,24 @@ impl Raw for Name {
     }
 }
 
+impl FromStr for Name {
+    type Err = ParseError;
+
+    /// Parses a name in presentation format, taking relative names to be relative to the root.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Name::parse_with_origin(s, &Name::root())
+    }
+}
+
 impl std::fmt::Display for Name {
+    /// Formats the name in presentation format without the trailing dot, see [Name::to_fqdn] for the fully qualified form.
+    /// The root name is written as a single dot.
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.labels[0].to_string())?;
+        if self.labels.is_empty() {
+            return write!(f, ".");
+        }
+
+        write!(f, "{}", self.labels[0])?;
 
         if self.labels.len() > 1 {
             for label in &self.labels[1..] {
@@ -143,6 +543,7 @@ mod tests {
     use crate::Raw;
     use crate::application::dns::Name;
     use crate::application::dns::name;
+    use crate::error::ParseErrorKind;
 
     #[test]
     fn test_dns_name() {
@@ -151,22 +552,193 @@ mod tests {
         let name = Name::new("from.string.example.com");
         assert_eq!(name.to_string(), "from.string.example.com");
 
-        let name = Name::from_bytes(b"\x05other\x07example\x03com");
+        let name = Name::from_bytes(b"\x05other\x07example\x03com\x00").unwrap();
         assert_eq!(name.to_string(), "other.example.com");
 
-        let labels: Vec<name::Label> = vec![name::Label::new("new".to_string()), name::Label::new("example".to_string()), name::Label::new("com".to_string())];
-        let name = Name::from_labels(labels.clone());
+        // A name must be terminated by the null byte.
+        let err = Name::from_bytes(b"\x05other\x07example\x03com").unwrap_err();
+        assert_eq!((err.offset, err.kind), (18, ParseErrorKind::Truncated { needed: 1, available: 0 }));
+        let err = Name::from_bytes(b"\x05other\x07exam").unwrap_err();
+        assert_eq!((err.field, err.offset), ("Label.contents", 7));
+        let err = Name::from_bytes(b"\x05other\x47example\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (6, ParseErrorKind::BadLabelType(0x47)));
+
+        // Compression pointers
+        let message = b"\x07example\x03com\x00\x05other\xC0\x00\x03new\xC0\x0D";
+        assert_eq!(Name::from_message(message, 0).unwrap(), (Name::new("example.com"), 13));
+        assert_eq!(Name::from_message(message, 13).unwrap(), (Name::new("other.example.com"), 8));
+        assert_eq!(Name::from_message(message, 21).unwrap(), (Name::new("new.other.example.com"), 6));
+
+        let err = Name::from_message(b"\x05other\xC0\x08", 0).unwrap_err();
+        assert_eq!((err.offset, err.kind), (6, ParseErrorKind::ForwardPointer(8)));
+        // A pointer to a name which ends with a pointer itself
+        let (name, size) = Name::from_message(b"\x03com\x00\x07example\xC0\x00\xFF\x03www\xC0\x05", 16).unwrap();
+        assert_eq!((name, size), (Name::new("www.example.com"), 6));
+        let err = Name::from_message(b"\x05other\x03new\xC0\x06", 6).unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, ParseErrorKind::PointerLoop(6)));
+        let err = Name::from_message(b"\x03new\x05other\xC0\x04\xC0\x00", 12).unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, ParseErrorKind::PointerLoop(4)));
+
+        let labels: Vec<name::Label> = vec![name::Label::new("new").unwrap(), name::Label::new("example").unwrap(), name::Label::new("com").unwrap()];
+        let name = Name::from_labels(labels.clone()).unwrap();
         assert_eq!(name.to_string(), "new.example.com");
+        assert_eq!(name.to_fqdn(), "new.example.com.");
+
+        // Presentation format
+        assert_eq!(Name::from_bytes(b"\x00").unwrap().to_string(), ".");
+        assert_eq!(Name::from_bytes(b"\x00").unwrap().to_fqdn(), ".");
+        let escaped = Name::from_bytes(b"\x03a.b\x04 (\\\x7F\x07example\x00").unwrap();
+        assert_eq!(escaped.to_fqdn(), "a\\.b.\\032\\(\\\\\\127.example.");
+        assert_eq!(escaped.labels()[0].as_bytes(), b"a.b");
+        assert_eq!(escaped.to_fqdn().parse::<Name>().unwrap(), escaped);
+
+        let origin = Name::new("example.com");
+        assert_eq!(Name::parse_with_origin("www", &origin).unwrap(), Name::new("www.example.com"));
+        assert_eq!(Name::parse_with_origin("www.example.org.", &origin).unwrap(), Name::new("www.example.org"));
+        assert_eq!(Name::parse_with_origin("@", &origin).unwrap(), origin);
+        assert_eq!(Name::parse_with_origin(".", &origin).unwrap(), Name::root());
+        assert!(Name::parse_with_origin("a..b", &origin).is_err());
+        assert!(Name::parse_with_origin(&"a".repeat(64), &origin).is_err());
 
         // Name get labels
         assert_eq!(&labels, name.labels());
 
         // Name and label raw
-        let label = name::Label::new("from".to_string());
+        let label = name::Label::new("from").unwrap();
         assert_eq!(b"\x04\x66\x72\x6F\x6D", &label.raw()[..]);
-        assert_eq!(name::Label::from_bytes(b"\x04\x66\x72\x6F\x6D"), label);
+        assert_eq!(name::Label::from_bytes(b"\x04\x66\x72\x6F\x6D").unwrap(), label);
+
+        // Labels keep bytes which are not valid UTF-8 and may contain dots
+        let binary = Name::from_bytes(b"\x02\xFF\xC3\x03a.b\x00").unwrap();
+        assert_eq!(binary.labels()[0].as_bytes(), b"\xFF\xC3");
+        assert_eq!(binary.raw(), b"\x02\xFF\xC3\x03a.b\x00");
+        assert_eq!(binary.to_fqdn(), "\\255\\195.a\\.b.");
+        assert_eq!(binary.to_fqdn().parse::<Name>().unwrap().raw(), binary.raw());
+        assert_eq!(name::Label::new(&b"a.b"[..]).unwrap().raw(), b"\x03a.b");
+
+        // Length limits
+        assert_eq!(name::Label::new("a".repeat(63)).unwrap().raw_size(), 64);
+        assert_eq!(name::Label::new("a".repeat(64)).unwrap_err().kind, ParseErrorKind::BadLength(64));
+        assert_eq!(name::Label::new("").unwrap_err().kind, ParseErrorKind::Invalid("empty label"));
+        let long = [&b"\x3F"[..], &[b'a'; 63]].concat().repeat(4);
+        let err = Name::from_bytes(&[&long[..], b"\x00"].concat()).unwrap_err();
+        assert_eq!((err.offset, err.kind), (0, ParseErrorKind::BadLength(257)));
+        let longest = Name::from_bytes(&[&long[..192], b"\x3D", &[b'a'; 61], b"\x00"].concat()).unwrap();
+        assert_eq!(longest.raw_size(), 255);
+        assert_eq!(longest.append(&Name::new("a")).unwrap_err().kind, ParseErrorKind::BadLength(257));
+        assert_eq!(longest.to_fqdn().parse::<Name>().unwrap(), longest);
+        assert!(Name::parse_with_origin("a", &longest).is_err());
+        assert!(Name::from_labels(vec![name::Label::new("a".repeat(63)).unwrap(); 4]).is_err());
+        assert!(Name::from_bytes(b"\x40").is_err());
+
+        // Root and fully qualified names
+        assert_eq!(Name::new("."), Name::root());
+        assert_eq!(Name::new(""), Name::root());
+        assert_eq!(Name::new("example.com."), Name::new("example.com"));
+        assert_eq!(Name::root().raw(), b"\x00");
+        assert_eq!(Name::root().str_len(), 1);
+        assert_eq!(Name::new("www").append(&Name::new("example.com")).unwrap(), Name::new("www.example.com"));
 
         let name = Name::new("from.string.example.com");
         assert_eq!(b"\x04\x66\x72\x6F\x6D\x06\x73\x74\x72\x69\x6E\x67\x07\x65\x78\x61\x6D\x70\x6C\x65\x03\x63\x6F\x6D\x00", &name.raw()[..]);
+
+        // Canonical DNSSEC order, from the example of RFC 4034 §6.1
+        let mut names: Vec<Name> = ["z.example", "\\200.z.example", "*.z.example", "zABC.a.EXAMPLE", "example", "Z.a.example", "yljkjljk.a.example", "a.example", "\\001.z.example"]
+            .iter().map(|s| s.parse().unwrap()).collect();
+        names.sort();
+        assert_eq!(names.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+                   ["example", "a.example", "yljkjljk.a.example", "Z.a.example", "zABC.a.EXAMPLE", "z.example", "\\001.z.example", "*.z.example", "\\200.z.example"]);
+        assert_eq!(Name::new("zABC.a.EXAMPLE").to_lowercase().to_string(), "zabc.a.example");
+        assert_eq!(Name::new("a.example").canonical_cmp(&Name::new("A.EXAMPLE")), std::cmp::Ordering::Equal);
+
+        // Equality and hashing ignore case
+        assert_eq!(Name::new("Example.COM"), Name::new("example.com"));
+        assert_ne!(Name::new("example.com"), Name::new("example.org"));
+        let set: std::collections::HashSet<Name> = [Name::new("WWW.example.com"), Name::new("www.Example.com")].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(Name::new("Example.COM").to_string(), "Example.COM");
+
+        // Hierarchy
+        let www = Name::new("www.Example.com");
+        assert_eq!(www.label_count(), 3);
+        assert!(www.is_subdomain_of(&Name::new("example.COM")));
+        assert!(www.is_subdomain_of(&www));
+        assert!(www.is_subdomain_of(&Name::root()));
+        assert!(!www.is_subdomain_of(&Name::new("ww.example.com")));
+        assert!(!Name::new("example.com").is_subdomain_of(&www));
+        assert_eq!(www.parent(), Some(Name::new("example.com")));
+        assert_eq!(Name::root().parent(), None);
+        assert!(Name::root().is_root() && !www.is_root());
+        assert_eq!(www.zone_cuts().map(|name| name.to_fqdn()).collect::<Vec<_>>(), [".", "com.", "Example.com.", "www.Example.com."]);
+        assert_eq!(Name::root().zone_cuts().count(), 1);
+        assert!(Name::new("*.example.com").is_wildcard());
+        assert!(!Name::new("a.*.example.com").is_wildcard() && !Name::root().is_wildcard());
+
+        // Fully qualified and relative names
+        let relative = Name::parse_relative("www.Example").unwrap();
+        assert!(!relative.is_fqdn() && Name::new("www.example").is_fqdn() && Name::root().is_fqdn());
+        assert_eq!(relative.to_fqdn(), "www.Example");
+        assert_ne!(relative, Name::new("www.example"));
+        assert!(relative < Name::new("www.example"));
+        assert_eq!(Name::parse_relative("www.example.").unwrap(), Name::new("www.example"));
+        assert_eq!(Name::parse_relative(".").unwrap(), Name::root());
+        assert!(Name::parse_relative("@").is_err());
+        assert_eq!(relative.append(&Name::new("com")).unwrap(), Name::new("www.example.com"));
+        assert_eq!(relative.append(&Name::parse_relative("org").unwrap()).unwrap().to_fqdn(), "www.Example.org");
+        assert_eq!(relative.raw(), b"\x03www\x07Example\x00");
+        assert_eq!(Name::from_relative_labels(vec![name::Label::new("www").unwrap()]).unwrap(), Name::parse_relative("www").unwrap());
+        assert!(Name::from_relative_labels(Vec::new()).is_err());
+
+        assert!(relative.is_subdomain_of(&Name::parse_relative("example").unwrap()));
+        assert!(!relative.is_subdomain_of(&Name::new("example")) && !Name::new("www.example").is_subdomain_of(&Name::parse_relative("example").unwrap()));
+        assert_eq!(relative.parent().map(|name| name.to_fqdn()), Some(String::from("Example")));
+        assert_eq!(Name::parse_relative("www").unwrap().parent(), None);
+        assert_eq!(relative.zone_cuts().map(|name| name.to_fqdn()).collect::<Vec<_>>(), ["Example", "www.Example"]);
+        assert!(!relative.to_lowercase().is_fqdn());
+    }
+
+    #[test]
+    #[cfg(feature = "idna")]
+    fn test_dns_name_idna() {
+        let name = Name::from_unicode("Bücher.Example").unwrap();
+        assert_eq!(name.raw(), b"\x0Dxn--bcher-kva\x07example\x00");
+        assert_eq!(name, Name::new("xn--bcher-kva.example"));
+        assert_eq!(name.to_unicode(), "bücher.example");
+        assert_eq!(Name::new("XN--BCHER-KVA.example").to_unicode(), "bücher.example");
+
+        // Nontransitional processing keeps the sharp s, and full stops other than the ASCII one separate labels.
+        assert_eq!(Name::from_unicode("faß.de").unwrap().to_string(), "xn--fa-hia.de");
+        assert_eq!(Name::from_unicode("☃。net.").unwrap().to_fqdn(), "xn--n3h.net.");
+        assert_eq!(Name::from_unicode("_dmarc.example.com").unwrap(), Name::new("_dmarc.example.com"));
+        assert_eq!(Name::from_unicode(".").unwrap(), Name::root());
+        assert_eq!(Name::root().to_unicode(), ".");
+
+        // Labels which are not valid A-labels are shown like in presentation format.
+        assert_eq!(Name::new("xn--a.b\\c").to_unicode(), "xn--a.b\\\\c");
+        assert_eq!(Name::from_bytes(b"\x02\xFF.\x00").unwrap().to_unicode(), "\\255\\.");
+        assert_eq!(Name::from_bytes(b"\x0Fxn--bcher-kva.a\x07example\x00").unwrap().to_unicode(), "xn--bcher-kva\\.a.example");
+
+        assert!(Name::from_unicode("xn--a.example").is_err());
+        assert!(Name::from_unicode("a..example").is_err());
+        assert!(Name::from_unicode(&"ü".repeat(64)).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_reverse_name() {
+        use std::net::IpAddr;
+
+        let v4: IpAddr = "192.0.2.10".parse().unwrap();
+        let name = Name::from_ip(v4);
+        assert_eq!(name.to_string(), "10.2.0.192.in-addr.arpa");
+        assert_eq!(name.to_ip(), Some(v4));
+        assert_eq!(Name::new("10.2.0.192.IN-ADDR.ARPA").to_ip(), Some(v4));
+
+        let v6: IpAddr = "2001:db8::567:89ab".parse().unwrap();
+        let name = Name::from_ip(v6);
+        assert_eq!(name.to_string(), "b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa");
+        assert_eq!(name.to_ip(), Some(v6));
+
+        assert_eq!(Name::new("example.com").to_ip(), None);
+        assert_eq!(Name::new("300.2.0.192.in-addr.arpa").to_ip(), None);
+    }
+}
@@ -0,0 +1,372 @@
+use std::str::FromStr;
+
+use data_encoding::BASE64;
+
+use crate::application::dns::{rdata::CharacterString, Name, Type};
+use crate::error::{ParseError, ParseErrorKind};
+
+/// A token of text in presentation format [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Token {
+    /// The text of the token with its escape sequences kept, without the quotes around a quoted token.
+    pub text: String,
+
+    /// Whether the whole token was enclosed in quotes, which makes it a character-string.
+    pub quoted: bool,
+
+    /// The byte offset of the token in the input.
+    pub offset: usize
+}
+
+/// A logical line of text in presentation format, which may span several lines within parentheses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Entry {
+    pub tokens: Vec<Token>,
+
+    /// Whether the line starts with whitespace, which means that it has no owner name of its own.
+    pub indented: bool,
+
+    /// The byte offset of the end of the entry in the input.
+    pub end: usize
+}
+
+/// Splits text in presentation format into entries of tokens.
+/// Comments are dropped, and parentheses join lines into a single entry.
+#[derive(Debug, Clone)]
+pub(crate) struct Tokenizer<'a> {
+    input: &'a [u8],
+    position: usize
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Tokenizer { input: input.as_bytes(), position: 0 }
+    }
+
+    /// Returns the next entry which has at least one token, or None at the end of the input.
+    pub fn next_entry(&mut self) -> Option<Result<Entry, ParseError>> {
+        while self.position < self.input.len() {
+            match self.read_entry() {
+                Ok(entry) if entry.tokens.is_empty() => continue,
+                result => return Some(result)
+            }
+        }
+
+        None
+    }
+
+    fn read_entry(&mut self) -> Result<Entry, ParseError> {
+        let error = |offset: usize, message: &'static str| ParseError::new("DNS", "Presentation", offset, ParseErrorKind::Invalid(message));
+
+        let indented = matches!(self.input.get(self.position), Some(b' ' | b'\t'));
+        let mut tokens = Vec::new();
+        let mut parenthesis = None;
+
+        while let Some(&byte) = self.input.get(self.position) {
+            match byte {
+                b'\n' if parenthesis.is_none() => {
+                    self.position += 1;
+                    break;
+                },
+                b' ' | b'\t' | b'\r' | b'\n' => self.position += 1,
+                b';' => {
+                    while self.input.get(self.position).is_some_and(|&byte| byte != b'\n') {
+                        self.position += 1;
+                    }
+                },
+                b'(' if parenthesis.is_none() => {
+                    parenthesis = Some(self.position);
+                    self.position += 1;
+                },
+                b'(' => return Err(error(self.position, "parentheses must not be nested")),
+                b')' if parenthesis.is_some() => {
+                    parenthesis = None;
+                    self.position += 1;
+                },
+                b')' => return Err(error(self.position, "unbalanced closing parenthesis")),
+                _ => tokens.push(self.read_token()?)
+            }
+        }
+
+        if let Some(offset) = parenthesis {
+            return Err(error(offset, "unbalanced opening parenthesis"));
+        }
+
+        Ok(Entry { tokens, indented, end: self.position })
+    }
+
+    /// Reads a token starting at the current position, which is neither whitespace nor a delimiter.
+    fn read_token(&mut self) -> Result<Token, ParseError> {
+        let offset = self.position;
+        let quoted = self.input[offset] == b'"';
+        let mut text = Vec::new();
+        let mut in_quotes = false;
+
+        if quoted {
+            self.position += 1;
+            in_quotes = true;
+        }
+
+        while let Some(&byte) = self.input.get(self.position) {
+            match byte {
+                b'\\' => {
+                    let end = (self.position + 2).min(self.input.len());
+                    text.extend_from_slice(&self.input[self.position..end]);
+                    self.position = end;
+                    continue;
+                },
+                b'"' if quoted => {
+                    self.position += 1;
+                    in_quotes = false;
+                    break;
+                },
+                // A quote within a token, as in `alpn="h2,h3"`, is kept as part of the token.
+                b'"' => in_quotes = !in_quotes,
+                b' ' | b'\t' | b'\r' | b'\n' | b';' | b'(' | b')' if !in_quotes => break,
+                _ => {}
+            }
+
+            text.push(byte);
+            self.position += 1;
+        }
+
+        if in_quotes {
+            return Err(ParseError::new("DNS", "Presentation", offset, ParseErrorKind::Invalid("unterminated quoted string")));
+        }
+
+        // Tokens only end at ASCII delimiters, so they are valid UTF-8 like the input.
+        Ok(Token { text: String::from_utf8(text).unwrap(), quoted, offset })
+    }
+}
+
+/// Tokenizes text which must consist of exactly one entry, e.g. a single record.
+pub(crate) fn single_entry(s: &str) -> Result<Entry, ParseError> {
+    let mut tokenizer = Tokenizer::new(s);
+
+    let entry = tokenizer.next_entry()
+        .unwrap_or_else(|| Err(ParseError::new("DNS", "Presentation", 0, ParseErrorKind::Invalid("empty input"))))?;
+
+    match tokenizer.next_entry() {
+        Some(Ok(next)) => Err(invalid("Presentation", &next.tokens[0], "expected a single entry")),
+        Some(Err(e)) => Err(e),
+        None => Ok(entry)
+    }
+}
+
+/// Reverses the escaping of presentation format: `\DDD` is the byte with the decimal value DDD,
+/// any other character after a backslash stands for itself.
+pub(crate) fn unescape(text: &str, field: &'static str) -> Result<Vec<u8>, ParseError> {
+    let error = |offset: usize| ParseError::new("DNS", field, offset, ParseErrorKind::InvalidCharacter('\\'));
+
+    let bytes = text.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            unescaped.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+
+        match bytes.get(i + 1..i + 4) {
+            Some(digits) if digits.iter().all(u8::is_ascii_digit) => {
+                let value = std::str::from_utf8(digits).unwrap().parse::<u16>().unwrap();
+                unescaped.push(u8::try_from(value).map_err(|_| error(i))?);
+                i += 4;
+            },
+            _ => {
+                unescaped.push(*bytes.get(i + 1).ok_or_else(|| error(i))?);
+                i += 2;
+            }
+        }
+    }
+
+    Ok(unescaped)
+}
+
+/// Parses a TTL or other time value in seconds, either as a plain number or with the units
+/// `w`, `d`, `h`, `m` and `s` as accepted by BIND, e.g. `1h30m`.
+pub(crate) fn parse_ttl(text: &str) -> Option<u32> {
+    if text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.parse().ok();
+    }
+
+    let mut total: u32 = 0;
+    let mut number: Option<u32> = None;
+
+    for c in text.chars() {
+        match c.to_ascii_lowercase() {
+            '0'..='9' => number = Some(number.unwrap_or(0).checked_mul(10)?.checked_add(c.to_digit(10)?)?),
+            unit => {
+                let multiplier = match unit {
+                    'w' => 604_800,
+                    'd' => 86_400,
+                    'h' => 3_600,
+                    'm' => 60,
+                    's' => 1,
+                    _ => return None
+                };
+                total = total.checked_add(number.take()?.checked_mul(multiplier)?)?;
+            }
+        }
+    }
+
+    // A trailing number without a unit counts as seconds.
+    total.checked_add(number.unwrap_or(0))
+}
+
+/// Reads the fields of a record in presentation format from its tokens.
+/// Errors point at the offending token, or at the end of the entry if a field is missing.
+#[derive(Debug, Clone)]
+pub(crate) struct Tokens<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    end: usize,
+
+    /// The name which relative names are appended to.
+    pub origin: &'a Name
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(tokens: &'a [Token], end: usize, origin: &'a Name) -> Self {
+        Tokens { tokens, position: 0, end, origin }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    pub fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Returns the next token, or an error attributed to `field` if there is none.
+    pub fn next(&mut self, field: &'static str) -> Result<&'a Token, ParseError> {
+        let token = self.tokens.get(self.position)
+            .ok_or_else(|| ParseError::new("DNS", field, self.end, ParseErrorKind::Invalid("missing field")))?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    /// Returns all tokens which have not been read yet.
+    pub fn rest(&mut self) -> &'a [Token] {
+        let rest = &self.tokens[self.position.min(self.tokens.len())..];
+        self.position = self.tokens.len();
+
+        rest
+    }
+
+    /// Returns an error if there are tokens left which have not been read.
+    pub fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(token) => Err(ParseError::new("DNS", "Presentation", token.offset, ParseErrorKind::Invalid("unexpected trailing data"))),
+            None => Ok(())
+        }
+    }
+
+    /// Reads a value of a type which implements FromStr, e.g. a number or an address.
+    pub fn parse<T: FromStr>(&mut self, field: &'static str) -> Result<T, ParseError> {
+        let token = self.next(field)?;
+        token.text.parse().map_err(|_| invalid(field, token, "invalid value"))
+    }
+
+    /// Reads a time value in seconds, see [parse_ttl].
+    pub fn ttl(&mut self, field: &'static str) -> Result<u32, ParseError> {
+        let token = self.next(field)?;
+        parse_ttl(&token.text).ok_or_else(|| invalid(field, token, "invalid time value"))
+    }
+
+    /// Reads a name, relative to the origin unless it ends with a dot.
+    pub fn name(&mut self, field: &'static str) -> Result<Name, ParseError> {
+        let token = self.next(field)?;
+        Name::parse_with_origin(&token.text, self.origin).map_err(|e| e.offset_by(token.offset))
+    }
+
+    /// Reads a string of any length, which may be quoted or a plain token.
+    /// Used for fields which take up the rest of the record data, such as the value of a CAA record.
+    pub fn string(&mut self, field: &'static str) -> Result<Vec<u8>, ParseError> {
+        let token = self.next(field)?;
+        unescape(&token.text, field).map_err(|e| e.offset_by(token.offset + usize::from(token.quoted)))
+    }
+
+    /// Reads a character-string, which may be quoted or a plain token.
+    pub fn character_string(&mut self, field: &'static str) -> Result<CharacterString, ParseError> {
+        let offset = self.peek().map_or(self.end, |token| token.offset);
+        let contents = self.string(field)?;
+
+        let length = contents.len();
+        CharacterString::new(contents).map_err(|_| ParseError::new("DNS", field, offset, ParseErrorKind::BadLength(length)))
+    }
+
+    /// Reads the remaining tokens as hexadecimal digits, which may be split by whitespace.
+    pub fn hex(&mut self, field: &'static str) -> Result<Vec<u8>, ParseError> {
+        self.encoded(field, |text| hex::decode(text).ok())
+    }
+
+    /// Reads the remaining tokens as Base64, which may be split by whitespace.
+    pub fn base64(&mut self, field: &'static str) -> Result<Vec<u8>, ParseError> {
+        self.encoded(field, |text| BASE64.decode(text.as_bytes()).ok())
+    }
+
+    fn encoded(&mut self, field: &'static str, decode: impl Fn(&str) -> Option<Vec<u8>>) -> Result<Vec<u8>, ParseError> {
+        let first = self.next(field)?;
+        self.position -= 1;
+
+        let text: String = self.rest().iter().map(|token| token.text.as_str()).collect();
+        decode(&text).ok_or_else(|| invalid(field, first, "invalid encoding"))
+    }
+
+    /// Reads the remaining tokens as a list of types.
+    pub fn types(&mut self) -> Result<Vec<Type>, ParseError> {
+        self.rest().iter()
+            .map(|token| token.text.parse().map_err(|e: ParseError| e.offset_by(token.offset)))
+            .collect()
+    }
+}
+
+/// Constructs an error for the given token.
+pub(crate) fn invalid(field: &'static str, token: &Token, message: &'static str) -> ParseError {
+    ParseError::new("DNS", field, token.offset, ParseErrorKind::Invalid(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ttl, unescape, Tokenizer};
+
+    #[test]
+    fn test_tokenizer() {
+        let mut tokenizer = Tokenizer::new("www 3600 IN TXT \"a b\" c\\ d ; comment\n\n  IN A 192.0.2.1\n@ SOA ns1 admin (\n 1 ; serial\n 2 3 4 5 )\nsvc HTTPS 1 . alpn=\"h2,h3\"");
+
+        let entry = tokenizer.next_entry().unwrap().unwrap();
+        let texts: Vec<(&str, bool)> = entry.tokens.iter().map(|token| (token.text.as_str(), token.quoted)).collect();
+        assert_eq!(texts, vec![("www", false), ("3600", false), ("IN", false), ("TXT", false), ("a b", true), ("c\\ d", false)]);
+        assert_eq!(entry.tokens[4].offset, 16);
+        assert!(!entry.indented);
+
+        let entry = tokenizer.next_entry().unwrap().unwrap();
+        assert!(entry.indented);
+        assert_eq!(entry.tokens.len(), 3);
+
+        let entry = tokenizer.next_entry().unwrap().unwrap();
+        assert_eq!(entry.tokens.iter().map(|token| token.text.as_str()).collect::<Vec<_>>(), vec!["@", "SOA", "ns1", "admin", "1", "2", "3", "4", "5"]);
+
+        let entry = tokenizer.next_entry().unwrap().unwrap();
+        assert_eq!(entry.tokens[4].text, "alpn=\"h2,h3\"");
+        assert!(tokenizer.next_entry().is_none());
+
+        assert!(Tokenizer::new("a ( b").next_entry().unwrap().is_err());
+        assert!(Tokenizer::new("a b )").next_entry().unwrap().is_err());
+        assert!(Tokenizer::new("a \"b").next_entry().unwrap().is_err());
+
+        assert_eq!(unescape("a\\.b\\032\\\\", "Test").unwrap(), b"a.b \\");
+        assert!(unescape("\\256", "Test").is_err());
+
+        assert_eq!(parse_ttl("3600"), Some(3600));
+        assert_eq!(parse_ttl("1h30m"), Some(5400));
+        assert_eq!(parse_ttl("1W"), Some(604800));
+        assert_eq!(parse_ttl("h"), None);
+        assert_eq!(parse_ttl("4294967296"), None);
+    }
+}
@@ -1,12 +1,14 @@
 
-use core::panic;
 use std::mem::size_of;
 
 use byteorder::{NetworkEndian, ByteOrder};
 
-use crate::application::dns::{Name, Type, Class};
+use crate::application::dns::{Name, Type, Class, MessageEncoder};
+use crate::error::ParseError;
 use crate::Raw;
 
+use super::read_u16;
+
 /// A structure representing a DNS query.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Question {
@@ -22,25 +24,38 @@ impl Question {
             name,
             qtype,
             // Sets the upper-most bit of class to 1, if unicast_response is true
-            class: class as u16 | (0x8000 * unicast_response as u16)
+            class: u16::from(class) | (0x8000 * unicast_response as u16)
         }
     }
 
     /// Constructs a DNS question from the given bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        let name = Name::from_bytes(bytes);
-        let name_end = name.raw_size();
-        
-        Question {
-            name,
-            qtype: Type::try_from(NetworkEndian::read_u16(&bytes[name_end..name_end + 2])).expect("DNS question has invalid type"),
-            class: NetworkEndian::read_u16(&bytes[name_end + 2..name_end + 4])
-        }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Question::from_message(bytes, 0).map(|(question, _)| question)
+    }
+
+    /// Constructs a DNS question starting at `offset` in the given DNS message.
+    /// Returns the question together with the number of bytes it occupies.
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (name, name_size) = Name::from_message(message, offset)?;
+        let name_end = offset + name_size;
+
+        let qtype = Type::from(read_u16(message, name_end, "Question.qtype")?);
+
+        let class = read_u16(message, name_end + 2, "Question.class")?;
+
+        Ok((Question { name, qtype, class }, name_size + 2 * size_of::<u16>()))
+    }
+
+    /// Writes the question to the given message encoder, which compresses its name if enabled.
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write_name(&self.name);
+        encoder.write_u16(self.qtype.into());
+        encoder.write_u16(self.class);
     }
 
     /// Sets the class of the question while preserving the unicast_response bit.
     pub fn set_class(&mut self, class: Class) {
-        self.class = class as u16 | (0x8000 * self.prefers_unicast_response() as u16)
+        self.class = u16::from(class) | (0x8000 * self.prefers_unicast_response() as u16)
     }
 
     /// Changes the unicast_response bit in the class field to the specified value.
@@ -48,12 +63,9 @@ impl Question {
         self.class = (self.class & !0x8000) | (0x8000 * value as u16)
     }
 
-    /// Retreives the class of the question.
-    /// # Panics
-    /// If the class is invalid, which may only happen if the question has been manually altered without using the provided API.
+    /// Retreives the class of the question, without the unicast_response bit.
     pub fn class(&self) -> Class {
-        // Apparently Rust has no API for converting ints to enums. C++ - 1, Rust - 0.
-        Class::try_from(self.class & 0x00ff).expect("DNS question contains invalid classs!")
+        Class::from(self.class & 0x7fff)
     }
 
     /// Returns whether or not the question prefers a unicast response.
@@ -78,14 +90,21 @@ impl Raw for Question {
         bytes.push(0);
 
         // Write type and class
-        NetworkEndian::write_u16(&mut bytes[name_size..name_size + size_of::<u16>()], self.qtype as u16);
-        NetworkEndian::write_u16(&mut bytes[name_size + size_of::<u16>()..name_size + 2 * size_of::<u16>()], self.class as u16);
+        NetworkEndian::write_u16(&mut bytes[name_size..name_size + size_of::<u16>()], self.qtype.into());
+        NetworkEndian::write_u16(&mut bytes[name_size + size_of::<u16>()..name_size + 2 * size_of::<u16>()], self.class);
 
         bytes
     }
 
     fn raw_size(&self) -> usize {
-        self.name.raw_size() + size_of::<Type>() + size_of::<Class>()
+        self.name.raw_size() + size_of::<u16>() + size_of::<u16>()
+    }
+}
+
+impl std::fmt::Display for Question {
+    /// Formats the question like a record without TTL and data, e.g. `example.com. IN A`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.name.to_fqdn(), self.class(), self.qtype)
     }
 }
 
@@ -113,11 +132,24 @@ mod tests {
         let q = Question::new(Name::new("question.example.com"), Type::A, Class::IN, true);
 
         assert_eq!(q.class(), Class::IN);
-        assert_eq!(q.prefers_unicast_response(), true);
+        assert!(q.prefers_unicast_response());
 
-        let q1 = Question::from_bytes(&q.raw());
+        let q1 = Question::from_bytes(&q.raw()).unwrap();
         assert_eq!(q, q1);
         assert_eq!(q.raw(), q1.raw());
 
+        // Unassigned classes are kept by their code, apart from the unicast_response bit.
+        let q3 = Question::from_bytes(b"\x07example\x03com\x00\x00\x01\x80\x07").unwrap();
+        assert_eq!(q3.class(), Class::Unknown(7));
+        assert!(q3.prefers_unicast_response());
+        assert_eq!(q3.raw(), b"\x07example\x03com\x00\x00\x01\x80\x07");
+        assert_eq!(q3.to_string(), "example.com. CLASS7 A");
+
+        // A question whose name points back into the message.
+        let message = b"\x07example\x03com\x00\x03www\xC0\x00\x00\x01\x00\x01";
+        let (q2, size) = Question::from_message(message, 13).unwrap();
+        assert_eq!(q2.name, Name::new("www.example.com"));
+        assert_eq!(size, 10);
+
     }
 }
\ No newline at end of file
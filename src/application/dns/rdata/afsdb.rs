@@ -2,7 +2,7 @@ use std::mem::size_of;
 
 use byteorder::{NetworkEndian, ByteOrder};
 
-use crate::{application::dns::Name, Raw};
+use crate::{application::dns::{Name, read_u16}, error::ParseError, Raw};
 
 
 /// Represents an AFSDB record. AFSDB records pertain to servers with ASD cells.
@@ -13,15 +13,20 @@ pub struct AFSDB {
 }
 
 impl AFSDB {
-    fn new(subtype: u16, name: Name) -> Self {
+    pub fn new(subtype: u16, name: Name) -> Self {
         AFSDB { subtype, name }
     }
 
-    fn from_bytes(bytes: &[u8]) -> Self {
-        AFSDB {
-            subtype: NetworkEndian::read_u16(bytes),
-            name: Name::from_bytes(&bytes[2..])
-        }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        AFSDB::from_message(bytes, 0).map(|(afsdb, _)| afsdb)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let subtype = read_u16(message, offset, "AFSDB.subtype")?;
+        let (name, name_size) = Name::from_message(message, offset + 2)?;
+
+        Ok((AFSDB { subtype, name }, 2 + name_size))
     }
 }
 
@@ -41,4 +46,10 @@ impl Raw for AFSDB {
     fn raw_size(&self) -> usize {
         size_of::<u16>() + self.name.raw_size()
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Display for AFSDB {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.subtype, self.name.to_fqdn())
+    }
+}
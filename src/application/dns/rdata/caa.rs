@@ -0,0 +1,92 @@
+use crate::{application::dns::{read_slice, read_u8}, error::{ParseError, ParseErrorKind}, Raw};
+
+use super::quote_character_string;
+
+
+/// Marks a property which a certification authority must understand before issuing.
+const CRITICAL: u8 = 0b1000_0000;
+
+/// Certification authority authorization, which restricts the authorities allowed to issue certificates
+/// for a domain. [RFC 8659](https://datatracker.ietf.org/doc/html/rfc8659)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CAA {
+    pub flags: u8,
+
+    /// The property, e.g. "issue", "issuewild" or "iodef". Made up of 1 to 15 ASCII letters and digits.
+    tag: String,
+
+    /// The value of the property, whose format depends on the tag.
+    pub value: Vec<u8>
+}
+
+impl CAA {
+    /// Constructs the record data from its values.
+    /// Fails if the tag is not made up of 1 to 15 ASCII letters and digits.
+    pub fn new(flags: u8, tag: String, value: Vec<u8>) -> Result<Self, ParseError> {
+        if !(1..=15).contains(&tag.len()) {
+            return Err(ParseError::new("DNS", "CAA.tag", 0, ParseErrorKind::BadLength(tag.len())));
+        }
+        if let Some((i, c)) = tag.char_indices().find(|(_, c)| !c.is_ascii_alphanumeric()) {
+            return Err(ParseError::new("DNS", "CAA.tag", i, ParseErrorKind::InvalidCharacter(c)));
+        }
+
+        Ok(CAA { flags, tag, value })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        CAA::from_message(bytes, 0).map(|(caa, _)| caa)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let flags = read_u8(message, offset, "CAA.flags")?;
+
+        let tag_length = usize::from(read_u8(message, offset + 1, "CAA.tag")?);
+        if !(1..=15).contains(&tag_length) {
+            return Err(ParseError::new("DNS", "CAA.tag", offset + 1, ParseErrorKind::BadLength(tag_length)));
+        }
+
+        let tag = read_slice(message, offset + 2, tag_length, "CAA.tag")?;
+        if let Some(i) = tag.iter().position(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ParseError::new("DNS", "CAA.tag", offset + 2 + i, ParseErrorKind::InvalidCharacter(tag[i] as char)));
+        }
+
+        let value = message[offset + 2 + tag_length..].to_vec();
+        let size = 2 + tag_length + value.len();
+
+        Ok((CAA { flags, tag: String::from_utf8(tag.to_vec()).unwrap(), value }, size))
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns whether or not an authority which does not understand the property must refuse to issue.
+    pub fn is_critical(&self) -> bool {
+        self.flags & CRITICAL != 0
+    }
+}
+
+impl Raw for CAA {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.push(self.flags);
+        // The length is checked on construction.
+        bytes.push(self.tag.len() as u8);
+        bytes.extend_from_slice(self.tag.as_bytes());
+        bytes.extend_from_slice(&self.value);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 + self.tag.len() + self.value.len()
+    }
+}
+
+impl std::fmt::Display for CAA {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.flags, self.tag, quote_character_string(&self.value))
+    }
+}
@@ -0,0 +1,68 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+use data_encoding::BASE64;
+
+use crate::{application::dns::{read_u16, read_u8}, error::ParseError, Raw};
+
+use super::Algorithm;
+
+
+/// A certificate or certificate revocation list. [RFC 4398](https://datatracker.ietf.org/doc/html/rfc4398)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CERT {
+    /// The format of the certificate, e.g. 1 for X.509 (PKIX) and 3 for OpenPGP (PGP).
+    pub cert_type: u16,
+
+    /// The key tag of the key within the certificate, computed as for a DNSKEY.
+    pub key_tag: u16,
+
+    /// The algorithm of the key within the certificate.
+    pub algorithm: Algorithm,
+
+    pub certificate: Vec<u8>
+}
+
+impl CERT {
+    pub fn new(cert_type: u16, key_tag: u16, algorithm: Algorithm, certificate: Vec<u8>) -> Self {
+        CERT { cert_type, key_tag, algorithm, certificate }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        CERT::from_message(bytes, 0).map(|(cert, _)| cert)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let cert_type = read_u16(message, offset, "CERT.cert_type")?;
+        let key_tag = read_u16(message, offset + 2, "CERT.key_tag")?;
+        let algorithm = read_u8(message, offset + 4, "CERT.algorithm")?.into();
+        let certificate = message[offset + 5..].to_vec();
+        let size = 5 + certificate.len();
+
+        Ok((CERT { cert_type, key_tag, algorithm, certificate }, size))
+    }
+}
+
+impl Raw for CERT {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 2 * size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.cert_type);
+        NetworkEndian::write_u16(&mut bytes[2..4], self.key_tag);
+        bytes.push(self.algorithm.into());
+        bytes.extend_from_slice(&self.certificate);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 * size_of::<u16>() + 1 + self.certificate.len()
+    }
+}
+
+impl std::fmt::Display for CERT {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.cert_type, self.key_tag, u8::from(self.algorithm), BASE64.encode(&self.certificate))
+    }
+}
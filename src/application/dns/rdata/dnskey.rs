@@ -0,0 +1,183 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+use data_encoding::BASE64;
+
+use crate::{application::dns::{read_u16, read_u8}, error::ParseError, Raw};
+
+
+/// Marks a key which may sign the records of a zone.
+const ZONE_KEY: u16 = 0b0000_0001_0000_0000;
+/// Marks a key which has been revoked by its owner. [RFC 5011](https://datatracker.ietf.org/doc/html/rfc5011)
+const REVOKE: u16 = 0b0000_0000_1000_0000;
+/// Marks a key which is meant to be pointed to by a DS record, commonly called a key signing key.
+const SECURE_ENTRY_POINT: u16 = 0b0000_0000_0000_0001;
+
+/// DNSSEC signing algorithms, numbered as assigned by
+/// [IANA](https://www.iana.org/assignments/dns-sec-alg-numbers/dns-sec-alg-numbers.xhtml).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// RSA/MD5 (Obsolete), [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    RSAMD5,
+    /// DSA/SHA-1 [RFC 2536](https://datatracker.ietf.org/doc/html/rfc2536)
+    DSA,
+    /// RSA/SHA-1 [RFC 3110](https://datatracker.ietf.org/doc/html/rfc3110)
+    RSASHA1,
+    /// DSA/SHA-1 with NSEC3 [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    DSANSEC3SHA1,
+    /// RSA/SHA-1 with NSEC3 [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    RSASHA1NSEC3SHA1,
+    /// RSA/SHA-256 [RFC 5702](https://datatracker.ietf.org/doc/html/rfc5702)
+    RSASHA256,
+    /// RSA/SHA-512 [RFC 5702](https://datatracker.ietf.org/doc/html/rfc5702)
+    RSASHA512,
+    /// GOST R 34.10-2001 [RFC 5933](https://datatracker.ietf.org/doc/html/rfc5933)
+    ECCGOST,
+    /// ECDSA with curve P-256 and SHA-256 [RFC 6605](https://datatracker.ietf.org/doc/html/rfc6605)
+    ECDSAP256SHA256,
+    /// ECDSA with curve P-384 and SHA-384 [RFC 6605](https://datatracker.ietf.org/doc/html/rfc6605)
+    ECDSAP384SHA384,
+    /// Ed25519 [RFC 8080](https://datatracker.ietf.org/doc/html/rfc8080)
+    ED25519,
+    /// Ed448 [RFC 8080](https://datatracker.ietf.org/doc/html/rfc8080)
+    ED448,
+    /// An algorithm which is not known to this library, identified by its number.
+    Unknown(u8)
+}
+
+impl From<u8> for Algorithm {
+    fn from(number: u8) -> Self {
+        use Algorithm::*;
+        match number {
+            1 => RSAMD5,
+            3 => DSA,
+            5 => RSASHA1,
+            6 => DSANSEC3SHA1,
+            7 => RSASHA1NSEC3SHA1,
+            8 => RSASHA256,
+            10 => RSASHA512,
+            12 => ECCGOST,
+            13 => ECDSAP256SHA256,
+            14 => ECDSAP384SHA384,
+            15 => ED25519,
+            16 => ED448,
+            _ => Unknown(number)
+        }
+    }
+}
+
+impl From<Algorithm> for u8 {
+    fn from(algorithm: Algorithm) -> Self {
+        use Algorithm::*;
+        match algorithm {
+            RSAMD5 => 1,
+            DSA => 3,
+            RSASHA1 => 5,
+            DSANSEC3SHA1 => 6,
+            RSASHA1NSEC3SHA1 => 7,
+            RSASHA256 => 8,
+            RSASHA512 => 10,
+            ECCGOST => 12,
+            ECDSAP256SHA256 => 13,
+            ECDSAP384SHA384 => 14,
+            ED25519 => 15,
+            ED448 => 16,
+            Unknown(number) => number
+        }
+    }
+}
+
+/// A public key of a zone. [RFC 4034 §2](https://datatracker.ietf.org/doc/html/rfc4034#section-2)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DNSKEY {
+    pub flags: u16,
+
+    /// Must be 3 for keys used by DNSSEC.
+    pub protocol: u8,
+
+    pub algorithm: Algorithm,
+
+    /// The public key in the format specified by the algorithm.
+    pub public_key: Vec<u8>
+}
+
+impl DNSKEY {
+    pub fn new(flags: u16, protocol: u8, algorithm: Algorithm, public_key: Vec<u8>) -> Self {
+        DNSKEY { flags, protocol, algorithm, public_key }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        DNSKEY::from_message(bytes, 0).map(|(dnskey, _)| dnskey)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let flags = read_u16(message, offset, "DNSKEY.flags")?;
+        let protocol = read_u8(message, offset + 2, "DNSKEY.protocol")?;
+        let algorithm = read_u8(message, offset + 3, "DNSKEY.algorithm")?.into();
+        let public_key = message[offset + 4..].to_vec();
+        let size = 4 + public_key.len();
+
+        Ok((DNSKEY { flags, protocol, algorithm, public_key }, size))
+    }
+
+    /// Returns whether or not the key may be used to verify signatures over the records of the zone.
+    pub fn is_zone_key(&self) -> bool {
+        self.flags & ZONE_KEY != 0
+    }
+
+    /// Returns whether or not the key is a secure entry point, i.e. a key signing key.
+    pub fn is_secure_entry_point(&self) -> bool {
+        self.flags & SECURE_ENTRY_POINT != 0
+    }
+
+    /// Returns whether or not the key has been revoked.
+    pub fn is_revoked(&self) -> bool {
+        self.flags & REVOKE != 0
+    }
+
+    /// Computes the key tag which RRSIG and DS records use to refer to this key.
+    /// [RFC 4034 Appendix B](https://datatracker.ietf.org/doc/html/rfc4034#appendix-B)
+    pub fn key_tag(&self) -> u16 {
+        let rdata = self.raw();
+
+        // The obsolete RSA/MD5 algorithm uses bits of the public key instead.
+        if self.algorithm == Algorithm::RSAMD5 {
+            return match rdata.len() {
+                len if len >= 4 => NetworkEndian::read_u16(&rdata[len - 3..len - 1]),
+                _ => 0
+            };
+        }
+
+        let mut sum: u32 = 0;
+        for (i, byte) in rdata.iter().enumerate() {
+            sum += if i % 2 == 0 { u32::from(*byte) << 8 } else { u32::from(*byte) };
+        }
+        sum += (sum >> 16) & 0xFFFF;
+
+        sum as u16
+    }
+}
+
+impl Raw for DNSKEY {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes, self.flags);
+        bytes.push(self.protocol);
+        bytes.push(self.algorithm.into());
+        bytes.extend_from_slice(&self.public_key);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        size_of::<u16>() + 2 + self.public_key.len()
+    }
+}
+
+impl std::fmt::Display for DNSKEY {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.flags, self.protocol, u8::from(self.algorithm), BASE64.encode(&self.public_key))
+    }
+}
@@ -0,0 +1,108 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{read_u16, read_u8}, error::ParseError, Raw};
+
+use super::Algorithm;
+
+
+/// Algorithms for the digest of a DS record, numbered as assigned by
+/// [IANA](https://www.iana.org/assignments/ds-rr-types/ds-rr-types.xhtml).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DigestType {
+    /// SHA-1 [RFC 3658](https://datatracker.ietf.org/doc/html/rfc3658)
+    SHA1,
+    /// SHA-256 [RFC 4509](https://datatracker.ietf.org/doc/html/rfc4509)
+    SHA256,
+    /// GOST R 34.11-94 [RFC 5933](https://datatracker.ietf.org/doc/html/rfc5933)
+    GOST,
+    /// SHA-384 [RFC 6605](https://datatracker.ietf.org/doc/html/rfc6605)
+    SHA384,
+    /// A digest type which is not known to this library, identified by its number.
+    Unknown(u8)
+}
+
+impl From<u8> for DigestType {
+    fn from(number: u8) -> Self {
+        match number {
+            1 => DigestType::SHA1,
+            2 => DigestType::SHA256,
+            3 => DigestType::GOST,
+            4 => DigestType::SHA384,
+            _ => DigestType::Unknown(number)
+        }
+    }
+}
+
+impl From<DigestType> for u8 {
+    fn from(digest_type: DigestType) -> Self {
+        match digest_type {
+            DigestType::SHA1 => 1,
+            DigestType::SHA256 => 2,
+            DigestType::GOST => 3,
+            DigestType::SHA384 => 4,
+            DigestType::Unknown(number) => number
+        }
+    }
+}
+
+/// Delegation signer, which refers to a DNSKEY of the delegated zone by its digest.
+/// [RFC 4034 §5](https://datatracker.ietf.org/doc/html/rfc4034#section-5)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DS {
+    /// The key tag of the DNSKEY referred to.
+    pub key_tag: u16,
+
+    /// The algorithm of the DNSKEY referred to.
+    pub algorithm: Algorithm,
+
+    pub digest_type: DigestType,
+
+    /// The digest over the owner name and record data of the DNSKEY.
+    pub digest: Vec<u8>
+}
+
+impl DS {
+    pub fn new(key_tag: u16, algorithm: Algorithm, digest_type: DigestType, digest: Vec<u8>) -> Self {
+        DS { key_tag, algorithm, digest_type, digest }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        DS::from_message(bytes, 0).map(|(ds, _)| ds)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let key_tag = read_u16(message, offset, "DS.key_tag")?;
+        let algorithm = read_u8(message, offset + 2, "DS.algorithm")?.into();
+        let digest_type = read_u8(message, offset + 3, "DS.digest_type")?.into();
+        let digest = message[offset + 4..].to_vec();
+        let size = 4 + digest.len();
+
+        Ok((DS { key_tag, algorithm, digest_type, digest }, size))
+    }
+}
+
+impl Raw for DS {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes, self.key_tag);
+        bytes.push(self.algorithm.into());
+        bytes.push(self.digest_type.into());
+        bytes.extend_from_slice(&self.digest);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        size_of::<u16>() + 2 + self.digest.len()
+    }
+}
+
+impl std::fmt::Display for DS {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.key_tag, u8::from(self.algorithm), u8::from(self.digest_type), hex::encode_upper(&self.digest))
+    }
+}
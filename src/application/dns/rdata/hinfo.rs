@@ -0,0 +1,55 @@
+use crate::{error::ParseError, Raw};
+
+use super::CharacterString;
+
+
+/// Used to acquire general information about a host.  
+/// The main use is for protocols such as FTP that can use special procedures
+/// when talking between machines or operating systems of the same type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HINFO {
+    /// A character-string which specifies the CPU type.
+    pub cpu: CharacterString,
+
+    /// A character-string which specifies the operating system type.
+    pub os: CharacterString
+}
+
+impl HINFO {
+    pub fn new(cpu: CharacterString, os: CharacterString) -> Self {
+        HINFO { cpu, os }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        HINFO::from_message(bytes, 0).map(|(hinfo, _)| hinfo)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (cpu, cpu_size) = CharacterString::from_message(message, offset, "HINFO.cpu")?;
+        let (os, os_size) = CharacterString::from_message(message, offset + cpu_size, "HINFO.os")?;
+
+        Ok((HINFO { cpu, os }, cpu_size + os_size))
+    }
+}
+
+impl Raw for HINFO {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.append(&mut self.cpu.raw());
+        bytes.append(&mut self.os.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.cpu.raw_size() + self.os.raw_size()
+    }
+}
+
+impl std::fmt::Display for HINFO {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.cpu, self.os)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{error::ParseError, Raw};
+
+use super::CharacterString;
+
+
+/// An ISDN (Integrated Service Digital Network) - a telephone number
+/// [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-3.2)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ISDN {
+    /// The ISDN number, a character-string of decimal digits.
+    pub address: CharacterString,
+
+    /// The optional subaddress, a character-string of hex digits.
+    pub subaddress: Option<CharacterString>
+}
+
+impl ISDN {
+    pub fn new(address: CharacterString, subaddress: Option<CharacterString>) -> Self {
+        ISDN { address, subaddress }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        ISDN::from_message(bytes, 0).map(|(isdn, _)| isdn)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (address, mut size) = CharacterString::from_message(message, offset, "ISDN.address")?;
+
+        let mut subaddress = None;
+        if offset + size < message.len() {
+            let (sa, sa_size) = CharacterString::from_message(message, offset + size, "ISDN.subaddress")?;
+            subaddress = Some(sa);
+            size += sa_size;
+        }
+
+        Ok((ISDN { address, subaddress }, size))
+    }
+}
+
+impl Raw for ISDN {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.append(&mut self.address.raw());
+        if let Some(subaddress) = &self.subaddress {
+            bytes.append(&mut subaddress.raw());
+        }
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.address.raw_size() + self.subaddress.as_ref().map_or(0, |sa| sa.raw_size())
+    }
+}
+
+impl std::fmt::Display for ISDN {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.address)?;
+
+        if let Some(subaddress) = &self.subaddress {
+            write!(f, " {}", subaddress)?;
+        }
+
+        Ok(())
+    }
+}
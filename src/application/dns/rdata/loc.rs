@@ -0,0 +1,233 @@
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{presentation::{invalid, Tokens}, read_u8, read_u32}, error::ParseError, Raw};
+
+/// The latitude and longitude of the equator and prime meridian respectively, in thousandths of an arc second.
+const LOC_EQUATOR: u32 = 1 << 31;
+/// The altitude of the WGS 84 reference spheroid, in centimeters.
+const LOC_ALTITUDE_BASE: u32 = 100_000 * 100;
+
+
+/// For expressing location information. [RFC 1876](https://datatracker.ietf.org/doc/html/rfc1876)
+/// The fields are stored as they appear on the wire; the accessors convert them to degrees and meters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LOC {
+    /// The version of the representation. Only version 0 is defined.
+    pub version: u8,
+
+    /// The diameter of a sphere enclosing the entity, encoded as a base and a power of ten in centimeters.
+    pub size: u8,
+
+    /// The horizontal precision, encoded like the size.
+    pub horizontal_precision: u8,
+
+    /// The vertical precision, encoded like the size.
+    pub vertical_precision: u8,
+
+    /// The latitude in thousandths of an arc second, with 2^31 being the equator.
+    pub latitude: u32,
+
+    /// The longitude in thousandths of an arc second, with 2^31 being the prime meridian.
+    pub longitude: u32,
+
+    /// The altitude in centimeters above a base of 100000 meters below the WGS 84 reference spheroid.
+    pub altitude: u32
+}
+
+impl LOC {
+    pub fn new(version: u8, size: u8, horizontal_precision: u8, vertical_precision: u8, latitude: u32, longitude: u32, altitude: u32) -> Self {
+        LOC { version, size, horizontal_precision, vertical_precision, latitude, longitude, altitude }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        LOC::from_message(bytes, 0).map(|(loc, _)| loc)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let loc = LOC {
+            version: read_u8(message, offset, "LOC.version")?,
+            size: read_u8(message, offset + 1, "LOC.size")?,
+            horizontal_precision: read_u8(message, offset + 2, "LOC.horizontal_precision")?,
+            vertical_precision: read_u8(message, offset + 3, "LOC.vertical_precision")?,
+            latitude: read_u32(message, offset + 4, "LOC.latitude")?,
+            longitude: read_u32(message, offset + 8, "LOC.longitude")?,
+            altitude: read_u32(message, offset + 12, "LOC.altitude")?
+        };
+
+        Ok((loc, 16))
+    }
+
+    /// Returns the latitude in degrees, positive towards the north.
+    pub fn latitude_degrees(&self) -> f64 {
+        (f64::from(self.latitude) - f64::from(LOC_EQUATOR)) / 3_600_000.0
+    }
+
+    /// Returns the longitude in degrees, positive towards the east.
+    pub fn longitude_degrees(&self) -> f64 {
+        (f64::from(self.longitude) - f64::from(LOC_EQUATOR)) / 3_600_000.0
+    }
+
+    /// Returns the altitude in meters relative to the WGS 84 reference spheroid.
+    pub fn altitude_meters(&self) -> f64 {
+        (f64::from(self.altitude) - f64::from(LOC_ALTITUDE_BASE)) / 100.0
+    }
+
+    /// Returns the size in meters.
+    pub fn size_meters(&self) -> f64 {
+        decode_precision(self.size)
+    }
+
+    /// Returns the horizontal precision in meters.
+    pub fn horizontal_precision_meters(&self) -> f64 {
+        decode_precision(self.horizontal_precision)
+    }
+
+    /// Returns the vertical precision in meters.
+    pub fn vertical_precision_meters(&self) -> f64 {
+        decode_precision(self.vertical_precision)
+    }
+}
+
+/// Decodes a size or precision, whose upper nibble is a base and lower nibble a power of ten in centimeters, into meters.
+fn decode_precision(value: u8) -> f64 {
+    f64::from(value >> 4) * 10f64.powi(i32::from(value & 0x0F)) / 100.0
+}
+
+impl Raw for LOC {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.raw_size()];
+
+        bytes[0] = self.version;
+        bytes[1] = self.size;
+        bytes[2] = self.horizontal_precision;
+        bytes[3] = self.vertical_precision;
+        NetworkEndian::write_u32(&mut bytes[4..8], self.latitude);
+        NetworkEndian::write_u32(&mut bytes[8..12], self.longitude);
+        NetworkEndian::write_u32(&mut bytes[12..16], self.altitude);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        16
+    }
+}
+
+impl std::fmt::Display for LOC {
+    /// Formats the location as degrees, minutes and seconds followed by the altitude, size and precisions in meters,
+    /// e.g. `42 21 54.000 N 71 6 18.000 W -24.00m 30.00m 10000.00m 10.00m` [RFC 1876 §3](https://datatracker.ietf.org/doc/html/rfc1876#section-3).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_coordinate(f, self.latitude, 'N', 'S')?;
+        write!(f, " ")?;
+        write_coordinate(f, self.longitude, 'E', 'W')?;
+
+        let altitude = i64::from(self.altitude) - i64::from(LOC_ALTITUDE_BASE);
+        let sign = if altitude < 0 { "-" } else { "" };
+        write!(f, " {}{}.{:02}m", sign, altitude.abs() / 100, altitude.abs() % 100)?;
+
+        for precision in [self.size, self.horizontal_precision, self.vertical_precision] {
+            let centimeters = u64::from(precision >> 4) * 10u64.pow(u32::from(precision & 0x0F));
+            write!(f, " {}.{:02}m", centimeters / 100, centimeters % 100)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a latitude or longitude in thousandths of arc seconds relative to the equator or prime meridian
+/// as degrees, minutes, seconds and hemisphere.
+fn write_coordinate(f: &mut std::fmt::Formatter, value: u32, positive: char, negative: char) -> std::fmt::Result {
+    let offset = i64::from(value) - i64::from(LOC_EQUATOR);
+    let hemisphere = if offset < 0 { negative } else { positive };
+    let milliseconds = offset.abs();
+
+    write!(f, "{} {} {}.{:03} {}", milliseconds / 3_600_000, milliseconds / 60_000 % 60, milliseconds / 1_000 % 60, milliseconds % 1_000, hemisphere)
+}
+
+impl LOC {
+    /// Reads the location in presentation format, see the Display implementation.
+    /// The size and precisions are optional and default to 1m, 10000m and 10m [RFC 1876 §3](https://datatracker.ietf.org/doc/html/rfc1876#section-3).
+    pub(crate) fn from_tokens(tokens: &mut Tokens) -> Result<Self, ParseError> {
+        let latitude = read_coordinate(tokens, "LOC.latitude", 'N', 'S', 90)?;
+        let longitude = read_coordinate(tokens, "LOC.longitude", 'E', 'W', 180)?;
+
+        let token = tokens.next("LOC.altitude")?;
+        let altitude = parse_decimal(token.text.trim_end_matches(['m', 'M']), 2)
+            .and_then(|centimeters| u32::try_from(centimeters + i64::from(LOC_ALTITUDE_BASE)).ok())
+            .ok_or_else(|| invalid("LOC.altitude", token, "invalid altitude"))?;
+
+        let mut precisions = [0x12, 0x16, 0x13];
+        for (precision, field) in precisions.iter_mut().zip(["LOC.size", "LOC.horizontal_precision", "LOC.vertical_precision"]) {
+            if tokens.is_empty() {
+                break;
+            }
+
+            let token = tokens.next(field)?;
+            *precision = parse_decimal(token.text.trim_end_matches(['m', 'M']), 2)
+                .and_then(encode_precision)
+                .ok_or_else(|| invalid(field, token, "invalid precision"))?;
+        }
+
+        Ok(LOC::new(0, precisions[0], precisions[1], precisions[2], latitude, longitude, altitude))
+    }
+}
+
+/// Reads a latitude or longitude as degrees, optional minutes and seconds, and hemisphere.
+fn read_coordinate(tokens: &mut Tokens, field: &'static str, positive: char, negative: char, max_degrees: i64) -> Result<u32, ParseError> {
+    let first = tokens.next(field)?;
+    let mut parts = vec![first];
+
+    // Up to three numbers are followed by the hemisphere.
+    let hemisphere = loop {
+        let token = tokens.next(field)?;
+        match token.text.to_ascii_uppercase().chars().collect::<Vec<_>>()[..] {
+            [c] if c == positive => break 1,
+            [c] if c == negative => break -1,
+            _ if parts.len() < 3 => parts.push(token),
+            _ => return Err(invalid(field, token, "expected a hemisphere"))
+        }
+    };
+
+    let degrees = parts[0].text.parse::<i64>().ok().filter(|degrees| *degrees <= max_degrees);
+    let minutes = parts.get(1).map_or(Some(0), |token| token.text.parse::<i64>().ok().filter(|minutes| *minutes < 60));
+    let seconds = parts.get(2).map_or(Some(0), |token| parse_decimal(&token.text, 3).filter(|seconds| (0..60_000).contains(seconds)));
+
+    let (Some(degrees), Some(minutes), Some(seconds)) = (degrees, minutes, seconds) else {
+        return Err(invalid(field, first, "invalid coordinate"));
+    };
+
+    let offset = (degrees * 3_600_000 + minutes * 60_000 + seconds) * hemisphere;
+    u32::try_from(i64::from(LOC_EQUATOR) + offset).map_err(|_| invalid(field, first, "invalid coordinate"))
+}
+
+/// Parses a decimal number with at most `places` fractional digits into an integer scaled by 10^places.
+fn parse_decimal(text: &str, places: usize) -> Option<i64> {
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if fraction.len() > places || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let negative = integer.starts_with('-');
+    let integer: i64 = match integer.trim_start_matches('-') {
+        "" if !fraction.is_empty() => 0,
+        digits => digits.parse().ok()?
+    };
+    let fraction: i64 = format!("{:0<width$}", fraction, width = places).parse().unwrap_or(0);
+    let value = integer.checked_mul(10i64.pow(places as u32))?.checked_add(fraction)?;
+
+    Some(if negative { -value } else { value })
+}
+
+/// Encodes a size or precision in centimeters as a base and power of ten, rounding down, see [decode_precision].
+fn encode_precision(centimeters: i64) -> Option<u8> {
+    let mut base = u64::try_from(centimeters).ok()?;
+    let mut exponent = 0;
+
+    while base > 9 {
+        base /= 10;
+        exponent += 1;
+    }
+
+    (exponent <= 9).then_some(((base as u8) << 4) | exponent)
+}
@@ -0,0 +1,57 @@
+use crate::{application::dns::{Name, MessageEncoder}, error::ParseError, Raw};
+
+
+/// Used to acquire mailbox or mail list information.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MINFO {
+    /// Specifies a mailbox which is responsible for the mailing list or mailbox.
+    pub rmailbx: Name,
+
+    /// Specifies a mailbox which is to receive error messages related to the mailing list or mailbox.
+    pub emailbx: Name
+}
+
+impl MINFO {
+    pub fn new(rmailbx: Name, emailbx: Name) -> Self {
+        MINFO { rmailbx, emailbx }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        MINFO::from_message(bytes, 0).map(|(minfo, _)| minfo)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (rmailbx, rmailbx_size) = Name::from_message(message, offset)?;
+        let (emailbx, emailbx_size) = Name::from_message(message, offset + rmailbx_size)?;
+
+        Ok((MINFO { rmailbx, emailbx }, rmailbx_size + emailbx_size))
+    }
+
+    /// Writes the record data to the given message encoder, compressing both names if enabled.
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write_name(&self.rmailbx);
+        encoder.write_name(&self.emailbx);
+    }
+}
+
+impl Raw for MINFO {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.append(&mut self.rmailbx.raw());
+        bytes.append(&mut self.emailbx.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.rmailbx.raw_size() + self.emailbx.raw_size()
+    }
+}
+
+impl std::fmt::Display for MINFO {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.rmailbx.to_fqdn(), self.emailbx.to_fqdn())
+    }
+}
@@ -1,14 +1,279 @@
-use std::{net::{Ipv4Addr, Ipv6Addr}, mem::size_of};
+use std::{net::{Ipv4Addr, Ipv6Addr}, mem::size_of, str::FromStr};
+
+use data_encoding::BASE32HEX_NOPAD;
 
 use crate::Raw;
-use crate::application::dns::Type;
-use byteorder::{NetworkEndian, ByteOrder};
+use crate::application::dns::{Name, Type, MessageEncoder};
+use crate::application::dns::presentation::{invalid, single_entry, Tokens};
+use crate::error::{ParseError, ParseErrorKind};
+
+use super::{read_slice, read_u8};
 
 mod afsdb;
+mod caa;
+mod cert;
+mod dnskey;
+mod ds;
+mod hinfo;
+mod isdn;
+mod loc;
+mod minfo;
 mod mx;
+mod naptr;
+mod nsap;
+mod nsec;
+mod nsec3;
+mod nsec3param;
+mod null;
+mod rp;
+mod rrsig;
+mod rt;
+mod soa;
+mod srv;
+mod sshfp;
+mod svcb;
+mod tlsa;
+mod txt;
+mod uri;
+mod wks;
 
 pub use afsdb::*;
+pub use caa::*;
+pub use cert::*;
+pub use dnskey::*;
+pub use ds::*;
+pub use hinfo::*;
+pub use isdn::*;
+pub use loc::*;
+pub use minfo::*;
 pub use mx::*;
+pub use naptr::*;
+pub use nsap::*;
+pub use nsec::*;
+pub use nsec3::*;
+pub use nsec3param::*;
+pub use null::*;
+pub use rp::*;
+pub use rrsig::*;
+pub use rt::*;
+pub use soa::*;
+pub use srv::*;
+pub use sshfp::*;
+pub use svcb::*;
+pub use tlsa::*;
+pub use txt::*;
+pub use uri::*;
+pub use wks::*;
+
+/// A character-string, a length byte followed by up to [CharacterString::MAX_LENGTH] bytes of any kind
+/// [RFC 1035 §3.3](https://datatracker.ietf.org/doc/html/rfc1035#section-3.3).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CharacterString {
+    contents: Vec<u8>
+}
+
+impl CharacterString {
+    /// The maximum length of the contents of a character-string in bytes.
+    pub const MAX_LENGTH: usize = 255;
+
+    /// Constructs a character-string from its contents.
+    /// Fails if the contents are longer than [CharacterString::MAX_LENGTH] bytes.
+    pub fn new(contents: impl Into<Vec<u8>>) -> Result<Self, ParseError> {
+        let contents = contents.into();
+
+        if contents.len() > CharacterString::MAX_LENGTH {
+            return Err(ParseError::new("DNS", "CharacterString", 0, ParseErrorKind::BadLength(contents.len())));
+        }
+
+        Ok(CharacterString { contents })
+    }
+
+    /// Reads a character-string at `offset` in the given DNS message.
+    /// Returns the string together with the number of bytes it occupies.
+    pub(crate) fn from_message(message: &[u8], offset: usize, field: &'static str) -> Result<(Self, usize), ParseError> {
+        let length = usize::from(read_u8(message, offset, field)?);
+        let contents = read_slice(message, offset + 1, length, field)?;
+
+        Ok((CharacterString { contents: contents.to_vec() }, 1 + length))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.contents
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.contents
+    }
+}
+
+impl Raw for CharacterString {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        // The length is checked on construction.
+        bytes.push(self.contents.len() as u8);
+        bytes.extend_from_slice(&self.contents);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        1 + self.contents.len()
+    }
+}
+
+impl std::fmt::Display for CharacterString {
+    /// Formats the string in presentation format: enclosed in quotes, with quotes and backslashes escaped
+    /// and bytes which are not printable ASCII written as `\DDD`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", quote_character_string(&self.contents))
+    }
+}
+
+/// Formats a character-string in presentation format: enclosed in quotes, with quotes and backslashes escaped
+/// and bytes which are not printable ASCII written as `\DDD` [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+pub(crate) fn quote_character_string(contents: &[u8]) -> String {
+    let mut quoted = String::from("\"");
+
+    for byte in contents {
+        match byte {
+            b'"' | b'\\' => quoted.push_str(&format!("\\{}", *byte as char)),
+            0x20..=0x7E => quoted.push(*byte as char),
+            _ => quoted.push_str(&format!("\\{:03}", byte))
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a salt in hexadecimal, or as `-` if it is empty [RFC 5155 §3.3](https://datatracker.ietf.org/doc/html/rfc5155#section-3.3).
+pub(crate) fn format_salt(salt: &[u8]) -> String {
+    match salt.is_empty() {
+        true => String::from("-"),
+        false => hex::encode_upper(salt)
+    }
+}
+
+/// Reads a salt of at most 255 bytes in hexadecimal, or `-` for an empty salt.
+fn read_salt(tokens: &mut Tokens, field: &'static str) -> Result<CharacterString, ParseError> {
+    let token = tokens.next(field)?;
+
+    let salt = match token.text.as_str() {
+        "-" => Vec::new(),
+        digits => hex::decode(digits).map_err(|_| invalid(field, token, "invalid encoding"))?
+    };
+
+    let length = salt.len();
+    CharacterString::new(salt).map_err(|_| ParseError::new("DNS", field, token.offset, ParseErrorKind::BadLength(length)))
+}
+
+/// The type bitmap of an NSEC or NSEC3 record, which lists the types of the records that exist at a name
+/// [RFC 4034 §4.1.2](https://datatracker.ietf.org/doc/html/rfc4034#section-4.1.2).
+/// The bitmap is kept in its wire format, which is unique for a set of types: every window is written once with as few
+/// bytes as possible, and bitmaps read from a message which are not in this form are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TypeBitmap {
+    bytes: Vec<u8>
+}
+
+impl TypeBitmap {
+    /// Constructs the canonical bitmap of the given types, which may be in any order.
+    /// Every window is written once with as few bytes as possible.
+    pub fn new(types: &[Type]) -> Self {
+        let mut codes: Vec<u16> = types.iter().map(|rtype| u16::from(*rtype)).collect();
+        codes.sort_unstable();
+        codes.dedup();
+
+        let mut bytes = Vec::new();
+        let mut rest = &codes[..];
+        while let Some(first) = rest.first() {
+            let (window, tail) = rest.split_at(rest.iter().take_while(|code| *code >> 8 == first >> 8).count());
+            rest = tail;
+
+            let mut bitmap = vec![0u8; usize::from(window[window.len() - 1] & 0xFF) / 8 + 1];
+            for code in window {
+                bitmap[usize::from(code & 0xFF) / 8] |= 0x80 >> (code % 8);
+            }
+
+            bytes.push((window[0] >> 8) as u8);
+            bytes.push(bitmap.len() as u8);
+            bytes.append(&mut bitmap);
+        }
+
+        TypeBitmap { bytes }
+    }
+
+    /// Reads the bitmap which extends from `offset` to the end of `message`.
+    /// Returns the bitmap together with the number of bytes it occupies.
+    pub(crate) fn from_message(message: &[u8], offset: usize, field: &'static str) -> Result<(Self, usize), ParseError> {
+        let mut previous_window: Option<u8> = None;
+
+        let mut i = offset;
+        while i < message.len() {
+            let window = read_u8(message, i, field)?;
+            let length = usize::from(read_u8(message, i + 1, field)?);
+
+            if previous_window.is_some_and(|previous| window <= previous) {
+                return Err(ParseError::new("DNS", field, i, ParseErrorKind::Invalid("type bitmap windows must be in ascending order")));
+            }
+            if !(1..=32).contains(&length) {
+                return Err(ParseError::new("DNS", field, i + 1, ParseErrorKind::BadLength(length)));
+            }
+            // The last byte of a window has to have a type in it, which also rules out windows without types.
+            if read_slice(message, i + 2, length, field)?[length - 1] == 0 {
+                return Err(ParseError::new("DNS", field, i + 1 + length, ParseErrorKind::Invalid("type bitmap windows must not end with a zero byte")));
+            }
+
+            previous_window = Some(window);
+            i += 2 + length;
+        }
+
+        Ok((TypeBitmap { bytes: message[offset..i].to_vec() }, i - offset))
+    }
+
+    /// Returns the types in the bitmap in ascending order.
+    pub fn types(&self) -> Vec<Type> {
+        let mut types = Vec::new();
+
+        let mut i = 0;
+        while i < self.bytes.len() {
+            let window = self.bytes[i];
+            let length = usize::from(self.bytes[i + 1]);
+
+            for (j, byte) in self.bytes[i + 2..i + 2 + length].iter().enumerate() {
+                for bit in 0..8 {
+                    if byte & (0x80 >> bit) != 0 {
+                        types.push(Type::from(u16::from(window) << 8 | (j * 8 + bit) as u16));
+                    }
+                }
+            }
+
+            i += 2 + length;
+        }
+
+        types
+    }
+
+    pub fn contains(&self, rtype: Type) -> bool {
+        self.types().contains(&rtype)
+    }
+
+    /// Whether the bitmap has no types.
+    pub fn is_empty(&self) -> bool {
+        self.types().is_empty()
+    }
+}
+
+impl Raw for TypeBitmap {
+    fn raw(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    fn raw_size(&self) -> usize {
+        self.bytes.len()
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// Similar to dns::Type, but contains data.
@@ -19,86 +284,911 @@ pub enum RData {
     AAAA(Ipv6Addr),
     /// For servers with ASD cells
     AFSDB(AFSDB),
+    /// Certification authority authorization [RFC 8659](https://datatracker.ietf.org/doc/html/rfc8659)
+    CAA(CAA),
+    /// A certificate or certificate revocation list [RFC 4398](https://datatracker.ietf.org/doc/html/rfc4398)
+    CERT(CERT),
+    /// The canonical name for an alias
+    CNAME(Name),
+    /// Redirection of a whole subtree [RFC 6672](https://datatracker.ietf.org/doc/html/rfc6672)
+    DNAME(Name),
+    /// Public key of a zone [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    DNSKEY(DNSKEY),
+    /// Delegation signer [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    DS(DS),
     /// Used to acquire general information about a host.  
     /// The main use is for protocols such as FTP that can use special procedures
     /// when talking between machines or operating systems of the same type.
-    HINFO,
+    HINFO(HINFO),
+    /// Service binding for HTTPS origins [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460)
+    HTTPS(SVCB),
     /// An ISDN (Integrated Service Digital Network) - a telephone number
-    ISDN,
+    ISDN(ISDN),
     ///  For xpressing location information. [RFC 1876](https://datatracker.ietf.org/doc/html/rfc1876)
-    LOC,
+    LOC(LOC),
+    /// A mailbox domain name, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MB(Name),
+    /// A mail destination (Obsolete - use MX), [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MD(Name),
+    /// A mail forwarder (Obsolete - use MX), [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MF(Name),
+    /// A mail group member, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MG(Name),
     /// Used to acquire mailbox or mail list information.
-    MINFO,
+    MINFO(MINFO),
+    /// A mail rename domain name, [RFC 1035](https://tools.ietf.org/html/rfc1035)
+    MR(Name),
     /// MX is used to acquire mail exchange information
     MX(MX),
+    /// Naming authority pointer [RFC 3403](https://datatracker.ietf.org/doc/html/rfc3403)
+    NAPTR(NAPTR),
+    /// An authoritative name server
+    NS(Name),
     /// NSAP structure [RFC 1706](https://datatracker.ietf.org/doc/html/rfc1706)
-    NSAP,
+    NSAP(NSAP),
+    /// Next secure record [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    NSEC(NSEC),
+    /// Hashed next secure record [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    NSEC3(NSEC3),
+    /// Parameters for NSEC3 [RFC 5155](https://datatracker.ietf.org/doc/html/rfc5155)
+    NSEC3PARAM(NSEC3PARAM),
     /// Used to represent arbitrary data.
-    NULL,
+    NULL(NULL),
+    /// A domain name pointer, mostly used for reverse lookups
+    PTR(Name),
+    /// Signature over an RRset [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034)
+    RRSIG(RRSIG),
     /// Route-through binding for hosts that do not have their own direct wide area network addresses
-    RT,
+    RT(RT),
     /// RP Responsible Person [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-2.2)
-    RP,
-    /// Start of zone authority.
-    SOA,
+    RP(RP),
+    /// Marks the start of a zone of authority.
+    SOA(SOA),
     /// Specifies the location of the server(s) for a specific protocol and domain.
-    SRV,
+    SRV(SRV),
+    /// SSH key fingerprint [RFC 4255](https://datatracker.ietf.org/doc/html/rfc4255)
+    SSHFP(SSHFP),
+    /// General purpose service binding [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460)
+    SVCB(SVCB),
+    /// TLS certificate association [RFC 6698](https://datatracker.ietf.org/doc/html/rfc6698)
+    TLSA(TLSA),
     /// A text record.
-    TXT,
+    TXT(TXT),
+    /// A URI [RFC 7553](https://datatracker.ietf.org/doc/html/rfc7553)
+    URI(URI),
     /// Used to describe the well known services supported by a particular protocol on a particular internet address.
-    WKS
+    WKS(WKS),
+    /// The data of a record whose type is not parsed by this library, kept verbatim
+    /// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597).
+    /// Also the empty data of records which stand for whole RRsets in dynamic updates.
+    Unknown {
+        rtype: Type,
+        bytes: Vec<u8>
+    }
 }
 
 impl RData {
-    pub fn from_bytes(rtype: Type, bytes: &[u8]) -> Self {
-        match rtype {
-            Type::A => RData::A(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+    /// Parses the data of a resource record of the given type.
+    /// All of `bytes` is taken to be the record data.
+    pub fn from_bytes(rtype: Type, bytes: &[u8]) -> Result<Self, ParseError> {
+        RData::from_message(rtype, bytes, 0, bytes.len())
+    }
+
+    /// Parses `length` bytes of record data of the given type, starting at `offset` in the given DNS message.
+    /// Any names within the data are decompressed against the message.
+    /// The data of types which are not parsed by this library is kept as [RData::Unknown].
+    ///
+    /// The parsers of the single types, e.g. [MX::from_message], take the data to extend to the end of `message`,
+    /// which is cut off at the end of the record data here, and return it together with the number of bytes it occupies.
+    pub fn from_message(rtype: Type, message: &[u8], offset: usize, length: usize) -> Result<Self, ParseError> {
+        read_slice(message, offset, length, "RData")?;
+
+        // Compression pointers may only point backwards, so cutting the message off at the end of the data
+        // keeps all reads within it.
+        let message = &message[..offset + length];
+
+        let (rdata, size) = match rtype {
+            Type::A => {
+                let octets: [u8; 4] = read_slice(message, offset, size_of::<u32>(), "RData.A")?.try_into().unwrap();
+                (RData::A(Ipv4Addr::from(octets)), octets.len())
+            },
             Type::AAAA => { 
-                let a = NetworkEndian::read_u16(&bytes[0..2]);
-                let b = NetworkEndian::read_u16(&bytes[2..4]);
-                let c = NetworkEndian::read_u16(&bytes[4..6]);
-                let d = NetworkEndian::read_u16(&bytes[6..8]);
-                let e = NetworkEndian::read_u16(&bytes[8..10]);
-                let f = NetworkEndian::read_u16(&bytes[10..12]);
-                let g = NetworkEndian::read_u16(&bytes[12..14]);
-                let h = NetworkEndian::read_u16(&bytes[14..16]);
-
-                RData::AAAA(Ipv6Addr::new(a, b, c, d, e, f, g, h))
+                let octets: [u8; 16] = read_slice(message, offset, size_of::<u128>(), "RData.AAAA")?.try_into().unwrap();
+                (RData::AAAA(Ipv6Addr::from(octets)), octets.len())
             },
-            Type::MX => RData::MX(MX::from_bytes(bytes)),
-            _ => todo!()
+            Type::AFSDB => AFSDB::from_message(message, offset).map(|(afsdb, size)| (RData::AFSDB(afsdb), size))?,
+            Type::CAA => CAA::from_message(message, offset).map(|(caa, size)| (RData::CAA(caa), size))?,
+            Type::CERT => CERT::from_message(message, offset).map(|(cert, size)| (RData::CERT(cert), size))?,
+            Type::CNAME => Name::from_message(message, offset).map(|(name, size)| (RData::CNAME(name), size))?,
+            Type::DNAME => Name::from_message(message, offset).map(|(name, size)| (RData::DNAME(name), size))?,
+            Type::DNSKEY => DNSKEY::from_message(message, offset).map(|(dnskey, size)| (RData::DNSKEY(dnskey), size))?,
+            Type::DS => DS::from_message(message, offset).map(|(ds, size)| (RData::DS(ds), size))?,
+            Type::HINFO => HINFO::from_message(message, offset).map(|(hinfo, size)| (RData::HINFO(hinfo), size))?,
+            Type::HTTPS => SVCB::from_message(message, offset).map(|(svcb, size)| (RData::HTTPS(svcb), size))?,
+            Type::ISDN => ISDN::from_message(message, offset).map(|(isdn, size)| (RData::ISDN(isdn), size))?,
+            Type::LOC => LOC::from_message(message, offset).map(|(loc, size)| (RData::LOC(loc), size))?,
+            Type::MB => Name::from_message(message, offset).map(|(name, size)| (RData::MB(name), size))?,
+            Type::MD => Name::from_message(message, offset).map(|(name, size)| (RData::MD(name), size))?,
+            Type::MF => Name::from_message(message, offset).map(|(name, size)| (RData::MF(name), size))?,
+            Type::MG => Name::from_message(message, offset).map(|(name, size)| (RData::MG(name), size))?,
+            Type::MINFO => MINFO::from_message(message, offset).map(|(minfo, size)| (RData::MINFO(minfo), size))?,
+            Type::MR => Name::from_message(message, offset).map(|(name, size)| (RData::MR(name), size))?,
+            Type::MX => MX::from_message(message, offset).map(|(mx, size)| (RData::MX(mx), size))?,
+            Type::NAPTR => NAPTR::from_message(message, offset).map(|(naptr, size)| (RData::NAPTR(naptr), size))?,
+            Type::NS => Name::from_message(message, offset).map(|(name, size)| (RData::NS(name), size))?,
+            Type::NSAP => NSAP::from_message(message, offset).map(|(nsap, size)| (RData::NSAP(nsap), size))?,
+            Type::NSEC => NSEC::from_message(message, offset).map(|(nsec, size)| (RData::NSEC(nsec), size))?,
+            Type::NSEC3 => NSEC3::from_message(message, offset).map(|(nsec3, size)| (RData::NSEC3(nsec3), size))?,
+            Type::NSEC3PARAM => NSEC3PARAM::from_message(message, offset).map(|(nsec3param, size)| (RData::NSEC3PARAM(nsec3param), size))?,
+            Type::NULL => NULL::from_message(message, offset).map(|(null, size)| (RData::NULL(null), size))?,
+            Type::PTR => Name::from_message(message, offset).map(|(name, size)| (RData::PTR(name), size))?,
+            Type::RRSIG => RRSIG::from_message(message, offset).map(|(rrsig, size)| (RData::RRSIG(rrsig), size))?,
+            Type::RT => RT::from_message(message, offset).map(|(rt, size)| (RData::RT(rt), size))?,
+            Type::RP => RP::from_message(message, offset).map(|(rp, size)| (RData::RP(rp), size))?,
+            Type::SOA => SOA::from_message(message, offset).map(|(soa, size)| (RData::SOA(soa), size))?,
+            Type::SRV => SRV::from_message(message, offset).map(|(srv, size)| (RData::SRV(srv), size))?,
+            Type::SSHFP => SSHFP::from_message(message, offset).map(|(sshfp, size)| (RData::SSHFP(sshfp), size))?,
+            Type::SVCB => SVCB::from_message(message, offset).map(|(svcb, size)| (RData::SVCB(svcb), size))?,
+            Type::TLSA => TLSA::from_message(message, offset).map(|(tlsa, size)| (RData::TLSA(tlsa), size))?,
+            Type::TXT => TXT::from_message(message, offset).map(|(txt, size)| (RData::TXT(txt), size))?,
+            Type::URI => URI::from_message(message, offset).map(|(uri, size)| (RData::URI(uri), size))?,
+            Type::WKS => WKS::from_message(message, offset).map(|(wks, size)| (RData::WKS(wks), size))?,
+            _ => return Ok(RData::Unknown { rtype, bytes: message[offset..].to_vec() })
+        };
+
+        if size != length {
+            return Err(ParseError::new("DNS", "RData", offset, ParseErrorKind::BadLength(length)));
         }
+
+        Ok(rdata)
     }
-}
 
-impl Raw for RData {
-    fn raw(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(self.raw_size());
+    /// Returns the data in the generic presentation format for record data of any type,
+    /// e.g. `\# 4 0A000001` [RFC 3597 §5](https://datatracker.ietf.org/doc/html/rfc3597#section-5).
+    pub fn to_generic_string(&self) -> String {
+        let bytes = self.raw();
 
-        match self {
-            RData::A(ip) => {
-                bytes.extend([0, 0, 0, 0].iter());
-                NetworkEndian::write_u32(&mut bytes, ip.clone().into());
+        if bytes.is_empty() {
+            return String::from("\\# 0");
+        }
+
+        format!("\\# {} {}", bytes.len(), hex::encode_upper(bytes))
+    }
+
+    /// Parses record data of the given type in presentation format, e.g. `10 mail.example.com.` for MX,
+    /// with relative names taken to be relative to `origin`.
+    /// Every type accepts the generic format `\# <length> <hex>` [RFC 3597 §5](https://datatracker.ietf.org/doc/html/rfc3597#section-5),
+    /// which is the only format for types which are not parsed by this library.
+    pub fn parse_with_origin(rtype: Type, s: &str, origin: &Name) -> Result<Self, ParseError> {
+        let entry = single_entry(s)?;
+        let mut tokens = Tokens::new(&entry.tokens, entry.end, origin);
+
+        let rdata = RData::from_tokens(rtype, &mut tokens)?;
+        tokens.finish()?;
+
+        Ok(rdata)
+    }
+
+    /// Reads record data of the given type from the remaining tokens of an entry.
+    pub(crate) fn from_tokens(rtype: Type, tokens: &mut Tokens) -> Result<Self, ParseError> {
+        if tokens.peek().is_some_and(|token| !token.quoted && token.text == "\\#") {
+            let token = tokens.next("RData")?;
+            let length: usize = tokens.parse("RData.length")?;
+            let bytes = if length == 0 { Vec::new() } else { tokens.hex("RData.data")? };
+
+            if bytes.len() != length {
+                return Err(ParseError::new("DNS", "RData.data", token.offset, ParseErrorKind::BadLength(length)));
+            }
+
+            return RData::from_bytes(rtype, &bytes).map_err(|e| ParseError { offset: token.offset, ..e });
+        }
+
+        let rdata = match rtype {
+            Type::A => RData::A(tokens.parse("A.address")?),
+            Type::AAAA => RData::AAAA(tokens.parse("AAAA.address")?),
+            Type::AFSDB => RData::AFSDB(AFSDB::new(tokens.parse("AFSDB.subtype")?, tokens.name("AFSDB.name")?)),
+            Type::CAA => {
+                let flags = tokens.parse("CAA.flags")?;
+                let token = tokens.next("CAA.tag")?;
+                let value = tokens.string("CAA.value")?;
+
+                RData::CAA(CAA::new(flags, token.text.clone(), value).map_err(|e| e.offset_by(token.offset))?)
+            },
+            Type::CERT => RData::CERT(CERT::new(tokens.parse("CERT.cert_type")?, tokens.parse("CERT.key_tag")?,
+                                                tokens.parse::<u8>("CERT.algorithm")?.into(), tokens.base64("CERT.certificate")?)),
+            Type::CNAME => RData::CNAME(tokens.name("CNAME")?),
+            Type::DNAME => RData::DNAME(tokens.name("DNAME")?),
+            Type::DNSKEY => RData::DNSKEY(DNSKEY::new(tokens.parse("DNSKEY.flags")?, tokens.parse("DNSKEY.protocol")?,
+                                                      tokens.parse::<u8>("DNSKEY.algorithm")?.into(), tokens.base64("DNSKEY.public_key")?)),
+            Type::DS => RData::DS(DS::new(tokens.parse("DS.key_tag")?, tokens.parse::<u8>("DS.algorithm")?.into(),
+                                          tokens.parse::<u8>("DS.digest_type")?.into(), tokens.hex("DS.digest")?)),
+            Type::HINFO => RData::HINFO(HINFO::new(tokens.character_string("HINFO.cpu")?, tokens.character_string("HINFO.os")?)),
+            Type::HTTPS => RData::HTTPS(SVCB::from_tokens(tokens)?),
+            Type::ISDN => {
+                let address = tokens.character_string("ISDN.address")?;
+                let subaddress = if tokens.is_empty() { None } else { Some(tokens.character_string("ISDN.subaddress")?) };
+
+                RData::ISDN(ISDN::new(address, subaddress))
             },
-            RData::AAAA(ip) => {
-                bytes.extend([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0].iter());
-                NetworkEndian::write_u128(&mut bytes, ip.clone().into());
+            Type::LOC => RData::LOC(LOC::from_tokens(tokens)?),
+            Type::MB => RData::MB(tokens.name("MB")?),
+            Type::MD => RData::MD(tokens.name("MD")?),
+            Type::MF => RData::MF(tokens.name("MF")?),
+            Type::MG => RData::MG(tokens.name("MG")?),
+            Type::MINFO => RData::MINFO(MINFO::new(tokens.name("MINFO.rmailbx")?, tokens.name("MINFO.emailbx")?)),
+            Type::MR => RData::MR(tokens.name("MR")?),
+            Type::MX => RData::MX(MX::new(tokens.parse("MX.preference")?, tokens.name("MX.host")?)),
+            Type::NAPTR => RData::NAPTR(NAPTR::new(tokens.parse("NAPTR.order")?, tokens.parse("NAPTR.preference")?,
+                                                   tokens.character_string("NAPTR.flags")?, tokens.character_string("NAPTR.services")?,
+                                                   tokens.character_string("NAPTR.regexp")?, tokens.name("NAPTR.replacement")?)),
+            Type::NS => RData::NS(tokens.name("NS")?),
+            Type::NSAP => {
+                let token = tokens.next("NSAP.address")?;
+                let address = token.text.strip_prefix("0x").or_else(|| token.text.strip_prefix("0X"))
+                    .and_then(|digits| hex::decode(digits.replace('.', "")).ok())
+                    .ok_or_else(|| invalid("NSAP.address", token, "expected hexadecimal digits after 0x"))?;
+
+                RData::NSAP(NSAP::new(address))
+            },
+            Type::NSEC => RData::NSEC(NSEC::new(tokens.name("NSEC.next_domain")?, tokens.types()?)),
+            Type::NSEC3 => {
+                let (hash_algorithm, flags, iterations) = (tokens.parse("NSEC3.hash_algorithm")?, tokens.parse("NSEC3.flags")?, tokens.parse("NSEC3.iterations")?);
+                let salt = read_salt(tokens, "NSEC3.salt")?;
+                let token = tokens.next("NSEC3.next_hashed_owner")?;
+                let hash = BASE32HEX_NOPAD.decode(token.text.to_ascii_uppercase().as_bytes())
+                    .map_err(|_| invalid("NSEC3.next_hashed_owner", token, "invalid encoding"))?;
+                let length = hash.len();
+                let next_hashed_owner = CharacterString::new(hash)
+                    .map_err(|_| ParseError::new("DNS", "NSEC3.next_hashed_owner", token.offset, ParseErrorKind::BadLength(length)))?;
+
+                RData::NSEC3(NSEC3::new(hash_algorithm, flags, iterations, salt, next_hashed_owner, tokens.types()?))
+            },
+            Type::NSEC3PARAM => {
+                let (hash_algorithm, flags, iterations) = (tokens.parse("NSEC3PARAM.hash_algorithm")?, tokens.parse("NSEC3PARAM.flags")?, tokens.parse("NSEC3PARAM.iterations")?);
+                RData::NSEC3PARAM(NSEC3PARAM::new(hash_algorithm, flags, iterations, read_salt(tokens, "NSEC3PARAM.salt")?))
             },
-            RData::MX(mx) => {
-                bytes.append(&mut mx.raw())
+            Type::PTR => RData::PTR(tokens.name("PTR")?),
+            Type::RP => RData::RP(RP::new(tokens.name("RP.mbox")?, tokens.name("RP.txt")?)),
+            Type::RRSIG => {
+                let type_covered = tokens.next("RRSIG.type_covered")?;
+                let type_covered = type_covered.text.parse().map_err(|e: ParseError| e.offset_by(type_covered.offset))?;
+                let (algorithm, labels, original_ttl) = (tokens.parse::<u8>("RRSIG.algorithm")?.into(), tokens.parse("RRSIG.labels")?, tokens.ttl("RRSIG.original_ttl")?);
+
+                let mut timestamp = |field| {
+                    let token = tokens.next(field)?;
+                    parse_timestamp(&token.text).ok_or_else(|| invalid(field, token, "invalid timestamp"))
+                };
+                let (expiration, inception) = (timestamp("RRSIG.expiration")?, timestamp("RRSIG.inception")?);
+
+                RData::RRSIG(RRSIG::new(type_covered, algorithm, labels, original_ttl, expiration, inception,
+                                        tokens.parse("RRSIG.key_tag")?, tokens.name("RRSIG.signer_name")?, tokens.base64("RRSIG.signature")?))
+            },
+            Type::RT => RData::RT(RT::new(tokens.parse("RT.preference")?, tokens.name("RT.host")?)),
+            Type::SOA => RData::SOA(SOA::new(tokens.name("SOA.mname")?, tokens.name("SOA.rname")?, tokens.parse("SOA.serial")?,
+                                             tokens.ttl("SOA.refresh")?, tokens.ttl("SOA.retry")?, tokens.ttl("SOA.expire")?, tokens.ttl("SOA.minimum")?)),
+            Type::SRV => RData::SRV(SRV::new(tokens.parse("SRV.priority")?, tokens.parse("SRV.weight")?, tokens.parse("SRV.port")?, tokens.name("SRV.target")?)),
+            Type::SSHFP => RData::SSHFP(SSHFP::new(tokens.parse("SSHFP.algorithm")?, tokens.parse("SSHFP.fingerprint_type")?, tokens.hex("SSHFP.fingerprint")?)),
+            Type::SVCB => RData::SVCB(SVCB::from_tokens(tokens)?),
+            Type::TLSA => RData::TLSA(TLSA::new(tokens.parse("TLSA.usage")?, tokens.parse("TLSA.selector")?, tokens.parse("TLSA.matching_type")?, tokens.hex("TLSA.data")?)),
+            Type::TXT => {
+                let mut strings = vec![tokens.character_string("TXT.strings")?];
+                while !tokens.is_empty() {
+                    strings.push(tokens.character_string("TXT.strings")?);
+                }
+
+                RData::TXT(TXT::new(strings)?)
+            },
+            Type::URI => RData::URI(URI::new(tokens.parse("URI.priority")?, tokens.parse("URI.weight")?, tokens.string("URI.target")?)),
+            Type::WKS => {
+                let address = tokens.parse("WKS.address")?;
+                let token = tokens.next("WKS.protocol")?;
+                let protocol = match token.text.to_ascii_lowercase().as_str() {
+                    "tcp" => 6,
+                    "udp" => 17,
+                    number => number.parse().map_err(|_| invalid("WKS.protocol", token, "invalid protocol"))?
+                };
+
+                let ports = tokens.rest().iter()
+                    .map(|token| token.text.parse().map_err(|_| invalid("WKS.ports", token, "services must be port numbers")))
+                    .collect::<Result<Vec<u16>, ParseError>>()?;
+
+                RData::WKS(WKS::from_ports(address, protocol, &ports))
+            },
+            _ => {
+                let token = tokens.next("RData")?;
+                return Err(invalid("RData", token, "this type only supports the generic format"));
             }
-            _ => todo!()
+        };
+
+        Ok(rdata)
+    }
+
+    /// Writes the data to the given message encoder.
+    /// Only names within the record types of [RFC 1035](https://datatracker.ietf.org/doc/html/rfc1035) are compressed,
+    /// since other implementations may not know how to decompress the rest [RFC 3597 §4](https://datatracker.ietf.org/doc/html/rfc3597#section-4).
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        match self {
+            RData::CNAME(name) | RData::MB(name) | RData::MD(name) | RData::MF(name) | RData::MG(name) | RData::MR(name)
+            | RData::NS(name) | RData::PTR(name) => encoder.write_name(name),
+            RData::MINFO(minfo) => minfo.encode(encoder),
+            RData::MX(mx) => mx.encode(encoder),
+            RData::SOA(soa) => soa.encode(encoder),
+            _ => encoder.write(&self.raw())
         }
+    }
+}
 
-        bytes
+impl Raw for RData {
+    fn raw(&self) -> Vec<u8> {
+        match self {
+            RData::A(ip) => ip.octets().to_vec(),
+            RData::AAAA(ip) => ip.octets().to_vec(),
+            RData::AFSDB(afsdb) => afsdb.raw(),
+            RData::CAA(caa) => caa.raw(),
+            RData::CERT(cert) => cert.raw(),
+            RData::CNAME(name) | RData::DNAME(name) | RData::MB(name) | RData::MD(name) | RData::MF(name) | RData::MG(name)
+            | RData::MR(name) | RData::NS(name) | RData::PTR(name) => name.raw(),
+            RData::DNSKEY(dnskey) => dnskey.raw(),
+            RData::DS(ds) => ds.raw(),
+            RData::HINFO(hinfo) => hinfo.raw(),
+            RData::HTTPS(svcb) | RData::SVCB(svcb) => svcb.raw(),
+            RData::ISDN(isdn) => isdn.raw(),
+            RData::LOC(loc) => loc.raw(),
+            RData::MINFO(minfo) => minfo.raw(),
+            RData::MX(mx) => mx.raw(),
+            RData::NAPTR(naptr) => naptr.raw(),
+            RData::NSAP(nsap) => nsap.raw(),
+            RData::NSEC(nsec) => nsec.raw(),
+            RData::NSEC3(nsec3) => nsec3.raw(),
+            RData::NSEC3PARAM(nsec3param) => nsec3param.raw(),
+            RData::NULL(null) => null.raw(),
+            RData::RRSIG(rrsig) => rrsig.raw(),
+            RData::RT(rt) => rt.raw(),
+            RData::RP(rp) => rp.raw(),
+            RData::SOA(soa) => soa.raw(),
+            RData::SRV(srv) => srv.raw(),
+            RData::SSHFP(sshfp) => sshfp.raw(),
+            RData::TLSA(tlsa) => tlsa.raw(),
+            RData::TXT(txt) => txt.raw(),
+            RData::URI(uri) => uri.raw(),
+            RData::WKS(wks) => wks.raw(),
+            RData::Unknown { bytes, .. } => bytes.clone()
+        }
     }
 
     fn raw_size(&self) -> usize {
         match self {
             RData::A(_) => size_of::<u32>(),
             RData::AAAA(_) => size_of::<u128>(),
+            RData::AFSDB(afsdb) => afsdb.raw_size(),
+            RData::CAA(caa) => caa.raw_size(),
+            RData::CERT(cert) => cert.raw_size(),
+            RData::CNAME(name) | RData::DNAME(name) | RData::MB(name) | RData::MD(name) | RData::MF(name) | RData::MG(name)
+            | RData::MR(name) | RData::NS(name) | RData::PTR(name) => name.raw_size(),
+            RData::DNSKEY(dnskey) => dnskey.raw_size(),
+            RData::DS(ds) => ds.raw_size(),
+            RData::HINFO(hinfo) => hinfo.raw_size(),
+            RData::HTTPS(svcb) | RData::SVCB(svcb) => svcb.raw_size(),
+            RData::ISDN(isdn) => isdn.raw_size(),
+            RData::LOC(loc) => loc.raw_size(),
+            RData::MINFO(minfo) => minfo.raw_size(),
             RData::MX(mx) => mx.raw_size(),
-            _ => todo!()
+            RData::NAPTR(naptr) => naptr.raw_size(),
+            RData::NSAP(nsap) => nsap.raw_size(),
+            RData::NSEC(nsec) => nsec.raw_size(),
+            RData::NSEC3(nsec3) => nsec3.raw_size(),
+            RData::NSEC3PARAM(nsec3param) => nsec3param.raw_size(),
+            RData::NULL(null) => null.raw_size(),
+            RData::RRSIG(rrsig) => rrsig.raw_size(),
+            RData::RT(rt) => rt.raw_size(),
+            RData::RP(rp) => rp.raw_size(),
+            RData::SOA(soa) => soa.raw_size(),
+            RData::SRV(srv) => srv.raw_size(),
+            RData::SSHFP(sshfp) => sshfp.raw_size(),
+            RData::TLSA(tlsa) => tlsa.raw_size(),
+            RData::TXT(txt) => txt.raw_size(),
+            RData::URI(uri) => uri.raw_size(),
+            RData::WKS(wks) => wks.raw_size(),
+            RData::Unknown { bytes, .. } => bytes.len()
+        }
+    }
+}
+
+impl FromStr for RData {
+    type Err = ParseError;
+
+    /// Parses the type followed by the record data in presentation format, e.g. `MX 10 mail.example.com.`.
+    /// Relative names are taken to be relative to the root.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entry = single_entry(s)?;
+        let root = Name::root();
+        let mut tokens = Tokens::new(&entry.tokens, entry.end, &root);
+
+        let token = tokens.next("RData.rtype")?;
+        let rtype = token.text.parse().map_err(|e: ParseError| e.offset_by(token.offset))?;
+        let rdata = RData::from_tokens(rtype, &mut tokens)?;
+        tokens.finish()?;
+
+        Ok(rdata)
+    }
+}
+
+impl std::fmt::Display for RData {
+    /// Formats the data in the presentation format of its type as used in master files [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+    /// Data of unknown types uses the generic format, see [RData::to_generic_string].
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RData::A(ip) => write!(f, "{}", ip),
+            RData::AAAA(ip) => write!(f, "{}", ip),
+            RData::AFSDB(afsdb) => write!(f, "{}", afsdb),
+            RData::CAA(caa) => write!(f, "{}", caa),
+            RData::CERT(cert) => write!(f, "{}", cert),
+            RData::CNAME(name) | RData::DNAME(name) | RData::MB(name) | RData::MD(name) | RData::MF(name) | RData::MG(name)
+            | RData::MR(name) | RData::NS(name) | RData::PTR(name) => write!(f, "{}", name.to_fqdn()),
+            RData::DNSKEY(dnskey) => write!(f, "{}", dnskey),
+            RData::DS(ds) => write!(f, "{}", ds),
+            RData::HINFO(hinfo) => write!(f, "{}", hinfo),
+            RData::HTTPS(svcb) | RData::SVCB(svcb) => write!(f, "{}", svcb),
+            RData::ISDN(isdn) => write!(f, "{}", isdn),
+            RData::LOC(loc) => write!(f, "{}", loc),
+            RData::MINFO(minfo) => write!(f, "{}", minfo),
+            RData::MX(mx) => write!(f, "{}", mx),
+            RData::NAPTR(naptr) => write!(f, "{}", naptr),
+            RData::NSAP(nsap) => write!(f, "{}", nsap),
+            RData::NSEC(nsec) => write!(f, "{}", nsec),
+            RData::NSEC3(nsec3) => write!(f, "{}", nsec3),
+            RData::NSEC3PARAM(nsec3param) => write!(f, "{}", nsec3param),
+            RData::NULL(null) => write!(f, "{}", null),
+            RData::RRSIG(rrsig) => write!(f, "{}", rrsig),
+            RData::RT(rt) => write!(f, "{}", rt),
+            RData::RP(rp) => write!(f, "{}", rp),
+            RData::SOA(soa) => write!(f, "{}", soa),
+            RData::SRV(srv) => write!(f, "{}", srv),
+            RData::SSHFP(sshfp) => write!(f, "{}", sshfp),
+            RData::TLSA(tlsa) => write!(f, "{}", tlsa),
+            RData::TXT(txt) => write!(f, "{}", txt),
+            RData::URI(uri) => write!(f, "{}", uri),
+            RData::WKS(wks) => write!(f, "{}", wks),
+            RData::Unknown { .. } => write!(f, "{}", self.to_generic_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::Ipv4Addr, time::{Duration, UNIX_EPOCH}};
+
+    use crate::{application::dns::{EncodeOptions, MessageEncoder, Name, ResourceRecord, Type}, error::ParseErrorKind, Raw};
+
+    use super::*;
+
+    fn string(contents: &[u8]) -> CharacterString {
+        CharacterString::new(contents.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_rdata_unknown() {
+        let rdata = RData::from_bytes(Type::Unknown(65280), b"\x0A\x00\x00\x01").unwrap();
+        assert_eq!(rdata, RData::Unknown { rtype: Type::Unknown(65280), bytes: vec![0x0A, 0, 0, 1] });
+        assert_eq!(rdata.raw(), b"\x0A\x00\x00\x01");
+        assert_eq!(rdata.to_generic_string(), "\\# 4 0A000001");
+
+        // Known types may use the generic format, too.
+        assert_eq!(RData::from_bytes(Type::A, b"\x0A\x00\x00\x01").unwrap().to_generic_string(), "\\# 4 0A000001");
+        assert_eq!(RData::from_bytes(Type::NULL, b"").unwrap().to_generic_string(), "\\# 0");
+
+        // Types which are not parsed by this library only have the generic presentation format.
+        assert_presentation(&[(Type::Unknown(65280), b"\x0A\x00\x00\x01", r"\# 4 0A000001")]);
+        let err = RData::parse_with_origin(Type::Unknown(65280), "0A000001", &Name::root()).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Invalid("this type only supports the generic format"));
+    }
+
+    #[test]
+    fn test_rdata_mail_names() {
+        // The names of the obsolete mail types may be compressed, so they are expanded rather than kept verbatim.
+        let message = b"\x07example\x00\x04mail\xC0\x00";
+        for (rtype, rdata) in [
+            (Type::MB, RData::MB(Name::new("mail.example"))),
+            (Type::MD, RData::MD(Name::new("mail.example"))),
+            (Type::MF, RData::MF(Name::new("mail.example"))),
+            (Type::MG, RData::MG(Name::new("mail.example"))),
+            (Type::MR, RData::MR(Name::new("mail.example")))
+        ] {
+            assert_eq!(RData::from_message(rtype, message, 9, 7).unwrap(), rdata, "{:?}", rtype);
+            assert_eq!(rdata.raw(), b"\x04mail\x07example\x00");
+            assert_eq!(rdata.to_string(), "mail.example.");
+            assert_eq!(RData::parse_with_origin(rtype, "mail", &Name::new("example")).unwrap(), rdata);
+        }
+    }
+
+    #[test]
+    fn test_rdata_svcb() {
+        // Test vectors from RFC 9460 Appendix D
+        let alias = SVCB::from_bytes(b"\x00\x00\x03foo\x07example\x03com\x00").unwrap();
+        assert!(alias.is_alias() && alias.params.is_empty());
+        assert_eq!(alias.to_string(), "0 foo.example.com.");
+
+        let bytes = b"\x00\x10\x03foo\x07example\x03org\x00\x00\x00\x00\x04\x00\x01\x00\x04\x00\x01\x00\x09\x02h2\x05h3-19\x00\x04\x00\x04\xC0\x00\x02\x01";
+        let rdata = RData::from_bytes(Type::HTTPS, bytes).unwrap();
+        assert_eq!(rdata.raw(), bytes);
+        let RData::HTTPS(svcb) = rdata else { panic!("expected HTTPS record data") };
+        assert_eq!(svcb.params, vec![
+            SvcParam::Mandatory(vec![1, 4]),
+            SvcParam::Alpn(vec![string(b"h2"), string(b"h3-19")]),
+            SvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1)])
+        ]);
+        assert_eq!(svcb.param(4), Some(&svcb.params[2]));
+        assert_eq!(svcb.to_string(), "16 foo.example.org. mandatory=alpn,ipv4hint alpn=h2,h3-19 ipv4hint=192.0.2.1");
+
+        assert_presentation(&[(Type::HTTPS, b"\x00\x01\x00\x00\x01\x00\x03\x02h2", "1 . alpn=h2")]);
+        assert_eq!(RData::parse_with_origin(Type::HTTPS, r#"1 svc alpn="h2,h3" port=8443"#, &Name::new("example.com")).unwrap().to_string(),
+                   "1 svc.example.com. alpn=h2,h3 port=8443");
+
+        // Parameters are written in the order of their keys.
+        let svcb = SVCB::new(1, Name::root(), vec![
+            SvcParam::Unknown { key: 667, value: b"hello".to_vec() },
+            SvcParam::Port(53),
+            SvcParam::Ipv6Hint(vec!["2001:db8::1".parse().unwrap()]),
+            SvcParam::Ech(vec![0xFE, 0x0D]),
+            SvcParam::NoDefaultAlpn
+        ]);
+        assert_eq!(svcb.to_string(), "1 . no-default-alpn port=53 ech=/g0= ipv6hint=2001:db8::1 key667=hello");
+        let parsed = SVCB::from_bytes(&svcb.raw()).unwrap();
+        assert_eq!(parsed.params.iter().map(SvcParam::key).collect::<Vec<_>>(), vec![2, 3, 5, 6, 667]);
+        assert_eq!(parsed.raw_size(), svcb.raw_size());
+
+        // Presentation format of single parameters
+        for (s, param) in [
+            ("port=53", SvcParam::Port(53)),
+            ("no-default-alpn", SvcParam::NoDefaultAlpn),
+            (r#"alpn="f\\\\oo\\,bar,h2""#, SvcParam::Alpn(vec![string(b"f\\oo,bar"), string(b"h2")])),
+            ("mandatory=alpn,ipv4hint", SvcParam::Mandatory(vec![1, 4])),
+            ("ipv4hint=192.0.2.1,192.0.2.2", SvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)])),
+            ("key667=\"hello\\210qoo\"", SvcParam::Unknown { key: 667, value: b"hello\xD2qoo".to_vec() }),
+        ] {
+            assert_eq!(s.parse::<SvcParam>().unwrap(), param, "{}", s);
+            assert_eq!(param.to_string().parse::<SvcParam>().unwrap(), param, "{}", param);
+        }
+        assert_eq!(SvcParam::Alpn(vec![string(b"f\\oo,bar"), string(b"h2")]).to_string(), r"alpn=f\\\\oo\\,bar,h2");
+        assert_eq!("key65536=1".parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("unknown SvcParamKey"));
+        assert_eq!("port=http".parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("invalid port"));
+        assert_eq!("alpn=h2,,h3".parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("empty alpn-id"));
+        assert_eq!(format!("alpn={}", "a".repeat(256)).parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("alpn-ids may be at most 255 bytes long"));
+        assert_eq!("mandatory=port,mandatory".parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("mandatory must not list itself"));
+        assert_eq!("mandatory=port,port".parse::<SvcParam>().unwrap_err().kind, ParseErrorKind::Invalid("mandatory must not list a key twice"));
+        let err = "svc. 300 IN SVCB 1 . port=1 port=2".parse::<ResourceRecord>().unwrap_err();
+        assert_eq!((err.field, err.kind), ("SvcParam.key", ParseErrorKind::Invalid("SvcParamKeys must not repeat")));
+
+        // Mandatory keys are written in ascending order.
+        let svcb = SVCB::new(1, Name::root(), vec![SvcParam::Mandatory(vec![4, 1])]);
+        assert_eq!(svcb.raw(), b"\x00\x01\x00\x00\x00\x00\x04\x00\x01\x00\x04");
+
+        // Malformed wire format
+        let err = SVCB::from_bytes(b"\x00\x01\x00\x00\x03\x00\x02\x00\x35\x00\x01\x00\x03\x02h2").unwrap_err();
+        assert_eq!((err.offset, err.kind), (9, ParseErrorKind::Invalid("SvcParamKeys must be in strictly ascending order")));
+        let err = SVCB::from_bytes(b"\x00\x01\x00\x00\x03\x00\x01\x35").unwrap_err();
+        assert_eq!((err.offset, err.kind), (7, ParseErrorKind::BadLength(1)));
+        let err = SVCB::from_bytes(b"\x00\x01\x00\x00\x00\x00\x04\x00\x03\x00\x03").unwrap_err();
+        assert_eq!((err.offset, err.kind), (7, ParseErrorKind::Invalid("mandatory keys must be in strictly ascending order")));
+        let err = SVCB::from_bytes(b"\x00\x01\x00\x00\x00\x00\x04\x00\x00\x00\x03").unwrap_err();
+        assert_eq!((err.offset, err.kind), (7, ParseErrorKind::Invalid("mandatory must not list itself")));
+        let err = SVCB::from_bytes(b"\x00\x01\x00\x00\x01\x00\x04\x02h2\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, ParseErrorKind::Invalid("empty alpn-id")));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 65535 bytes")]
+    fn test_rdata_svc_param_too_long() {
+        // The length of the value must not wrap around.
+        SvcParam::Ech(vec![0; 70000]).raw();
+    }
+
+    /// Checks that the data of each case parses into its type and is written back unchanged.
+    fn assert_round_trip(cases: &[(Type, &[u8])]) {
+        for (rtype, bytes) in cases {
+            let rdata = RData::from_bytes(*rtype, bytes).unwrap();
+            assert!(!matches!(rdata, RData::Unknown { .. }));
+            assert_eq!(rdata.raw(), *bytes, "{:?}", rtype);
+            assert_eq!(rdata.raw_size(), bytes.len());
         }
     }
-}
\ No newline at end of file
+
+    /// Checks that the data of each case is formatted as the given text, which parses back into the same data.
+    fn assert_presentation(cases: &[(Type, &[u8], &str)]) {
+        for (rtype, bytes, text) in cases {
+            let rdata = RData::from_bytes(*rtype, bytes).unwrap();
+            assert_eq!(rdata.to_string(), *text, "{:?}", rtype);
+            assert_eq!(RData::parse_with_origin(*rtype, text, &Name::root()).unwrap(), rdata, "{:?}", rtype);
+            // Every type may also be written in the generic format of RFC 3597.
+            assert_eq!(RData::parse_with_origin(*rtype, &format!("\\# {} {}", bytes.len(), hex::encode(bytes)), &Name::root()).unwrap(), rdata, "{:?}", rtype);
+        }
+    }
+
+    #[test]
+    fn test_rdata_rfc1035() {
+        assert_round_trip(&[
+            (Type::HINFO, b"\x05AMD64\x05Linux"),
+            (Type::ISDN, b"\x0F150862028003217\x03004"),
+            (Type::ISDN, b"\x0F150862028003217"),
+            (Type::LOC, b"\x00\x12\x16\x13\x89\x16\xCC\x28\x70\xBE\xC1\xD0\x00\x98\x6F\x70"),
+            (Type::MINFO, b"\x05admin\x07example\x00\x06errors\x07example\x00"),
+            (Type::NSAP, b"\x47\x00\x05\x80\x00\x5A\x00"),
+            (Type::NULL, b"\x00\xFF\x10"),
+            (Type::RT, b"\x00\x0A\x05relay\x07example\x00"),
+            (Type::RP, b"\x05admin\x07example\x00\x04info\x07example\x00"),
+            (Type::SOA, b"\x03ns1\x07example\x00\x05admin\x07example\x00\x78\x56\x34\x12\x00\x00\x0E\x10\x00\x00\x02\x58\x00\x09\x3A\x80\x00\x00\x01\x2C"),
+            (Type::SRV, b"\x00\x0A\x00\x05\x14\x95\x03sip\x07example\x00"),
+            (Type::TXT, b"\x0Bv=spf1 -all\x00\x03abc"),
+            (Type::WKS, b"\xC0\x00\x02\x01\x06\x00\x00\x00\x40\x00\x00\x00\x01"),
+        ]);
+
+        assert_presentation(&[
+            (Type::HINFO, b"\x05AMD64\x05Linux", r#""AMD64" "Linux""#),
+            (Type::ISDN, b"\x0F150862028003217\x03004", r#""150862028003217" "004""#),
+            (Type::LOC, b"\x00\x12\x16\x13\x89\x16\xCC\x28\x70\xBE\xC1\xD0\x00\x98\x6F\x70", "42 21 29.000 N 71 5 34.000 W -100.00m 1.00m 10000.00m 10.00m"),
+            (Type::MINFO, b"\x05admin\x07example\x00\x06errors\x07example\x00", "admin.example. errors.example."),
+            (Type::NSAP, b"\x47\x00\x05\x80\x00\x5A\x00", "0x47000580005A00"),
+            (Type::NULL, b"\x00\xFF\x10", r"\# 3 00FF10"),
+            (Type::RP, b"\x05admin\x07example\x00\x04info\x07example\x00", "admin.example. info.example."),
+            (Type::SOA, b"\x03ns1\x07example\x00\x05admin\x07example\x00\x78\x56\x34\x12\x00\x00\x0E\x10\x00\x00\x02\x58\x00\x09\x3A\x80\x00\x00\x01\x2C",
+             "ns1.example. admin.example. 2018915346 3600 600 604800 300"),
+            (Type::SRV, b"\x00\x0A\x00\x05\x14\x95\x03sip\x07example\x00", "10 5 5269 sip.example."),
+            (Type::TXT, b"\x0Bv=spf1 -all\x00\x07\"a\\b\x07\xC3\xA9", r#""v=spf1 -all" "" "\"a\\b\007\195\169""#),
+            (Type::WKS, b"\xC0\x00\x02\x01\x06\x00\x00\x00\x40\x00\x00\x00\x01", "192.0.2.1 6 25 63"),
+        ]);
+
+        // Accessors
+        let soa = SOA::from_bytes(b"\x03ns1\x07example\x00\x05admin\x07example\x00\x78\x56\x34\x12\x00\x00\x0E\x10\x00\x00\x02\x58\x00\x09\x3A\x80\x00\x00\x01\x2C").unwrap();
+        assert_eq!(soa, SOA::new(Name::new("ns1.example"), Name::new("admin.example"), 0x78563412, 3600, 600, 604800, 300));
+
+        let txt = TXT::new(vec![string(b"v=DKIM1; k=rsa; "), string(b"p=MIGf")]).unwrap();
+        assert_eq!(txt.text(), "v=DKIM1; k=rsa; p=MIGf");
+        assert_eq!(TXT::from_text(&"a".repeat(300)).strings().iter().map(|s| s.as_bytes().len()).collect::<Vec<_>>(), vec![255, 45]);
+
+        assert_eq!(TXT::from_text("").strings(), [CharacterString::default()]);
+        let err = TXT::new(Vec::new()).unwrap_err();
+        assert_eq!((err.field, err.kind), ("TXT", ParseErrorKind::BadLength(0)));
+        let err = TXT::from_bytes(b"").unwrap_err();
+        assert_eq!((err.offset, err.kind), (0, ParseErrorKind::BadLength(0)));
+
+        // Character-strings longer than 255 bytes cannot be encoded, so they are rejected on construction.
+        let err = CharacterString::new(vec![b'a'; 256]).unwrap_err();
+        assert_eq!((err.field, err.kind), ("CharacterString", ParseErrorKind::BadLength(256)));
+        let err = format!("example. 300 IN HINFO AMD64 {}", "a".repeat(256)).parse::<ResourceRecord>().unwrap_err();
+        assert_eq!((err.field, err.kind), ("HINFO.os", ParseErrorKind::BadLength(256)));
+        assert!(RData::parse_with_origin(Type::TXT, &format!("\"{}\"", "a".repeat(256)), &Name::root()).is_err());
+
+        let srv = SRV::from_bytes(b"\x00\x0A\x00\x05\x14\x95\x03sip\x07example\x00").unwrap();
+        assert_eq!((srv.priority, srv.weight, srv.port, &srv.target), (10, 5, 5269, &Name::new("sip.example")));
+
+        let wks = WKS::from_ports(Ipv4Addr::new(192, 0, 2, 1), 6, &[25, 63]);
+        assert_eq!(wks.bitmap, b"\x00\x00\x00\x40\x00\x00\x00\x01");
+        assert_eq!(wks.ports(), vec![25, 63]);
+        // A bit past port 65535 must not wrap around to port 0.
+        let mut bitmap = WKS::from_ports(Ipv4Addr::new(192, 0, 2, 1), 6, &[65535]).bitmap;
+        bitmap.extend_from_slice(b"\x80");
+        assert_eq!(WKS::new(Ipv4Addr::new(192, 0, 2, 1), 6, bitmap).ports(), vec![65535]);
+
+        let loc = LOC::from_bytes(b"\x00\x12\x16\x13\x89\x16\xCC\x28\x70\xBE\xC1\xD0\x00\x98\x6F\x70").unwrap();
+        assert!((loc.latitude_degrees() - 42.3581).abs() < 0.001);
+        assert!((loc.longitude_degrees() + 71.0928).abs() < 0.001);
+        assert_eq!(loc.altitude_meters(), -100.0);
+        assert_eq!((loc.size_meters(), loc.horizontal_precision_meters(), loc.vertical_precision_meters()), (1.0, 10000.0, 10.0));
+
+        // The data must fill the record data exactly.
+        let err = RData::from_bytes(Type::SRV, b"\x00\x0A\x00\x05\x14\x95\x00\x00").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::BadLength(8));
+
+        // Names in SOA records are compressed, those in SRV records are not.
+        let mut encoder = MessageEncoder::new(EncodeOptions::default());
+        encoder.write_name(&Name::new("example"));
+        RData::SOA(soa).encode(&mut encoder);
+        RData::SRV(srv).encode(&mut encoder);
+        assert_eq!(&encoder.bytes()[9..27], b"\x03ns1\xC0\x00\x05admin\xC0\x00\x78\x56\x34\x12");
+        assert_eq!(&encoder.bytes()[43..], b"\x00\x0A\x00\x05\x14\x95\x03sip\x07example\x00");
+    }
+
+    #[test]
+    fn test_rdata_name_types() {
+        assert_round_trip(&[
+            (Type::CNAME, b"\x03www\x07example\x00"),
+            (Type::DNAME, b"\x07example\x00"),
+            (Type::NS, b"\x03ns1\x07example\x00"),
+            (Type::PTR, b"\x04host\x07example\x00"),
+        ]);
+
+        assert_presentation(&[
+            (Type::CNAME, b"\x03www\x07example\x00", "www.example."),
+            (Type::NS, b"\x00", "."),
+        ]);
+
+        // Names in CNAME records are compressed, those in DNAME records are not.
+        let mut encoder = MessageEncoder::new(EncodeOptions::default());
+        encoder.write_name(&Name::new("example"));
+        RData::CNAME(Name::new("www.example")).encode(&mut encoder);
+        RData::DNAME(Name::new("example")).encode(&mut encoder);
+        assert_eq!(&encoder.bytes()[9..], b"\x03www\xC0\x00\x07example\x00");
+    }
+
+    #[test]
+    fn test_rdata_dnssec() {
+        assert_round_trip(&[
+            (Type::DS, b"\xEC\x45\x05\x01\x2B\xB1\x83\xAF\x5F\x22\x58\x81\x79\xA5\x3B\x0A\x98\x63\x1F\xAD\x1A\x29\x21\x18"),
+            (Type::NSEC3, b"\x01\x01\x00\x0C\x04\xAA\xBB\xCC\xDD\x14\x2C\x7E\x94\x23\xA7\x1B\x5E\x7A\xA3\xA6\x51\x24\x74\x84\xB5\x7C\x61\x3F\x02\xA8\x00\x06\x40\x00\x00\x00\x00\x02"),
+            (Type::NSEC3PARAM, b"\x01\x00\x00\x0C\x04\xAA\xBB\xCC\xDD"),
+        ]);
+
+        assert_presentation(&[
+            (Type::DS, b"\xEC\x45\x05\x01\x2B\xB1\x83\xAF\x5F\x22\x58\x81\x79\xA5\x3B\x0A\x98\x63\x1F\xAD\x1A\x29\x21\x18",
+             "60485 5 1 2BB183AF5F22588179A53B0A98631FAD1A292118"),
+            (Type::NSEC, b"\x04host\x07example\x00\x00\x06\x40\x01\x00\x00\x00\x03", "host.example. A MX RRSIG NSEC"),
+            (Type::NSEC3, b"\x01\x01\x00\x0C\x04\xAA\xBB\xCC\xDD\x14\x2C\x7E\x94\x23\xA7\x1B\x5E\x7A\xA3\xA6\x51\x24\x74\x84\xB5\x7C\x61\x3F\x02\xA8\x00\x06\x40\x00\x00\x00\x00\x02",
+             "1 1 12 AABBCCDD 5HV988T73DF7L8T6A4I7915LFHGJU0L8 A RRSIG"),
+            (Type::NSEC3PARAM, b"\x01\x00\x00\x00\x00", "1 0 0 -"),
+            (Type::DNSKEY, b"\x01\x01\x03\x0D\xAA\xBB\xCC", "257 3 13 qrvM"),
+        ]);
+
+        // Records taken from the examples of RFC 4034
+        let dnskey = DNSKEY::from_bytes(&hex::decode("010003050103D22A6CA77F35B893206FD35E4C506D8378843709B97E041647E1BFF43D8D64C649AF1E371973C9E891FCE3DF519A8C840A63EE42A6D2EBDDBB97035D215AA4E417B1FA45FA11A9741EA2098C1DFA5FB5FEB332FD4BC8152089AEF36BA644CCE2413B3B72BE18CBEF8DA253F4E93D2103866D9234A2E28DF529A67D5468DBEFE3").unwrap()).unwrap();
+        assert_eq!((dnskey.algorithm, dnskey.is_zone_key(), dnskey.is_secure_entry_point()), (Algorithm::RSASHA1, true, false));
+        assert_eq!(dnskey.key_tag(), 2642);
+
+        let rrsig_bytes = b"\x00\x01\x05\x03\x00\x01\x51\x80\x3E\x7C\x9D\xD7\x3E\x55\x10\xD7\x0A\x52\x07example\x03com\x00\x01\x02\x03";
+        let rrsig = RData::from_bytes(Type::RRSIG, rrsig_bytes).unwrap();
+        assert_eq!(rrsig, RData::RRSIG(RRSIG::new(Type::A, Algorithm::RSASHA1, 3, 86400, 0x3E7C9DD7, 0x3E5510D7, 2642, Name::new("example.com"), vec![1, 2, 3])));
+        assert_eq!(rrsig.raw(), rrsig_bytes);
+        if let RData::RRSIG(rrsig) = rrsig {
+            assert_eq!(rrsig.inception_time(), UNIX_EPOCH + Duration::from_secs(1045762263));
+            assert!(rrsig.is_valid_at(UNIX_EPOCH + Duration::from_secs(1046000000)));
+            assert!(!rrsig.is_valid_at(UNIX_EPOCH + Duration::from_secs(1048400000)));
+        }
+
+        let nsec_bytes = b"\x04host\x07example\x03com\x00\x00\x06\x40\x01\x00\x00\x00\x03\x04\x1B\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x20";
+        let nsec = NSEC::from_bytes(nsec_bytes).unwrap();
+        assert_eq!(nsec.types.types(), vec![Type::A, Type::MX, Type::RRSIG, Type::NSEC, Type::Unknown(1234)]);
+        assert!(nsec.types.contains(Type::MX) && !nsec.types.contains(Type::AAAA));
+        assert_eq!(NSEC::new(Name::new("host.example.com"), vec![Type::Unknown(1234), Type::NSEC, Type::A, Type::RRSIG, Type::MX, Type::A]).raw(), nsec_bytes);
+
+        let nsec3 = NSEC3::from_bytes(b"\x01\x01\x00\x0C\x00\x01\xFF").unwrap();
+        assert!(nsec3.is_opt_out() && nsec3.types.is_empty());
+
+        let salt = CharacterString::new(b"\xAA\xBB".to_vec()).unwrap();
+        let hash = CharacterString::new(vec![0x11; 20]).unwrap();
+        let nsec3 = NSEC3::new(1, 0, 10, salt.clone(), hash, vec![Type::A]);
+        assert_eq!(NSEC3::from_bytes(&nsec3.raw()).unwrap(), nsec3);
+        assert_eq!(NSEC3PARAM::from_bytes(b"\x01\x00\x00\x0A\x02\xAA\xBB").unwrap(), NSEC3PARAM::new(1, 0, 10, salt));
+
+        // Salts and hashes longer than 255 bytes cannot be encoded, so they are rejected when parsing text.
+        let err = RData::parse_with_origin(Type::NSEC3PARAM, &format!("1 0 0 {}", "AB".repeat(256)), &Name::root()).unwrap_err();
+        assert_eq!((err.field, err.offset, err.kind), ("NSEC3PARAM.salt", 6, ParseErrorKind::BadLength(256)));
+        let err = RData::parse_with_origin(Type::NSEC3, &format!("1 0 0 - {}", "00".repeat(205)), &Name::root()).unwrap_err();
+        assert_eq!((err.field, err.offset, err.kind), ("NSEC3.next_hashed_owner", 8, ParseErrorKind::BadLength(256)));
+
+        // Bitmaps are unique for a set of types, so that records with the same types are equal.
+        let nsec = NSEC::from_bytes(b"\x00\x00\x01\x40").unwrap();
+        assert_eq!(nsec, NSEC::new(Name::root(), vec![Type::A]));
+        assert_eq!(nsec.raw(), b"\x00\x00\x01\x40");
+        let err = NSEC::from_bytes(b"\x00\x00\x02\x40\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (4, ParseErrorKind::Invalid("type bitmap windows must not end with a zero byte")));
+        let err = NSEC::from_bytes(b"\x00\x00\x01\x00\x01\x01\x40").unwrap_err();
+        assert_eq!(err.offset, 3);
+
+        // Malformed type bitmaps
+        let err = NSEC::from_bytes(b"\x00\x00\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, ParseErrorKind::BadLength(0)));
+        let err = NSEC::from_bytes(b"\x00\x01\x01\x40\x00\x01\x40").unwrap_err();
+        assert_eq!((err.offset, err.kind), (4, ParseErrorKind::Invalid("type bitmap windows must be in ascending order")));
+
+        // The presentation format of signatures, with the validity period as a date in UTC
+        let rrsig = RData::parse_with_origin(Type::RRSIG, "A 5 3 86400 20030322173103 20030220173103 2642 example.com. 3q0=", &Name::root()).unwrap();
+        match rrsig {
+            RData::RRSIG(rrsig) => assert_eq!((rrsig.expiration, rrsig.inception), (1048354263, 1045762263)),
+            _ => panic!("not an RRSIG")
+        }
+
+        // Example from RFC 4034 §3.3
+        let rrsig = RRSIG::new(Type::A, Algorithm::RSASHA1, 3, 86400, 1048354263, 1045762263, 2642, Name::new("example.com"), vec![0xDE, 0xAD]);
+        assert_eq!(rrsig.to_string(), "A 5 3 86400 20030322173103 20030220173103 2642 example.com. 3q0=");
+        let rrsig = RRSIG { expiration: u32::MAX, inception: 951868799, ..rrsig };
+        assert_eq!(rrsig.to_string(), "A 5 3 86400 21060207062815 20000229235959 2642 example.com. 3q0=");
+    }
+
+    #[test]
+    fn test_rdata_caa_tlsa_sshfp() {
+        assert_round_trip(&[
+            (Type::CAA, b"\x00\x05issueletsencrypt.org"),
+            (Type::CAA, b"\x80\x05iodefmailto:security@example.com"),
+            (Type::TLSA, b"\x03\x01\x01\x0D\x6F\xFB\x6E\x2D\x8E\x3F\x50\x1E\x8A\x4E\x36\x4C\xB0\xB0\xE3\x27\xD4\x2A\x4A\x47\x0E\x2F\x4A\x86\x83\x59\x8E\x06\xC4\xC0\xB2\x10"),
+            (Type::SSHFP, b"\x04\x02\xA8\x7F\x1B\x68\x7A\xC0\xE5\x7D\x2A\x08\x1A\x2F\x28\x26\x72\x33\x4D\x90\xED\x31\x6D\x2B\x81\x8C\xA9\x58\x0E\xA3\x84\xD9\x24\x01"),
+        ]);
+
+        assert_presentation(&[
+            (Type::CAA, b"\x80\x05iodefmailto:security@example.com", r#"128 iodef "mailto:security@example.com""#),
+            (Type::TLSA, b"\x03\x01\x01\x0D\x6F\xFB", "3 1 1 0D6FFB"),
+            (Type::SSHFP, b"\x04\x02\xA8\x7F", "4 2 A87F"),
+        ]);
+
+        let caa = CAA::from_bytes(b"\x80\x05iodefmailto:security@example.com").unwrap();
+        assert!(caa.is_critical());
+        assert_eq!((caa.tag(), caa.value.as_slice()), ("iodef", b"mailto:security@example.com".as_slice()));
+        let err = CAA::from_bytes(b"\x00\x00").unwrap_err();
+        assert_eq!((err.offset, err.kind), (1, ParseErrorKind::BadLength(0)));
+        let err = CAA::from_bytes(b"\x00\x05iss-e;").unwrap_err();
+        assert_eq!((err.offset, err.kind), (5, ParseErrorKind::InvalidCharacter('-')));
+
+        // The value is not a character-string, so it may be longer than 255 bytes.
+        let caa = RData::CAA(CAA::new(0, String::from("issue"), vec![b'a'; 300]).unwrap());
+        assert_eq!(RData::parse_with_origin(Type::CAA, &caa.to_string(), &Name::root()).unwrap(), caa);
+
+        // Tags which cannot be encoded or read back are rejected on construction.
+        let err = CAA::new(0, "a".repeat(300), Vec::new()).unwrap_err();
+        assert_eq!((err.field, err.kind), ("CAA.tag", ParseErrorKind::BadLength(300)));
+        let err = CAA::new(0, String::new(), Vec::new()).unwrap_err();
+        assert_eq!((err.field, err.kind), ("CAA.tag", ParseErrorKind::BadLength(0)));
+        let err = CAA::new(0, String::from("iss-e"), Vec::new()).unwrap_err();
+        assert_eq!((err.offset, err.kind), (3, ParseErrorKind::InvalidCharacter('-')));
+        let err = RData::parse_with_origin(Type::CAA, "0 iss-e \"ca.example\"", &Name::root()).unwrap_err();
+        assert_eq!((err.offset, err.kind), (5, ParseErrorKind::InvalidCharacter('-')));
+
+        let tlsa = TLSA::new(3, 1, 1, vec![0xAB; 32]);
+        assert_eq!(TLSA::from_bytes(&tlsa.raw()).unwrap(), tlsa);
+    }
+
+    #[test]
+    fn test_rdata_naptr_uri_cert() {
+        assert_round_trip(&[
+            (Type::NAPTR, b"\x00\x64\x00\x0A\x01U\x07E2U+sip\x1B!^.*$!sip:info@example.com!\x00"),
+            (Type::NAPTR, b"\x00\x64\x00\x32\x01s\x07SIP+D2U\x00\x04_sip\x04_udp\x07example\x00"),
+            (Type::URI, b"\x00\x0A\x00\x01ftp://ftp1.example.com/public"),
+            (Type::CERT, b"\x00\x01\x30\x39\x08\x30\x82\x01\x0A"),
+        ]);
+
+        assert_presentation(&[
+            (Type::NAPTR, b"\x00\x64\x00\x0A\x01U\x07E2U+sip\x1B!^.*$!sip:info@example.com!\x00",
+             r#"100 10 "U" "E2U+sip" "!^.*$!sip:info@example.com!" ."#),
+            (Type::URI, b"\x00\x0A\x00\x01ftp://ftp1.example.com/public", r#"10 1 "ftp://ftp1.example.com/public""#),
+            (Type::CERT, b"\x00\x01\x30\x39\x08\x30\x82\x01\x0A", "1 12345 8 MIIBCg=="),
+        ]);
+
+        let naptr = NAPTR::from_bytes(b"\x00\x64\x00\x32\x01s\x07SIP+D2U\x00\x04_sip\x04_udp\x07example\x00").unwrap();
+        assert_eq!(naptr, NAPTR::new(100, 50, string(b"s"), string(b"SIP+D2U"), CharacterString::default(), Name::new("_sip._udp.example")));
+
+        let cert = CERT::from_bytes(b"\x00\x01\x30\x39\x08\x30\x82\x01\x0A").unwrap();
+        assert_eq!((cert.cert_type, cert.key_tag, cert.algorithm), (1, 12345, Algorithm::RSASHA256));
+
+        assert_eq!(URI::from_bytes(b"\x00\x0A\x00\x01ftp://ftp1.example.com/public").unwrap().target, b"ftp://ftp1.example.com/public");
+
+        // The target is not a character-string, so it may be longer than 255 bytes.
+        let uri = RData::URI(URI::new(10, 1, format!("https://example.com/{}", "a".repeat(300)).into_bytes()));
+        assert_eq!(RData::parse_with_origin(Type::URI, &uri.to_string(), &Name::root()).unwrap(), uri);
+    }
+
+    #[test]
+    fn test_rdata_presentation() {
+        assert_presentation(&[
+            (Type::A, b"\xC0\x00\x02\x01", "192.0.2.1"),
+            (Type::AAAA, b"\x20\x01\x0D\xB8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01", "2001:db8::1"),
+            (Type::MX, b"\x00\x0A\x04mail\x07example\x00", "10 mail.example."),
+        ]);
+
+        // Relative names, TTL units, unquoted character-strings and multiple tokens of Base64
+        let origin = Name::new("example.com");
+        assert_eq!(RData::parse_with_origin(Type::MX, "10 mail", &origin).unwrap(), RData::MX(MX::new(10, Name::new("mail.example.com"))));
+        assert_eq!(RData::parse_with_origin(Type::SOA, "@ admin ( 1 1h 10m 1w 5m )", &origin).unwrap().to_string(),
+                   "example.com. admin.example.com. 1 3600 600 604800 300");
+        assert_eq!(RData::parse_with_origin(Type::TXT, r#"v=spf1 "a b" \"c\""#, &origin).unwrap().to_string(), r#""v=spf1" "a b" "\"c\"""#);
+        assert_eq!(RData::parse_with_origin(Type::DNSKEY, "257 3 13 qr vM", &origin).unwrap().to_string(), "257 3 13 qrvM");
+        assert_eq!(RData::parse_with_origin(Type::LOC, "42 21 N 71 W 10m", &origin).unwrap().to_string(),
+                   "42 21 0.000 N 71 0 0.000 W 10.00m 1.00m 10000.00m 10.00m");
+        assert_eq!("AAAA 2001:db8::1".parse::<RData>().unwrap(), RData::AAAA("2001:db8::1".parse().unwrap()));
+        assert_eq!("TYPE1 \\# 4 C0000201".parse::<RData>().unwrap(), RData::A("192.0.2.1".parse().unwrap()));
+
+        let err = RData::parse_with_origin(Type::A, "192.0.2", &origin).unwrap_err();
+        assert_eq!((err.offset, err.kind), (0, ParseErrorKind::Invalid("invalid value")));
+        let err = RData::parse_with_origin(Type::MX, "10", &origin).unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, ParseErrorKind::Invalid("missing field")));
+        let err = RData::parse_with_origin(Type::A, "192.0.2.1 extra", &origin).unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, ParseErrorKind::Invalid("unexpected trailing data")));
+        assert!(RData::parse_with_origin(Type::NULL, "\\# 3 00FF", &origin).is_err());
+    }
+}
@@ -2,7 +2,7 @@ use std::mem::size_of;
 
 use byteorder::{NetworkEndian, ByteOrder};
 
-use crate::{application::dns::Name, Raw};
+use crate::{application::dns::{Name, MessageEncoder, read_u16}, error::ParseError, Raw};
 
 
 /// Used for mail exchange information
@@ -21,11 +21,22 @@ impl MX {
         MX { preference, host }
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        MX {
-            preference: NetworkEndian::read_u16(bytes),
-            host: Name::from_bytes(&bytes[2..])
-        }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        MX::from_message(bytes, 0).map(|(mx, _)| mx)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let preference = read_u16(message, offset, "MX.preference")?;
+        let (host, host_size) = Name::from_message(message, offset + 2)?;
+
+        Ok((MX { preference, host }, 2 + host_size))
+    }
+
+    /// Writes the record data to the given message encoder, compressing the host name if enabled.
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write_u16(self.preference);
+        encoder.write_name(&self.host);
     }
 }
 
@@ -45,4 +56,10 @@ impl Raw for MX {
     fn raw_size(&self) -> usize {
         size_of::<u16>() + self.host.raw_size()
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Display for MX {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.preference, self.host.to_fqdn())
+    }
+}
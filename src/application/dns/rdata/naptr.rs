@@ -0,0 +1,84 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{Name, read_u16}, error::ParseError, Raw};
+
+use super::CharacterString;
+
+
+/// Naming authority pointer, a rule for rewriting a string into a name or URI.
+/// [RFC 3403](https://datatracker.ietf.org/doc/html/rfc3403)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NAPTR {
+    /// The order in which records must be processed, lower values first.
+    pub order: u16,
+
+    /// The preference among records with the same order, lower values first.
+    pub preference: u16,
+
+    /// A character-string of flags which control the rewriting, e.g. "U" for a terminal rule producing a URI.
+    pub flags: CharacterString,
+
+    /// A character-string which specifies the services available down this rewrite path.
+    pub services: CharacterString,
+
+    /// A character-string containing a substitution expression applied to the original string.
+    pub regexp: CharacterString,
+
+    /// The next name to query, if the regular expression is empty.
+    pub replacement: Name
+}
+
+impl NAPTR {
+    pub fn new(order: u16, preference: u16, flags: CharacterString, services: CharacterString, regexp: CharacterString, replacement: Name) -> Self {
+        NAPTR { order, preference, flags, services, regexp, replacement }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NAPTR::from_message(bytes, 0).map(|(naptr, _)| naptr)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let order = read_u16(message, offset, "NAPTR.order")?;
+        let preference = read_u16(message, offset + 2, "NAPTR.preference")?;
+
+        let mut size = 4;
+        let (flags, flags_size) = CharacterString::from_message(message, offset + size, "NAPTR.flags")?;
+        size += flags_size;
+        let (services, services_size) = CharacterString::from_message(message, offset + size, "NAPTR.services")?;
+        size += services_size;
+        let (regexp, regexp_size) = CharacterString::from_message(message, offset + size, "NAPTR.regexp")?;
+        size += regexp_size;
+        let (replacement, replacement_size) = Name::from_message(message, offset + size)?;
+        size += replacement_size;
+
+        Ok((NAPTR { order, preference, flags, services, regexp, replacement }, size))
+    }
+}
+
+impl Raw for NAPTR {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 2 * size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.order);
+        NetworkEndian::write_u16(&mut bytes[2..4], self.preference);
+        bytes.append(&mut self.flags.raw());
+        bytes.append(&mut self.services.raw());
+        bytes.append(&mut self.regexp.raw());
+        bytes.append(&mut self.replacement.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 * size_of::<u16>() + self.flags.raw_size() + self.services.raw_size() + self.regexp.raw_size() + self.replacement.raw_size()
+    }
+}
+
+impl std::fmt::Display for NAPTR {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {} {} {}", self.order, self.preference, self.flags, self.services, self.regexp, self.replacement.to_fqdn())
+    }
+}
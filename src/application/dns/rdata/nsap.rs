@@ -0,0 +1,44 @@
+use crate::{error::ParseError, Raw};
+
+
+/// An OSI network service access point address [RFC 1706](https://datatracker.ietf.org/doc/html/rfc1706)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NSAP {
+    /// The NSAP address in binary form.
+    pub address: Vec<u8>
+}
+
+impl NSAP {
+    pub fn new(address: Vec<u8>) -> Self {
+        NSAP { address }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NSAP::from_message(bytes, 0).map(|(nsap, _)| nsap)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let address = message.get(offset..).unwrap_or_default().to_vec();
+        let size = address.len();
+
+        Ok((NSAP { address }, size))
+    }
+}
+
+impl Raw for NSAP {
+    fn raw(&self) -> Vec<u8> {
+        self.address.clone()
+    }
+
+    fn raw_size(&self) -> usize {
+        self.address.len()
+    }
+}
+
+impl std::fmt::Display for NSAP {
+    /// Formats the address as hexadecimal digits prefixed with `0x` [RFC 1706 §5](https://datatracker.ietf.org/doc/html/rfc1706#section-5).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode_upper(&self.address))
+    }
+}
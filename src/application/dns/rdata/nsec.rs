@@ -0,0 +1,59 @@
+use crate::{application::dns::{Name, Type}, error::ParseError, Raw};
+
+use super::TypeBitmap;
+
+
+/// Next secure record, which proves that no names exist between its owner and the next name in the zone
+/// and which types exist at its owner. [RFC 4034 §4](https://datatracker.ietf.org/doc/html/rfc4034#section-4)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NSEC {
+    /// The next owner name in the canonical order of the zone.
+    pub next_domain: Name,
+
+    /// The types of the records which exist at the owner name.
+    pub types: TypeBitmap
+}
+
+impl NSEC {
+    /// Constructs a record with the canonical type bitmap of the given types, which may be in any order.
+    pub fn new(next_domain: Name, types: Vec<Type>) -> Self {
+        NSEC { next_domain, types: TypeBitmap::new(&types) }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NSEC::from_message(bytes, 0).map(|(nsec, _)| nsec)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (next_domain, name_size) = Name::from_message(message, offset)?;
+        let (types, types_size) = TypeBitmap::from_message(message, offset + name_size, "NSEC.types")?;
+
+        Ok((NSEC { next_domain, types }, name_size + types_size))
+    }
+}
+
+impl Raw for NSEC {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = self.next_domain.raw();
+        bytes.append(&mut self.types.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.next_domain.raw_size() + self.types.raw_size()
+    }
+}
+
+impl std::fmt::Display for NSEC {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.next_domain.to_fqdn())?;
+
+        for rtype in self.types.types() {
+            write!(f, " {}", rtype)?;
+        }
+
+        Ok(())
+    }
+}
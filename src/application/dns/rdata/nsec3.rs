@@ -0,0 +1,95 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+use data_encoding::BASE32HEX_NOPAD;
+
+use crate::{application::dns::{Type, read_u16, read_u8}, error::ParseError, Raw};
+
+use super::{format_salt, CharacterString, TypeBitmap};
+
+
+/// Marks an NSEC3 record which may cover unsigned delegations.
+const OPT_OUT: u8 = 0b0000_0001;
+
+/// Hashed next secure record, which proves the non-existence of names by their hashes.
+/// [RFC 5155 §3](https://datatracker.ietf.org/doc/html/rfc5155#section-3)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NSEC3 {
+    /// The hash function applied to the names, where 1 is SHA-1.
+    pub hash_algorithm: u8,
+
+    pub flags: u8,
+
+    /// The number of additional times the hash function is applied.
+    pub iterations: u16,
+
+    /// Appended to the name before every application of the hash function.
+    pub salt: CharacterString,
+
+    /// The next hashed owner name in hash order, as raw bytes rather than the base32 owner label.
+    pub next_hashed_owner: CharacterString,
+
+    /// The types of the records which exist at the original owner name.
+    pub types: TypeBitmap
+}
+
+impl NSEC3 {
+    /// Constructs a record with the canonical type bitmap of the given types, which may be in any order.
+    pub fn new(hash_algorithm: u8, flags: u8, iterations: u16, salt: CharacterString, next_hashed_owner: CharacterString, types: Vec<Type>) -> Self {
+        NSEC3 { hash_algorithm, flags, iterations, salt, next_hashed_owner, types: TypeBitmap::new(&types) }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NSEC3::from_message(bytes, 0).map(|(nsec3, _)| nsec3)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let hash_algorithm = read_u8(message, offset, "NSEC3.hash_algorithm")?;
+        let flags = read_u8(message, offset + 1, "NSEC3.flags")?;
+        let iterations = read_u16(message, offset + 2, "NSEC3.iterations")?;
+
+        let (salt, salt_size) = CharacterString::from_message(message, offset + 4, "NSEC3.salt")?;
+        let (next_hashed_owner, hash_size) = CharacterString::from_message(message, offset + 4 + salt_size, "NSEC3.next_hashed_owner")?;
+
+        let (types, types_size) = TypeBitmap::from_message(message, offset + 4 + salt_size + hash_size, "NSEC3.types")?;
+        let size = 4 + salt_size + hash_size + types_size;
+
+        Ok((NSEC3 { hash_algorithm, flags, iterations, salt, next_hashed_owner, types }, size))
+    }
+
+    /// Returns whether or not the record may cover unsigned delegations.
+    pub fn is_opt_out(&self) -> bool {
+        self.flags & OPT_OUT != 0
+    }
+}
+
+impl Raw for NSEC3 {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![self.hash_algorithm, self.flags, 0, 0];
+
+        NetworkEndian::write_u16(&mut bytes[2..4], self.iterations);
+        bytes.append(&mut self.salt.raw());
+        bytes.append(&mut self.next_hashed_owner.raw());
+        bytes.append(&mut self.types.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 + size_of::<u16>() + self.salt.raw_size() + self.next_hashed_owner.raw_size() + self.types.raw_size()
+    }
+}
+
+impl std::fmt::Display for NSEC3 {
+    /// Formats the record data with the salt in hexadecimal and the next hashed owner name in Base32hex without padding [RFC 5155 §3.3](https://datatracker.ietf.org/doc/html/rfc5155#section-3.3).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {} {}", self.hash_algorithm, self.flags, self.iterations, format_salt(self.salt.as_bytes()), BASE32HEX_NOPAD.encode(self.next_hashed_owner.as_bytes()))?;
+
+        for rtype in self.types.types() {
+            write!(f, " {}", rtype)?;
+        }
+
+        Ok(())
+    }
+}
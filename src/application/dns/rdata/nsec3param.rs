@@ -0,0 +1,66 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{read_u16, read_u8}, error::ParseError, Raw};
+
+use super::{format_salt, CharacterString};
+
+
+/// The parameters an authoritative server uses to compute the hashes of NSEC3 records.
+/// [RFC 5155 §4](https://datatracker.ietf.org/doc/html/rfc5155#section-4)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NSEC3PARAM {
+    /// The hash function applied to the names, where 1 is SHA-1.
+    pub hash_algorithm: u8,
+
+    /// Must be 0 in NSEC3PARAM records.
+    pub flags: u8,
+
+    /// The number of additional times the hash function is applied.
+    pub iterations: u16,
+
+    /// Appended to the name before every application of the hash function.
+    pub salt: CharacterString
+}
+
+impl NSEC3PARAM {
+    pub fn new(hash_algorithm: u8, flags: u8, iterations: u16, salt: CharacterString) -> Self {
+        NSEC3PARAM { hash_algorithm, flags, iterations, salt }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NSEC3PARAM::from_message(bytes, 0).map(|(nsec3param, _)| nsec3param)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let hash_algorithm = read_u8(message, offset, "NSEC3PARAM.hash_algorithm")?;
+        let flags = read_u8(message, offset + 1, "NSEC3PARAM.flags")?;
+        let iterations = read_u16(message, offset + 2, "NSEC3PARAM.iterations")?;
+        let (salt, salt_size) = CharacterString::from_message(message, offset + 4, "NSEC3PARAM.salt")?;
+
+        Ok((NSEC3PARAM { hash_algorithm, flags, iterations, salt }, 4 + salt_size))
+    }
+}
+
+impl Raw for NSEC3PARAM {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![self.hash_algorithm, self.flags, 0, 0];
+
+        NetworkEndian::write_u16(&mut bytes[2..4], self.iterations);
+        bytes.append(&mut self.salt.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 + size_of::<u16>() + self.salt.raw_size()
+    }
+}
+
+impl std::fmt::Display for NSEC3PARAM {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.hash_algorithm, self.flags, self.iterations, format_salt(self.salt.as_bytes()))
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{error::ParseError, Raw};
+
+
+/// Used to represent arbitrary data of up to 65535 bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NULL {
+    pub data: Vec<u8>
+}
+
+impl NULL {
+    pub fn new(data: Vec<u8>) -> Self {
+        NULL { data }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        NULL::from_message(bytes, 0).map(|(null, _)| null)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let data = message.get(offset..).unwrap_or_default().to_vec();
+        let size = data.len();
+
+        Ok((NULL { data }, size))
+    }
+}
+
+impl Raw for NULL {
+    fn raw(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn raw_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl std::fmt::Display for NULL {
+    /// NULL records have no presentation format of their own, so this uses the generic one of [RFC 3597 §5](https://datatracker.ietf.org/doc/html/rfc3597#section-5).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.data.is_empty() {
+            true => write!(f, "\\# 0"),
+            false => write!(f, "\\# {} {}", self.data.len(), hex::encode_upper(&self.data))
+        }
+    }
+}
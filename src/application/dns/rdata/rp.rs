@@ -0,0 +1,51 @@
+use crate::{application::dns::Name, error::ParseError, Raw};
+
+
+/// Identifies the person responsible for a domain [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-2.2)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RP {
+    /// The mailbox of the responsible person, in the same form as the rname of an SOA record.
+    pub mbox: Name,
+
+    /// A name for which TXT records with further information exist.
+    pub txt: Name
+}
+
+impl RP {
+    pub fn new(mbox: Name, txt: Name) -> Self {
+        RP { mbox, txt }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        RP::from_message(bytes, 0).map(|(rp, _)| rp)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (mbox, mbox_size) = Name::from_message(message, offset)?;
+        let (txt, txt_size) = Name::from_message(message, offset + mbox_size)?;
+
+        Ok((RP { mbox, txt }, mbox_size + txt_size))
+    }
+}
+
+impl Raw for RP {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.append(&mut self.mbox.raw());
+        bytes.append(&mut self.txt.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.mbox.raw_size() + self.txt.raw_size()
+    }
+}
+
+impl std::fmt::Display for RP {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.mbox.to_fqdn(), self.txt.to_fqdn())
+    }
+}
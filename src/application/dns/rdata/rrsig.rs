@@ -0,0 +1,166 @@
+use std::{mem::size_of, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use byteorder::{NetworkEndian, ByteOrder};
+use data_encoding::BASE64;
+
+use crate::{application::dns::{Name, Type, read_u16, read_u32, read_u8}, error::ParseError, Raw};
+
+use super::Algorithm;
+
+
+/// A signature over the set of records with the same owner, class and type.
+/// [RFC 4034 §3](https://datatracker.ietf.org/doc/html/rfc4034#section-3)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RRSIG {
+    /// The type of the signed records.
+    pub type_covered: Type,
+
+    pub algorithm: Algorithm,
+
+    /// The number of labels of the owner name of the signed records, not counting a leading wildcard label.
+    pub labels: u8,
+
+    /// The TTL of the signed records as it appears in the authoritative zone.
+    pub original_ttl: u32,
+
+    /// The end of the validity period in seconds since the UNIX epoch, modulo 2^32.
+    pub expiration: u32,
+
+    /// The start of the validity period in seconds since the UNIX epoch, modulo 2^32.
+    pub inception: u32,
+
+    /// The key tag of the DNSKEY which verifies the signature.
+    pub key_tag: u16,
+
+    /// The owner of the DNSKEY which verifies the signature.
+    pub signer_name: Name,
+
+    pub signature: Vec<u8>
+}
+
+impl RRSIG {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(type_covered: Type, algorithm: Algorithm, labels: u8, original_ttl: u32, expiration: u32, inception: u32, key_tag: u16, signer_name: Name, signature: Vec<u8>) -> Self {
+        RRSIG { type_covered, algorithm, labels, original_ttl, expiration, inception, key_tag, signer_name, signature }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        RRSIG::from_message(bytes, 0).map(|(rrsig, _)| rrsig)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let type_covered = read_u16(message, offset, "RRSIG.type_covered")?.into();
+        let algorithm = read_u8(message, offset + 2, "RRSIG.algorithm")?.into();
+        let labels = read_u8(message, offset + 3, "RRSIG.labels")?;
+        let original_ttl = read_u32(message, offset + 4, "RRSIG.original_ttl")?;
+        let expiration = read_u32(message, offset + 8, "RRSIG.expiration")?;
+        let inception = read_u32(message, offset + 12, "RRSIG.inception")?;
+        let key_tag = read_u16(message, offset + 16, "RRSIG.key_tag")?;
+        let (signer_name, name_size) = Name::from_message(message, offset + 18)?;
+        let signature = message[offset + 18 + name_size..].to_vec();
+        let size = 18 + name_size + signature.len();
+
+        Ok((RRSIG { type_covered, algorithm, labels, original_ttl, expiration, inception, key_tag, signer_name, signature }, size))
+    }
+
+    /// Returns the end of the validity period.
+    pub fn expiration_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(u64::from(self.expiration))
+    }
+
+    /// Returns the start of the validity period.
+    pub fn inception_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(u64::from(self.inception))
+    }
+
+    /// Returns whether or not the given time lies within the validity period.
+    /// The timestamps are compared with serial number arithmetic, so the period may span the year 2106
+    /// [RFC 4034 §3.1.5](https://datatracker.ietf.org/doc/html/rfc4034#section-3.1.5).
+    pub fn is_valid_at(&self, time: SystemTime) -> bool {
+        let now = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs() as u32);
+
+        (now.wrapping_sub(self.inception) as i32) >= 0 && (self.expiration.wrapping_sub(now) as i32) >= 0
+    }
+}
+
+impl Raw for RRSIG {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 18];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.type_covered.into());
+        bytes[2] = self.algorithm.into();
+        bytes[3] = self.labels;
+        NetworkEndian::write_u32(&mut bytes[4..8], self.original_ttl);
+        NetworkEndian::write_u32(&mut bytes[8..12], self.expiration);
+        NetworkEndian::write_u32(&mut bytes[12..16], self.inception);
+        NetworkEndian::write_u16(&mut bytes[16..18], self.key_tag);
+
+        bytes.append(&mut self.signer_name.raw());
+        bytes.extend_from_slice(&self.signature);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 * size_of::<u16>() + 2 + 3 * size_of::<u32>() + self.signer_name.raw_size() + self.signature.len()
+    }
+}
+
+impl std::fmt::Display for RRSIG {
+    /// Formats the record data with the validity period as `YYYYMMDDHHmmSS` in UTC and the signature in Base64
+    /// [RFC 4034 §3.2](https://datatracker.ietf.org/doc/html/rfc4034#section-3.2).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {} {} {} {} {} {}", self.type_covered, u8::from(self.algorithm), self.labels, self.original_ttl,
+               format_timestamp(self.expiration), format_timestamp(self.inception), self.key_tag, self.signer_name.to_fqdn(),
+               BASE64.encode(&self.signature))
+    }
+}
+
+/// Formats seconds since the UNIX epoch as `YYYYMMDDHHmmSS` in UTC.
+fn format_timestamp(timestamp: u32) -> String {
+    let (days, seconds) = (timestamp / 86_400, timestamp % 86_400);
+
+    // Converts the days since the epoch into a date of the proleptic Gregorian calendar, using eras of 400 years
+    // which start on the 1st of March, so that the leap day is the last day of an era year.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = era * 400 + year_of_era + u32::from(month <= 2);
+
+    format!("{:04}{:02}{:02}{:02}{:02}{:02}", year, month, day, seconds / 3_600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Parses a timestamp in the form `YYYYMMDDHHmmSS` in UTC, or as plain seconds since the UNIX epoch
+/// [RFC 4034 §3.2](https://datatracker.ietf.org/doc/html/rfc4034#section-3.2).
+/// Dates beyond 2106 wrap around like the field itself.
+pub(crate) fn parse_timestamp(text: &str) -> Option<u32> {
+    if text.len() != 14 {
+        return text.parse().ok();
+    }
+    if !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let field = |range: std::ops::Range<usize>| text[range].parse::<i64>().unwrap();
+    let (year, month, day) = (field(0..4), field(4..6), field(6..8));
+    let (hours, minutes, seconds) = (field(8..10), field(10..12), field(12..14));
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    // The inverse of the conversion in format_timestamp.
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some((days * 86_400 + hours * 3_600 + minutes * 60 + seconds).rem_euclid(1 << 32) as u32)
+}
@@ -0,0 +1,60 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{Name, read_u16}, error::ParseError, Raw};
+
+
+/// Route-through binding for hosts that do not have their own direct wide area network addresses
+/// [RFC 1183](https://datatracker.ietf.org/doc/html/rfc1183#section-3.3)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RT {
+    /// The preference given to this record among others with the same owner.
+    /// Lower values mean higher preference.
+    pub preference: u16,
+
+    /// A host which will serve as an intermediate in reaching the owner.
+    pub host: Name
+}
+
+impl RT {
+    pub fn new(preference: u16, host: Name) -> Self {
+        RT { preference, host }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        RT::from_message(bytes, 0).map(|(rt, _)| rt)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let preference = read_u16(message, offset, "RT.preference")?;
+        let (host, host_size) = Name::from_message(message, offset + 2)?;
+
+        Ok((RT { preference, host }, 2 + host_size))
+    }
+}
+
+impl Raw for RT {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.push(0);
+        bytes.push(0);
+
+        NetworkEndian::write_u16(&mut bytes, self.preference);
+        bytes.append(&mut self.host.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        size_of::<u16>() + self.host.raw_size()
+    }
+}
+
+impl std::fmt::Display for RT {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.preference, self.host.to_fqdn())
+    }
+}
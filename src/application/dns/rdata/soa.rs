@@ -0,0 +1,103 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{Name, MessageEncoder, read_u32}, error::ParseError, Raw};
+
+
+/// Marks the start of a zone of authority.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SOA {
+    /// The name server that was the original or primary source of data for this zone.
+    pub mname: Name,
+
+    /// The mailbox of the person responsible for this zone.
+    pub rname: Name,
+
+    /// The version number of the original copy of the zone.  
+    /// Zone transfers preserve this value. It wraps and should be compared using sequence space arithmetic.
+    pub serial: u32,
+
+    /// The number of seconds before the zone should be refreshed.
+    pub refresh: u32,
+
+    /// The number of seconds before a failed refresh should be retried.
+    pub retry: u32,
+
+    /// The number of seconds after which the zone is no longer authoritative if it could not be refreshed.
+    pub expire: u32,
+
+    /// The TTL for negative responses from this zone [RFC 2308](https://datatracker.ietf.org/doc/html/rfc2308).
+    pub minimum: u32
+}
+
+impl SOA {
+    pub fn new(mname: Name, rname: Name, serial: u32, refresh: u32, retry: u32, expire: u32, minimum: u32) -> Self {
+        SOA { mname, rname, serial, refresh, retry, expire, minimum }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        SOA::from_message(bytes, 0).map(|(soa, _)| soa)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let (mname, mname_size) = Name::from_message(message, offset)?;
+        let (rname, rname_size) = Name::from_message(message, offset + mname_size)?;
+        let start = offset + mname_size + rname_size;
+
+        let soa = SOA {
+            mname,
+            rname,
+            serial: read_u32(message, start, "SOA.serial")?,
+            refresh: read_u32(message, start + 4, "SOA.refresh")?,
+            retry: read_u32(message, start + 8, "SOA.retry")?,
+            expire: read_u32(message, start + 12, "SOA.expire")?,
+            minimum: read_u32(message, start + 16, "SOA.minimum")?
+        };
+
+        Ok((soa, mname_size + rname_size + 5 * size_of::<u32>()))
+    }
+
+    /// Writes the record data to the given message encoder, compressing both names if enabled.
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write_name(&self.mname);
+        encoder.write_name(&self.rname);
+        encoder.write(&self.timers());
+    }
+
+    /// Returns the serial and the timers in wire format.
+    fn timers(&self) -> [u8; 5 * size_of::<u32>()] {
+        let mut bytes = [0; 5 * size_of::<u32>()];
+
+        NetworkEndian::write_u32(&mut bytes[0..4], self.serial);
+        NetworkEndian::write_u32(&mut bytes[4..8], self.refresh);
+        NetworkEndian::write_u32(&mut bytes[8..12], self.retry);
+        NetworkEndian::write_u32(&mut bytes[12..16], self.expire);
+        NetworkEndian::write_u32(&mut bytes[16..20], self.minimum);
+
+        bytes
+    }
+}
+
+impl Raw for SOA {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.append(&mut self.mname.raw());
+        bytes.append(&mut self.rname.raw());
+        bytes.extend_from_slice(&self.timers());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.mname.raw_size() + self.rname.raw_size() + 5 * size_of::<u32>()
+    }
+}
+
+impl std::fmt::Display for SOA {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {} {} {} {}", self.mname.to_fqdn(), self.rname.to_fqdn(), self.serial, self.refresh, self.retry, self.expire, self.minimum)
+    }
+}
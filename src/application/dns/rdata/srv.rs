@@ -0,0 +1,66 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::{Name, read_u16}, error::ParseError, Raw};
+
+
+/// Specifies the location of the server(s) for a specific protocol and domain.
+/// [RFC 2782](https://datatracker.ietf.org/doc/html/rfc2782)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SRV {
+    /// The priority of the target host. Clients contact the target with the lowest priority first.
+    pub priority: u16,
+
+    /// The relative weight among targets with the same priority.
+    pub weight: u16,
+
+    /// The port of the service on the target host.
+    pub port: u16,
+
+    /// The target host. A target of "." means that the service is decidedly not available.
+    pub target: Name
+}
+
+impl SRV {
+    pub fn new(priority: u16, weight: u16, port: u16, target: Name) -> Self {
+        SRV { priority, weight, port, target }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        SRV::from_message(bytes, 0).map(|(srv, _)| srv)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let priority = read_u16(message, offset, "SRV.priority")?;
+        let weight = read_u16(message, offset + 2, "SRV.weight")?;
+        let port = read_u16(message, offset + 4, "SRV.port")?;
+        let (target, target_size) = Name::from_message(message, offset + 6)?;
+
+        Ok((SRV { priority, weight, port, target }, 6 + target_size))
+    }
+}
+
+impl Raw for SRV {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 3 * size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.priority);
+        NetworkEndian::write_u16(&mut bytes[2..4], self.weight);
+        NetworkEndian::write_u16(&mut bytes[4..6], self.port);
+        bytes.append(&mut self.target.raw());
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        3 * size_of::<u16>() + self.target.raw_size()
+    }
+}
+
+impl std::fmt::Display for SRV {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.priority, self.weight, self.port, self.target.to_fqdn())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{application::dns::read_u8, error::ParseError, Raw};
+
+
+/// The fingerprint of an SSH host key. [RFC 4255](https://datatracker.ietf.org/doc/html/rfc4255)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SSHFP {
+    /// The algorithm of the host key: 1 for RSA, 2 for DSA, 3 for ECDSA, 4 for Ed25519 and 6 for Ed448.
+    pub algorithm: u8,
+
+    /// The hash function of the fingerprint: 1 for SHA-1 and 2 for SHA-256.
+    pub fingerprint_type: u8,
+
+    pub fingerprint: Vec<u8>
+}
+
+impl SSHFP {
+    pub fn new(algorithm: u8, fingerprint_type: u8, fingerprint: Vec<u8>) -> Self {
+        SSHFP { algorithm, fingerprint_type, fingerprint }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        SSHFP::from_message(bytes, 0).map(|(sshfp, _)| sshfp)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let algorithm = read_u8(message, offset, "SSHFP.algorithm")?;
+        let fingerprint_type = read_u8(message, offset + 1, "SSHFP.fingerprint_type")?;
+        let fingerprint = message[offset + 2..].to_vec();
+        let size = 2 + fingerprint.len();
+
+        Ok((SSHFP { algorithm, fingerprint_type, fingerprint }, size))
+    }
+}
+
+impl Raw for SSHFP {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![self.algorithm, self.fingerprint_type];
+        bytes.extend_from_slice(&self.fingerprint);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 + self.fingerprint.len()
+    }
+}
+
+impl std::fmt::Display for SSHFP {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.algorithm, self.fingerprint_type, hex::encode_upper(&self.fingerprint))
+    }
+}
@@ -0,0 +1,402 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use byteorder::{NetworkEndian, ByteOrder};
+use data_encoding::BASE64;
+
+use crate::{application::dns::{presentation::{unescape, Tokens}, Name, read_slice, read_u16}, error::{ParseError, ParseErrorKind}, Raw};
+
+use super::CharacterString;
+
+
+/// The names of the SvcParamKeys registered by [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460#section-14.3.2),
+/// indexed by their number.
+const KEY_NAMES: [&str; 7] = ["mandatory", "alpn", "no-default-alpn", "port", "ipv4hint", "ech", "ipv6hint"];
+
+/// Service binding, which tells clients where and how an origin can be reached.
+/// The HTTPS record type shares this structure. [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SVCB {
+    /// 0 for AliasMode, in which the record only points to another name,
+    /// otherwise the preference of the record among others in ServiceMode, lower values first.
+    pub priority: u16,
+
+    /// The name of the alternative endpoint, or the root name for the owner itself.
+    pub target: Name,
+
+    /// The parameters of the endpoint, which are written in ascending order of their keys.
+    /// Each key may appear only once.
+    pub params: Vec<SvcParam>
+}
+
+impl SVCB {
+    pub fn new(priority: u16, target: Name, params: Vec<SvcParam>) -> Self {
+        SVCB { priority, target, params }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        SVCB::from_message(bytes, 0).map(|(svcb, _)| svcb)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let priority = read_u16(message, offset, "SVCB.priority")?;
+        let (target, target_size) = Name::from_message(message, offset + 2)?;
+
+        let mut params: Vec<SvcParam> = Vec::new();
+        let mut i = offset + 2 + target_size;
+        while i < message.len() {
+            let key = read_u16(message, i, "SvcParam.key")?;
+            if params.last().is_some_and(|previous| key <= previous.key()) {
+                return Err(ParseError::new("DNS", "SvcParam.key", i, ParseErrorKind::Invalid("SvcParamKeys must be in strictly ascending order")));
+            }
+
+            let length = usize::from(read_u16(message, i + 2, "SvcParam.length")?);
+            let value = read_slice(message, i + 4, length, "SvcParam.value")?;
+            params.push(SvcParam::from_value(key, value).map_err(|e| e.offset_by(i + 4))?);
+            i += 4 + length;
+        }
+
+        Ok((SVCB { priority, target, params }, i - offset))
+    }
+
+    /// Returns whether or not the record is in AliasMode.
+    pub fn is_alias(&self) -> bool {
+        self.priority == 0
+    }
+
+    /// Returns the parameter with the given key.
+    pub fn param(&self, key: u16) -> Option<&SvcParam> {
+        self.params.iter().find(|param| param.key() == key)
+    }
+
+    fn sorted_params(&self) -> Vec<&SvcParam> {
+        let mut params: Vec<&SvcParam> = self.params.iter().collect();
+        params.sort_by_key(|param| param.key());
+        params
+    }
+}
+
+impl Raw for SVCB {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 2];
+
+        NetworkEndian::write_u16(&mut bytes, self.priority);
+        bytes.append(&mut self.target.raw());
+        for param in self.sorted_params() {
+            bytes.append(&mut param.raw());
+        }
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 + self.target.raw_size() + self.params.iter().map(|param| param.raw_size()).sum::<usize>()
+    }
+}
+
+impl SVCB {
+    /// Reads the record data in presentation format, see the Display implementation.
+    pub(crate) fn from_tokens(tokens: &mut Tokens) -> Result<Self, ParseError> {
+        let priority = tokens.parse("SVCB.priority")?;
+        let target = tokens.name("SVCB.target")?;
+
+        let mut params: Vec<SvcParam> = Vec::new();
+        for token in tokens.rest() {
+            let param: SvcParam = token.text.parse().map_err(|e: ParseError| e.offset_by(token.offset))?;
+            if params.iter().any(|previous| previous.key() == param.key()) {
+                return Err(ParseError::new("DNS", "SvcParam.key", token.offset, ParseErrorKind::Invalid("SvcParamKeys must not repeat")));
+            }
+            params.push(param);
+        }
+
+        Ok(SVCB::new(priority, target, params))
+    }
+}
+
+impl std::fmt::Display for SVCB {
+    /// Formats the record data in presentation format, e.g. `1 . alpn=h2,h3 port=8443`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.priority, self.target.to_fqdn())?;
+
+        for param in self.sorted_params() {
+            write!(f, " {}", param)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A parameter of a service binding. [RFC 9460 §7](https://datatracker.ietf.org/doc/html/rfc9460#section-7)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SvcParam {
+    /// The keys of the parameters which a client must understand to use the record,
+    /// written in ascending order.
+    Mandatory(Vec<u16>),
+    /// The application protocol identifiers supported by the endpoint, e.g. "h2" and "h3", none of which may be empty.
+    Alpn(Vec<CharacterString>),
+    /// The endpoint does not support the default protocol of the scheme.
+    NoDefaultAlpn,
+    /// The port of the endpoint.
+    Port(u16),
+    /// Addresses which the client may use before the A records of the target are known.
+    Ipv4Hint(Vec<Ipv4Addr>),
+    /// An Encrypted ClientHello configuration list, written in base64 in presentation format.
+    Ech(Vec<u8>),
+    /// Addresses which the client may use before the AAAA records of the target are known.
+    Ipv6Hint(Vec<Ipv6Addr>),
+    /// A parameter which is not parsed by this library, kept verbatim.
+    Unknown {
+        key: u16,
+        value: Vec<u8>
+    }
+}
+
+impl SvcParam {
+    /// Parses the value of the parameter with the given key.
+    pub fn from_value(key: u16, value: &[u8]) -> Result<Self, ParseError> {
+        let bad_length = || ParseError::new("DNS", "SvcParam.value", 0, ParseErrorKind::BadLength(value.len()));
+
+        let param = match key {
+            0 => {
+                if value.is_empty() || value.len() % 2 != 0 {
+                    return Err(bad_length());
+                }
+
+                let keys: Vec<u16> = value.chunks(2).map(NetworkEndian::read_u16).collect();
+                if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+                    return Err(ParseError::new("DNS", "SvcParam.value", 0, ParseErrorKind::Invalid("mandatory keys must be in strictly ascending order")));
+                }
+                check_mandatory(&keys).map_err(|message| ParseError::new("DNS", "SvcParam.value", 0, ParseErrorKind::Invalid(message)))?;
+                SvcParam::Mandatory(keys)
+            },
+            1 => {
+                let mut ids = Vec::new();
+                let mut i = 0;
+                while i < value.len() {
+                    let (id, size) = CharacterString::from_message(value, i, "SvcParam.alpn")?;
+                    if id.as_bytes().is_empty() {
+                        return Err(ParseError::new("DNS", "SvcParam.alpn", i, ParseErrorKind::Invalid("empty alpn-id")));
+                    }
+                    ids.push(id);
+                    i += size;
+                }
+
+                if ids.is_empty() {
+                    return Err(bad_length());
+                }
+                SvcParam::Alpn(ids)
+            },
+            2 if value.is_empty() => SvcParam::NoDefaultAlpn,
+            3 if value.len() == 2 => SvcParam::Port(NetworkEndian::read_u16(value)),
+            4 if !value.is_empty() && value.len() % 4 == 0 => {
+                SvcParam::Ipv4Hint(value.chunks(4).map(|octets| Ipv4Addr::from(<[u8; 4]>::try_from(octets).unwrap())).collect())
+            },
+            5 => SvcParam::Ech(value.to_vec()),
+            6 if !value.is_empty() && value.len() % 16 == 0 => {
+                SvcParam::Ipv6Hint(value.chunks(16).map(|octets| Ipv6Addr::from(<[u8; 16]>::try_from(octets).unwrap())).collect())
+            },
+            2..=6 => return Err(bad_length()),
+            _ => SvcParam::Unknown { key, value: value.to_vec() }
+        };
+
+        Ok(param)
+    }
+
+    /// Returns the number of the SvcParamKey.
+    pub fn key(&self) -> u16 {
+        match self {
+            SvcParam::Mandatory(_) => 0,
+            SvcParam::Alpn(_) => 1,
+            SvcParam::NoDefaultAlpn => 2,
+            SvcParam::Port(_) => 3,
+            SvcParam::Ipv4Hint(_) => 4,
+            SvcParam::Ech(_) => 5,
+            SvcParam::Ipv6Hint(_) => 6,
+            SvcParam::Unknown { key, .. } => *key
+        }
+    }
+
+    /// Returns the value of the parameter in wire format.
+    pub fn value(&self) -> Vec<u8> {
+        match self {
+            SvcParam::Mandatory(keys) => {
+                let mut keys = keys.clone();
+                keys.sort_unstable();
+                keys.iter().flat_map(|key| key.to_be_bytes()).collect()
+            },
+            SvcParam::Alpn(ids) => {
+                let mut value = Vec::new();
+                for id in ids {
+                    value.append(&mut id.raw());
+                }
+                value
+            },
+            SvcParam::NoDefaultAlpn => Vec::new(),
+            SvcParam::Port(port) => port.to_be_bytes().to_vec(),
+            SvcParam::Ipv4Hint(addresses) => addresses.iter().flat_map(|address| address.octets()).collect(),
+            SvcParam::Ech(config) => config.clone(),
+            SvcParam::Ipv6Hint(addresses) => addresses.iter().flat_map(|address| address.octets()).collect(),
+            SvcParam::Unknown { value, .. } => value.clone()
+        }
+    }
+}
+
+impl Raw for SvcParam {
+    /// Panics if the value is longer than 65535 bytes, which no parameter can be.
+    fn raw(&self) -> Vec<u8> {
+        let value = self.value();
+        let mut bytes = vec![0; 4];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.key());
+        NetworkEndian::write_u16(&mut bytes[2..4], u16::try_from(value.len()).expect("The value of a SvcParam may be at most 65535 bytes long."));
+        bytes.extend_from_slice(&value);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        4 + self.value().len()
+    }
+}
+
+/// Returns the presentation name of a SvcParamKey, e.g. "alpn" or "key65333".
+fn key_name(key: u16) -> String {
+    KEY_NAMES.get(usize::from(key)).map_or_else(|| format!("key{}", key), |name| name.to_string())
+}
+
+/// Parses the presentation name of a SvcParamKey.
+fn parse_key(name: &str) -> Option<u16> {
+    match KEY_NAMES.iter().position(|known| *known == name) {
+        Some(key) => Some(key as u16),
+        None => name.strip_prefix("key").filter(|number| !number.starts_with('+')).and_then(|number| number.parse().ok())
+    }
+}
+
+/// Checks the keys listed by a mandatory parameter, which must not include mandatory itself
+/// or list a key twice [RFC 9460 §8](https://datatracker.ietf.org/doc/html/rfc9460#section-8).
+fn check_mandatory(keys: &[u16]) -> Result<(), &'static str> {
+    if keys.contains(&0) {
+        return Err("mandatory must not list itself");
+    }
+    if keys.iter().enumerate().any(|(i, key)| keys[..i].contains(key)) {
+        return Err("mandatory must not list a key twice");
+    }
+
+    Ok(())
+}
+
+/// Escapes bytes for a presentation format value: printable ASCII is kept, except for characters which
+/// delimit values, and all other bytes are written as `\DDD`.
+fn escape(bytes: &[u8], separator: Option<u8>) -> String {
+    let mut escaped = String::new();
+
+    for byte in bytes {
+        match byte {
+            // Within a value-list, commas and backslashes are escaped twice, once for the list and once for the character-string.
+            b',' if separator == Some(b',') => escaped.push_str("\\\\,"),
+            b'\\' if separator == Some(b',') => escaped.push_str("\\\\\\\\"),
+            b'"' | b'\\' | b';' | b'(' | b')' => escaped.push_str(&format!("\\{}", *byte as char)),
+            0x21..=0x7E => escaped.push(*byte as char),
+            _ => escaped.push_str(&format!("\\{:03}", byte))
+        }
+    }
+
+    escaped
+}
+
+/// Splits an unescaped value-list at its commas, keeping commas and backslashes escaped by a backslash
+/// [RFC 9460 Appendix A.1](https://datatracker.ietf.org/doc/html/rfc9460#appendix-A.1).
+fn split_value_list(value: &[u8]) -> Vec<Vec<u8>> {
+    let mut items = vec![Vec::new()];
+
+    let mut i = 0;
+    while i < value.len() {
+        match value[i] {
+            b'\\' if i + 1 < value.len() => {
+                items.last_mut().unwrap().push(value[i + 1]);
+                i += 2;
+                continue;
+            },
+            b',' => items.push(Vec::new()),
+            byte => items.last_mut().unwrap().push(byte)
+        }
+        i += 1;
+    }
+
+    items
+}
+
+impl std::fmt::Display for SvcParam {
+    /// Formats the parameter in presentation format, e.g. `alpn=h2,h3`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let key = key_name(self.key());
+
+        match self {
+            SvcParam::Mandatory(keys) => write!(f, "{}={}", key, keys.iter().map(|key| key_name(*key)).collect::<Vec<_>>().join(",")),
+            SvcParam::Alpn(ids) => write!(f, "{}={}", key, ids.iter().map(|id| escape(id.as_bytes(), Some(b','))).collect::<Vec<_>>().join(",")),
+            SvcParam::NoDefaultAlpn => write!(f, "{}", key),
+            SvcParam::Port(port) => write!(f, "{}={}", key, port),
+            SvcParam::Ipv4Hint(addresses) => write!(f, "{}={}", key, addresses.iter().map(Ipv4Addr::to_string).collect::<Vec<_>>().join(",")),
+            SvcParam::Ech(config) => write!(f, "{}={}", key, BASE64.encode(config)),
+            SvcParam::Ipv6Hint(addresses) => write!(f, "{}={}", key, addresses.iter().map(Ipv6Addr::to_string).collect::<Vec<_>>().join(",")),
+            SvcParam::Unknown { value, .. } if value.is_empty() => write!(f, "{}", key),
+            SvcParam::Unknown { value, .. } => write!(f, "{}={}", key, escape(value, None))
+        }
+    }
+}
+
+impl FromStr for SvcParam {
+    type Err = ParseError;
+
+    /// Parses a parameter in presentation format, e.g. `port=8443` or `alpn="h2,h3"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (s, None)
+        };
+
+        let key = parse_key(name).ok_or_else(|| ParseError::new("DNS", "SvcParam.key", 0, ParseErrorKind::Invalid("unknown SvcParamKey")))?;
+
+        let value_offset = name.len() + 1;
+        let value = match value {
+            Some(value) if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') => {
+                unescape(&value[1..value.len() - 1], "SvcParam.value").map_err(|e| e.offset_by(value_offset + 1))?
+            },
+            Some(value) => unescape(value, "SvcParam.value").map_err(|e| e.offset_by(value_offset))?,
+            None => Vec::new()
+        };
+
+        let invalid = |message: &'static str| ParseError::new("DNS", "SvcParam.value", value_offset, ParseErrorKind::Invalid(message));
+        let text = String::from_utf8(value.clone()).map_err(|_| invalid("the value is not UTF-8"));
+
+        let param = match key {
+            0 => {
+                let keys = text?.split(',')
+                    .map(|name| parse_key(name).ok_or_else(|| invalid("unknown SvcParamKey")))
+                    .collect::<Result<Vec<u16>, ParseError>>()?;
+                check_mandatory(&keys).map_err(invalid)?;
+                SvcParam::Mandatory(keys)
+            },
+            1 => SvcParam::Alpn(split_value_list(&value).into_iter()
+                    .map(|id| match id.is_empty() {
+                        true => Err(invalid("empty alpn-id")),
+                        false => CharacterString::new(id).map_err(|_| invalid("alpn-ids may be at most 255 bytes long"))
+                    })
+                    .collect::<Result<Vec<CharacterString>, ParseError>>()?),
+            2 if value.is_empty() => SvcParam::NoDefaultAlpn,
+            2 => return Err(invalid("no-default-alpn takes no value")),
+            3 => SvcParam::Port(text?.parse().map_err(|_| invalid("invalid port"))?),
+            4 => SvcParam::Ipv4Hint(text?.split(',')
+                    .map(|address| address.parse().map_err(|_| invalid("invalid IPv4 address")))
+                    .collect::<Result<Vec<Ipv4Addr>, ParseError>>()?),
+            5 => SvcParam::Ech(BASE64.decode(&value).map_err(|_| invalid("invalid base64"))?),
+            6 => SvcParam::Ipv6Hint(text?.split(',')
+                    .map(|address| address.parse().map_err(|_| invalid("invalid IPv6 address")))
+                    .collect::<Result<Vec<Ipv6Addr>, ParseError>>()?),
+            _ => SvcParam::Unknown { key, value }
+        };
+
+        Ok(param)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{application::dns::read_u8, error::ParseError, Raw};
+
+
+/// TLS certificate association, which binds a certificate or public key to a TLS service with DANE.
+/// [RFC 6698](https://datatracker.ietf.org/doc/html/rfc6698)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TLSA {
+    /// How the association is used: 0 for a CA constraint, 1 for a service certificate constraint,
+    /// 2 for a trust anchor assertion and 3 for a domain-issued certificate.
+    pub usage: u8,
+
+    /// Which part of the certificate is matched: 0 for the full certificate, 1 for the SubjectPublicKeyInfo.
+    pub selector: u8,
+
+    /// How the data is matched: 0 for an exact match, 1 for a SHA-256 hash and 2 for a SHA-512 hash.
+    pub matching_type: u8,
+
+    /// The certificate association data to be matched.
+    pub data: Vec<u8>
+}
+
+impl TLSA {
+    pub fn new(usage: u8, selector: u8, matching_type: u8, data: Vec<u8>) -> Self {
+        TLSA { usage, selector, matching_type, data }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        TLSA::from_message(bytes, 0).map(|(tlsa, _)| tlsa)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let usage = read_u8(message, offset, "TLSA.usage")?;
+        let selector = read_u8(message, offset + 1, "TLSA.selector")?;
+        let matching_type = read_u8(message, offset + 2, "TLSA.matching_type")?;
+        let data = message[offset + 3..].to_vec();
+        let size = 3 + data.len();
+
+        Ok((TLSA { usage, selector, matching_type, data }, size))
+    }
+}
+
+impl Raw for TLSA {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![self.usage, self.selector, self.matching_type];
+        bytes.extend_from_slice(&self.data);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        3 + self.data.len()
+    }
+}
+
+impl std::fmt::Display for TLSA {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.usage, self.selector, self.matching_type, hex::encode_upper(&self.data))
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{error::{ParseError, ParseErrorKind}, Raw};
+
+use super::CharacterString;
+
+
+/// A text record, made up of one or more character-strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TXT {
+    /// The character-strings of the record, of which there must be at least one.
+    strings: Vec<CharacterString>
+}
+
+impl TXT {
+    /// Constructs a text record from its character-strings.
+    /// Fails if there are none, since a text record has at least one string.
+    pub fn new(strings: Vec<CharacterString>) -> Result<Self, ParseError> {
+        if strings.is_empty() {
+            return Err(ParseError::new("DNS", "TXT", 0, ParseErrorKind::BadLength(0)));
+        }
+
+        Ok(TXT { strings })
+    }
+
+    /// Constructs a text record from the given text, splitting it into character-strings of at most 255 bytes.
+    /// The empty text becomes a single empty string.
+    pub fn from_text(text: &str) -> Self {
+        if text.is_empty() {
+            return TXT { strings: vec![CharacterString::default()] };
+        }
+
+        TXT { strings: text.as_bytes().chunks(CharacterString::MAX_LENGTH).map(|chunk| CharacterString { contents: chunk.to_vec() }).collect() }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        TXT::from_message(bytes, 0).map(|(txt, _)| txt)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        if offset >= message.len() {
+            return Err(ParseError::new("DNS", "TXT", offset, ParseErrorKind::BadLength(0)));
+        }
+
+        let mut strings = Vec::new();
+
+        let mut i = offset;
+        while i < message.len() {
+            let (string, size) = CharacterString::from_message(message, i, "TXT")?;
+            strings.push(string);
+            i += size;
+        }
+
+        Ok((TXT { strings }, i - offset))
+    }
+
+    pub fn strings(&self) -> &[CharacterString] {
+        &self.strings
+    }
+
+    /// Returns the character-strings concatenated into a single string, which is how
+    /// e.g. SPF and DKIM records split over several strings are meant to be read.  
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    pub fn text(&self) -> String {
+        let bytes: Vec<u8> = self.strings.iter().flat_map(|string| string.as_bytes()).copied().collect();
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+}
+
+impl Raw for TXT {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        for string in &self.strings {
+            bytes.append(&mut string.raw());
+        }
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        self.strings.iter().map(|string| string.raw_size()).sum()
+    }
+}
+
+impl std::fmt::Display for TXT {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let strings: Vec<String> = self.strings.iter().map(|string| string.to_string()).collect();
+        write!(f, "{}", strings.join(" "))
+    }
+}
@@ -0,0 +1,63 @@
+use std::mem::size_of;
+
+use byteorder::{NetworkEndian, ByteOrder};
+
+use crate::{application::dns::read_u16, error::ParseError, Raw};
+
+use super::quote_character_string;
+
+
+/// Maps a name to a URI. [RFC 7553](https://datatracker.ietf.org/doc/html/rfc7553)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct URI {
+    /// The priority of the target, lower values first.
+    pub priority: u16,
+
+    /// The relative weight among targets with the same priority.
+    pub weight: u16,
+
+    /// The URI, which is not length-prefixed and takes up the rest of the record data.
+    pub target: Vec<u8>
+}
+
+impl URI {
+    pub fn new(priority: u16, weight: u16, target: Vec<u8>) -> Self {
+        URI { priority, weight, target }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        URI::from_message(bytes, 0).map(|(uri, _)| uri)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let priority = read_u16(message, offset, "URI.priority")?;
+        let weight = read_u16(message, offset + 2, "URI.weight")?;
+        let target = message[offset + 4..].to_vec();
+        let size = 4 + target.len();
+
+        Ok((URI { priority, weight, target }, size))
+    }
+}
+
+impl Raw for URI {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 2 * size_of::<u16>()];
+
+        NetworkEndian::write_u16(&mut bytes[0..2], self.priority);
+        NetworkEndian::write_u16(&mut bytes[2..4], self.weight);
+        bytes.extend_from_slice(&self.target);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        2 * size_of::<u16>() + self.target.len()
+    }
+}
+
+impl std::fmt::Display for URI {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.priority, self.weight, quote_character_string(&self.target))
+    }
+}
@@ -0,0 +1,92 @@
+use std::net::Ipv4Addr;
+
+use crate::{application::dns::{read_slice, read_u8}, error::ParseError, Raw};
+
+
+/// Used to describe the well known services supported by a particular protocol on a particular internet address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WKS {
+    pub address: Ipv4Addr,
+
+    /// An IP protocol number, e.g. 6 for TCP or 17 for UDP.
+    pub protocol: u8,
+
+    /// A bitmap in which the n-th bit (counting from the most significant bit of the first byte)
+    /// signifies whether port n is served.
+    pub bitmap: Vec<u8>
+}
+
+impl WKS {
+    pub fn new(address: Ipv4Addr, protocol: u8, bitmap: Vec<u8>) -> Self {
+        WKS { address, protocol, bitmap }
+    }
+
+    /// Constructs a WKS record whose bitmap contains exactly the given ports.
+    pub fn from_ports(address: Ipv4Addr, protocol: u8, ports: &[u16]) -> Self {
+        let mut bitmap = vec![0; ports.iter().max().map_or(0, |max| usize::from(*max) / 8 + 1)];
+        for port in ports {
+            bitmap[usize::from(*port) / 8] |= 0x80 >> (port % 8);
+        }
+
+        WKS { address, protocol, bitmap }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        WKS::from_message(bytes, 0).map(|(wks, _)| wks)
+    }
+
+    /// Parses the record data at `offset` in the given DNS message, see [super::RData::from_message].
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        let octets: [u8; 4] = read_slice(message, offset, 4, "WKS.address")?.try_into().unwrap();
+        let protocol = read_u8(message, offset + 4, "WKS.protocol")?;
+        let bitmap = message[offset + 5..].to_vec();
+        let size = 5 + bitmap.len();
+
+        Ok((WKS { address: Ipv4Addr::from(octets), protocol, bitmap }, size))
+    }
+
+    /// Returns the ports which are set in the bitmap in ascending order.
+    /// Bits past port 65535 are ignored.
+    pub fn ports(&self) -> Vec<u16> {
+        let mut ports = Vec::new();
+
+        for (i, byte) in self.bitmap.iter().take(8192).enumerate() {
+            for bit in 0..8 {
+                if byte & (0x80 >> bit) != 0 {
+                    ports.push((i * 8 + bit) as u16);
+                }
+            }
+        }
+
+        ports
+    }
+}
+
+impl Raw for WKS {
+    fn raw(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.raw_size());
+
+        bytes.extend_from_slice(&self.address.octets());
+        bytes.push(self.protocol);
+        bytes.extend_from_slice(&self.bitmap);
+
+        bytes
+    }
+
+    fn raw_size(&self) -> usize {
+        4 + 1 + self.bitmap.len()
+    }
+}
+
+impl std::fmt::Display for WKS {
+    /// Formats the protocol and services by number, e.g. `192.0.2.1 6 25 80`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.address, self.protocol)?;
+
+        for port in self.ports() {
+            write!(f, " {}", port)?;
+        }
+
+        Ok(())
+    }
+}
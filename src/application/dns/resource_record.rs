@@ -1,11 +1,17 @@
 use std::mem::size_of;
+use std::str::FromStr;
 
 use byteorder::{NetworkEndian, ByteOrder};
 
+use crate::error::{ParseError, ParseErrorKind};
 use crate::Raw;
 
-use crate::application::dns::{Type, Class, Name};
+use crate::application::dns::{Type, Class, Name, MessageEncoder};
 use super::rdata::RData;
+use super::presentation::{invalid, parse_ttl, single_entry, Tokens};
+use super::{read_u16, read_u32};
+
+pub(crate) const RDATA_TOO_LONG: &str = "The data of a resource record may be at most 65535 bytes long.";
 
 /// A structure representing a DNS resource record.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -13,31 +19,119 @@ pub struct ResourceRecord {
     pub name: Name,
     pub rtype: Type,
     pub class: Class,
-    pub ttl: u16,
-    pub rlength: u16,
+    pub ttl: u32,
     pub rdata: RData
 }
 
 impl ResourceRecord {
-    pub fn new(name: Name, rtype: Type, class: Class, ttl: u16, rlength: u16, rdata: RData) -> Self {
-        ResourceRecord { name, rtype, class, ttl, rlength, rdata }
+    pub fn new(name: Name, rtype: Type, class: Class, ttl: u32, rdata: RData) -> Self {
+        ResourceRecord { name, rtype, class, ttl, rdata }
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        let name = Name::from_bytes(bytes);
-        let start = name.raw_size();
-
-        let rtype = Type::try_from(NetworkEndian::read_u16(&bytes[start..start + 2])).unwrap();
+    /// Returns the length of the uncompressed record data.
+    /// The length is always computed from the data, so that it cannot go out of sync with it.
+    /// Panics if the data is longer than 65535 bytes, which no record can be.
+    pub fn rlength(&self) -> u16 {
+        u16::try_from(self.rdata.raw_size()).expect(RDATA_TOO_LONG)
+    }
 
-        ResourceRecord {
-            name,
-            rtype,
-            class: Class::try_from(NetworkEndian::read_u16(&bytes[start + 2..start + 4])).unwrap(),
-            ttl: NetworkEndian::read_u16(&bytes[start + 4..start + 6]),
-            rlength: NetworkEndian::read_u16(&bytes[start + 6..start + 8]),
-            rdata: RData::from_bytes(rtype, &bytes[start + 8..])
+    /// Returns the serial number if the record is an SOA record.
+    pub fn soa_serial(&self) -> Option<u32> {
+        match &self.rdata {
+            RData::SOA(soa) => Some(soa.serial),
+            _ => None
         }
+    }
+
+    /// Constructs a resource record from the given bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        ResourceRecord::from_message(bytes, 0).map(|(record, _)| record)
+    }
+
+    /// Constructs a resource record starting at `offset` in the given DNS message.
+    /// Returns the record together with the number of bytes it occupies.
+    pub fn from_message(message: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        ResourceRecord::from_message_with(message, offset, false)
+    }
+
+    /// Constructs a resource record as [ResourceRecord::from_message] does. If `update` is true, the record is taken to be
+    /// a prerequisite or update of a dynamic update, in which records of class ANY or NONE may have empty data of any type
+    /// [RFC 2136 §2.4](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4), which is kept as [RData::Unknown].
+    pub(crate) fn from_message_with(message: &[u8], offset: usize, update: bool) -> Result<(Self, usize), ParseError> {
+        let (name, name_size) = Name::from_message(message, offset)?;
+        let start = offset + name_size;
+
+        let rtype = Type::from(read_u16(message, start, "ResourceRecord.rtype")?);
+
+        let class = read_u16(message, start + 2, "ResourceRecord.class")?;
+        let class = Class::from(class);
 
+        let ttl = read_u32(message, start + 4, "ResourceRecord.ttl")?;
+        let rlength = usize::from(read_u16(message, start + 8, "ResourceRecord.rlength")?);
+        let rdata = if update && rlength == 0 && matches!(class, Class::ANY | Class::NONE) {
+            RData::Unknown { rtype, bytes: Vec::new() }
+        } else {
+            RData::from_message(rtype, message, start + 10, rlength)?
+        };
+
+        // The record data may contain compressed names, so its size on the wire is only known through rlength.
+        Ok((ResourceRecord { name, rtype, class, ttl, rdata }, name_size + 10 + rlength))
+    }
+
+    /// Parses a record in presentation format, i.e. a line of a master file like `www 3600 IN MX 10 mail.example.com.`
+    /// [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+    /// Relative names get `origin` appended. The TTL is required, the class defaults to IN and either may come first.
+    pub fn parse_with_origin(s: &str, origin: &Name) -> Result<Self, ParseError> {
+        let entry = single_entry(s)?;
+        let mut tokens = Tokens::new(&entry.tokens, entry.end, origin);
+
+        let name = tokens.name("ResourceRecord.name")?;
+        let record = ResourceRecord::from_tokens(name, &mut tokens, None, Class::IN)?;
+        tokens.finish()?;
+
+        Ok(record)
+    }
+
+    /// Reads the optional TTL and class, the type and the record data from the remaining tokens of an entry.
+    /// `default_ttl` and `default_class` are used for the fields which are left out.
+    pub(crate) fn from_tokens(name: Name, tokens: &mut Tokens, default_ttl: Option<u32>, default_class: Class) -> Result<Self, ParseError> {
+        let mut ttl = None;
+        let mut class = None;
+
+        let rtype = loop {
+            let token = tokens.next("ResourceRecord.rtype")?;
+
+            if ttl.is_none() && token.text.starts_with(|c: char| c.is_ascii_digit()) {
+                ttl = Some(parse_ttl(&token.text).ok_or_else(|| invalid("ResourceRecord.ttl", token, "invalid TTL"))?);
+            } else if let (None, Ok(parsed)) = (class, token.text.parse::<Class>()) {
+                class = Some(parsed);
+            } else {
+                break token.text.parse::<Type>().map_err(|e| e.offset_by(token.offset))?;
+            }
+        };
+
+        let ttl = ttl.or(default_ttl)
+            .ok_or_else(|| ParseError::new("DNS", "ResourceRecord.ttl", tokens.peek().map_or(0, |token| token.offset), ParseErrorKind::Invalid("missing TTL")))?;
+        let rdata = RData::from_tokens(rtype, tokens)?;
+
+        Ok(ResourceRecord { name, rtype, class: class.unwrap_or(default_class), ttl, rdata })
+    }
+
+    /// Writes the record to the given message encoder, which compresses its names if enabled.
+    /// Panics if the data is longer than 65535 bytes, see [ResourceRecord::rlength].
+    pub fn encode(&self, encoder: &mut MessageEncoder) {
+        encoder.write_name(&self.name);
+        encoder.write_u16(self.rtype.into());
+        encoder.write_u16(u16::from(self.class));
+        encoder.write_u32(self.ttl);
+
+        // The length of the data is only known once it has been written, since it may contain compressed names.
+        let rlength_offset = encoder.bytes().len();
+        encoder.write_u16(0);
+        self.rdata.encode(encoder);
+
+        let rlength = encoder.bytes().len() - rlength_offset - size_of::<u16>();
+        encoder.patch_u16(rlength_offset, u16::try_from(rlength).expect(RDATA_TOO_LONG));
     }
 }
 
@@ -47,34 +141,119 @@ impl Raw for ResourceRecord {
 
         bytes.append(&mut self.name.raw());
 
-        let mut start = self.name.raw_size();
+        let mut fields = [0; 10];
+        NetworkEndian::write_u16(&mut fields[0..2], self.rtype.into());
+        NetworkEndian::write_u16(&mut fields[2..4], u16::from(self.class));
+        NetworkEndian::write_u32(&mut fields[4..8], self.ttl);
+        NetworkEndian::write_u16(&mut fields[8..10], self.rlength());
+        bytes.extend_from_slice(&fields);
 
-        bytes.push(0);
-        bytes.push(0);
-        NetworkEndian::write_u16(&mut bytes[start..start + 2], self.rtype as u16);
-        start += 2;
+        bytes.append(&mut self.rdata.raw());
 
-        bytes.push(0);
-        bytes.push(0);
-        NetworkEndian::write_u16(&mut bytes[start..start + 2], self.class as u16);
-        start += 2;
+        bytes
+    }
 
-        bytes.push(0);
-        bytes.push(0);
-        NetworkEndian::write_u16(&mut bytes[start..start + 2], self.ttl as u16);
-        start += 2;
+    fn raw_size(&self) -> usize {
+        self.name.raw_size() + size_of::<u16>() + size_of::<u16>() + size_of::<u32>() + size_of::<u16>() + self.rdata.raw_size()
+    }
+}
 
-        bytes.push(0);
-        bytes.push(0);
-        NetworkEndian::write_u16(&mut bytes[start..start + 4], self.rlength as u16);
-        start += 2;
+impl FromStr for ResourceRecord {
+    type Err = ParseError;
 
-        bytes.append(&mut self.rdata.raw());
+    /// Parses a record in presentation format with relative names taken to be relative to the root,
+    /// see [ResourceRecord::parse_with_origin].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ResourceRecord::parse_with_origin(s, &Name::root())
+    }
+}
 
-        bytes
+impl std::fmt::Display for ResourceRecord {
+    /// Formats the record as a line of a master file, e.g. `example.com. 300 IN A 192.0.2.1`
+    /// [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {} {} {}", self.name.to_fqdn(), self.ttl, self.class, self.rtype, self.rdata)
     }
+}
 
-    fn raw_size(&self) -> usize {
-        self.name.raw_size() + size_of::<Type>() + size_of::<Class>() + size_of::<u16>() + size_of::<u16>() + self.rdata.raw_size()
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use crate::{application::dns::{rdata::{CharacterString, RData, MX, TXT}, Class, Name, Type}, error::ParseErrorKind, Raw};
+
+    use super::ResourceRecord;
+
+    #[test]
+    fn test_resource_record() {
+        // "example.com", followed by an A record and a record of unknown type which both point back to it.
+        let message = b"\x07example\x03com\x00\
+                        \xC0\x00\x00\x01\x00\x01\x00\x01\x51\x80\x00\x04\x5D\xB8\xD8\x22\
+                        \xC0\x00\xFF\x00\x00\x01\x00\x00\x00\x3C\x00\x03\x01\x02\x03";
+
+        let (a, size) = ResourceRecord::from_message(message, 13).unwrap();
+        assert_eq!(size, 16);
+        assert_eq!(a, ResourceRecord::new(Name::new("example.com"), Type::A, Class::IN, 86400, RData::A(Ipv4Addr::new(93, 184, 216, 34))));
+
+        let (unknown, size) = ResourceRecord::from_message(message, 29).unwrap();
+        assert_eq!(size, 15);
+        assert_eq!(unknown.rlength(), 3);
+        assert_eq!(unknown.raw(), b"\x07example\x03com\x00\xFF\x00\x00\x01\x00\x00\x00\x3C\x00\x03\x01\x02\x03");
+        assert_eq!(ResourceRecord::from_bytes(&unknown.raw()).unwrap(), unknown);
+
+        // A multicast DNS record with the cache-flush bit set keeps its whole class.
+        let mdns = b"\x07example\x05local\x00\x00\x01\x80\x01\x00\x00\x00\x78\x00\x04\xC0\x00\x02\x01";
+        let record = ResourceRecord::from_bytes(mdns).unwrap();
+        assert_eq!(record.class, Class::Unknown(0x8001));
+        assert_eq!(record.raw(), mdns);
+        assert_eq!(record.to_string(), "example.local. 120 CLASS32769 A 192.0.2.1");
+        assert_eq!(record.to_string().parse::<ResourceRecord>().unwrap(), record);
+
+        // The record data must fill rlength exactly.
+        let err = ResourceRecord::from_bytes(b"\x00\x00\x01\x00\x01\x00\x00\x00\x3C\x00\x05\x01\x02\x03\x04\x05").unwrap_err();
+        assert_eq!((err.offset, err.kind), (11, ParseErrorKind::BadLength(5)));
+        let err = ResourceRecord::from_bytes(b"\x00\x00\x01\x00\x01\x00\x00\x00\x3C\x00\x03\x01\x02\x03\x04").unwrap_err();
+        assert_eq!((err.offset, err.kind), (11, ParseErrorKind::Truncated { needed: 4, available: 3 }));
+        let err = ResourceRecord::from_bytes(b"\x00\x00\x01\x00\x01\x00\x00\x00\x3C\x00\x04\x01\x02\x03").unwrap_err();
+        assert_eq!((err.offset, err.kind), (11, ParseErrorKind::Truncated { needed: 4, available: 3 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 65535 bytes")]
+    fn test_resource_record_too_long() {
+        // 300 strings of 255 bytes do not fit in rlength, which must not wrap around.
+        let txt = TXT::new(vec![CharacterString::new(vec![b'a'; 255]).unwrap(); 300]).unwrap();
+        ResourceRecord::new(Name::new("example.com"), Type::TXT, Class::IN, 300, RData::TXT(txt)).raw();
+    }
+
+    #[test]
+    fn test_resource_record_presentation() {
+        let origin = Name::new("example.com");
+        let mx = ResourceRecord::parse_with_origin("www 3600 IN MX 10 mail.example.com.", &origin).unwrap();
+        assert_eq!(mx, ResourceRecord::new(Name::new("www.example.com"), Type::MX, Class::IN, 3600, RData::MX(MX::new(10, Name::new("mail.example.com")))));
+        assert_eq!(mx.to_string().parse::<ResourceRecord>().unwrap(), mx);
+
+        // The class may come first or be left out, and the TTL may use units.
+        assert_eq!(ResourceRecord::parse_with_origin("www IN 1h MX 10 mail", &origin).unwrap(), mx);
+        assert_eq!(ResourceRecord::parse_with_origin("www.example.com. 1H MX 10 mail.example.com.", &origin).unwrap(), mx);
+
+        let txt = r#"a\.b\032c.example. 300 CH TXT "quoted; text" unquoted "\"\195\169""#.parse::<ResourceRecord>().unwrap();
+        assert_eq!(txt.name.labels()[0].as_bytes(), b"a.b c");
+        assert_eq!(txt.class, Class::CH);
+        assert_eq!(txt.rdata.raw(), b"\x0Cquoted; text\x08unquoted\x03\"\xC3\xA9");
+        assert_eq!(txt.to_string(), r#"a\.b\032c.example. 300 CH TXT "quoted; text" "unquoted" "\"\195\169""#);
+
+        let generic = "example. 60 CLASS1 TYPE65280 \\# 3 ( 010203 )".parse::<ResourceRecord>().unwrap();
+        assert_eq!(generic.raw(), b"\x07example\x00\xFF\x00\x00\x01\x00\x00\x00\x3C\x00\x03\x01\x02\x03");
+        assert_eq!(generic.to_string(), "example. 60 IN TYPE65280 \\# 3 010203");
+
+        let err = "www IN A 192.0.2.1".parse::<ResourceRecord>().unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Invalid("missing TTL"));
+        let err = "www 60 IN BOGUS 192.0.2.1".parse::<ResourceRecord>().unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, ParseErrorKind::Invalid("unknown type")));
+        let err = "www 60 IN A".parse::<ResourceRecord>().unwrap_err();
+        assert_eq!((err.offset, err.kind), (11, ParseErrorKind::Invalid("missing field")));
+        assert!("www 60 IN A 192.0.2.1\nwww 60 IN A 192.0.2.2".parse::<ResourceRecord>().is_err());
+        assert!("".parse::<ResourceRecord>().is_err());
     }
 }
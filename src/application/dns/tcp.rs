@@ -0,0 +1,142 @@
+use crate::error::{ParseError, ParseErrorKind};
+
+use super::{read_u16, DNSLayer};
+
+/// Splits a stream of DNS over TCP into messages, each of which is prefixed with its length as two bytes
+/// [RFC 1035 §4.2.2](https://datatracker.ietf.org/doc/html/rfc1035#section-4.2.2).
+/// Bytes are pushed as they are received, and each message is taken out as soon as all of its bytes have arrived.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TcpDecoder {
+    buffer: Vec<u8>,
+
+    /// The position in the buffer of the first byte which has not been taken out yet.
+    start: usize,
+
+    /// The offset in the stream of the start of the buffer, to which parse errors are relative.
+    offset: usize
+}
+
+impl TcpDecoder {
+    pub fn new() -> Self {
+        TcpDecoder::default()
+    }
+
+    /// Appends bytes received from the stream.
+    pub fn push(&mut self, bytes: &[u8]) {
+        // The bytes of messages which were taken out are only dropped here, so that taking out many messages is cheap.
+        if self.start > 0 {
+            self.buffer.drain(..self.start);
+            self.offset += self.start;
+            self.start = 0;
+        }
+
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the next message if all of its bytes have been pushed, or None if more are needed.
+    /// A message which cannot be parsed is skipped and its error is returned, so that the messages after it can still be read.
+    /// The offsets of errors are counted from the start of the stream.
+    pub fn next_message(&mut self) -> Option<Result<DNSLayer, ParseError>> {
+        let pending = &self.buffer[self.start..];
+        let length = usize::from(read_u16(pending, 0, "TCP.length").ok()?);
+        let message = pending.get(2..2 + length)?;
+
+        let position = self.offset + self.start;
+        let result = DNSLayer::from_bytes(message).map_err(|e| e.offset_by(position + 2));
+        self.start += 2 + length;
+
+        Some(result)
+    }
+
+    /// Returns the number of bytes which have been pushed but not taken out as part of a message.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// Checks that the stream did not end within a message, i.e. that there are no bytes left which do not form a whole message.
+    /// Should be called once the stream is closed and all messages have been taken out.
+    pub fn finish(&self) -> Result<(), ParseError> {
+        let pending = &self.buffer[self.start..];
+        let position = self.offset + self.start;
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let length = read_u16(pending, 0, "TCP.length").map_err(|e| e.offset_by(position))?;
+        Err(ParseError::new("DNS", "TCP.message", position + 2, ParseErrorKind::Truncated { needed: usize::from(length), available: pending.len() - 2 }))
+    }
+}
+
+/// Parses a whole stream of DNS over TCP into its messages, e.g. the response to a zone transfer.
+/// Fails at the first message which cannot be parsed, or if the stream ends within a message.
+pub fn parse_tcp_stream(bytes: &[u8]) -> Result<Vec<DNSLayer>, ParseError> {
+    let mut decoder = TcpDecoder::new();
+    decoder.push(bytes);
+
+    let mut messages = Vec::new();
+    while let Some(message) = decoder.next_message() {
+        messages.push(message?);
+    }
+    decoder.finish()?;
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::{rdata::RData, Class, DNSLayer, Name, ResourceRecord, Type};
+    use crate::error::ParseErrorKind;
+    use crate::Raw;
+
+    use super::{parse_tcp_stream, TcpDecoder};
+
+    #[test]
+    fn test_tcp() {
+        let query = DNSLayer::query("example.com", Type::AXFR).id(0x1234).build();
+        let framed = query.raw_tcp();
+        assert_eq!(framed[..2], (query.raw().len() as u16).to_be_bytes());
+        assert_eq!(framed[2..], query.raw());
+
+        // A response spread over several messages, received in chunks which do not line up with them.
+        let soa = ResourceRecord::parse_with_origin("@ 3600 IN SOA ns1 admin 1 2 3 4 5", &Name::new("example.com")).unwrap();
+        let a = ResourceRecord::new(Name::new("www.example.com"), Type::A, Class::IN, 300, RData::A("192.0.2.1".parse().unwrap()));
+        let messages = vec![
+            DNSLayer::response_to(&query).answer(soa.clone()).answer(a.clone()).build(),
+            DNSLayer::response_to(&query).answer(a).build(),
+            DNSLayer::response_to(&query).answer(soa).build(),
+        ];
+        let stream: Vec<u8> = messages.iter().flat_map(|message| message.raw_tcp()).collect();
+
+        let mut decoder = TcpDecoder::new();
+        let mut decoded = Vec::new();
+        for chunk in stream.chunks(7) {
+            decoder.push(chunk);
+            while let Some(message) = decoder.next_message() {
+                decoded.push(message.unwrap());
+            }
+        }
+        assert_eq!(decoded, messages);
+        assert_eq!(decoder.buffered(), 0);
+        assert!(decoder.finish().is_ok());
+        assert_eq!(parse_tcp_stream(&stream).unwrap(), messages);
+        assert_eq!(parse_tcp_stream(&[]).unwrap(), vec![]);
+
+        // The stream ends within the length or within the last message.
+        let err = parse_tcp_stream(&stream[..stream.len() - 1]).unwrap_err();
+        let last = stream.len() - messages[2].raw_tcp().len();
+        assert_eq!((err.field, err.offset), ("TCP.message", last + 2));
+        assert_eq!(err.kind, ParseErrorKind::Truncated { needed: messages[2].raw().len(), available: messages[2].raw().len() - 1 });
+        let err = parse_tcp_stream(&stream[..last + 1]).unwrap_err();
+        assert_eq!((err.field, err.offset), ("TCP.length", last));
+
+        // A malformed message is skipped, with the error pointing into the stream.
+        let mut decoder = TcpDecoder::new();
+        decoder.push(b"\x00\x03\x00\x00\x00");
+        decoder.push(&stream);
+        let err = decoder.next_message().unwrap().unwrap_err();
+        assert_eq!(err.offset, 2);
+        assert_eq!(decoder.next_message().unwrap().unwrap(), messages[0]);
+        assert!(parse_tcp_stream(&[b"\x00\x03\x00\x00\x00", &stream[..]].concat()).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::HashMap;
+
+use super::rdata::RData;
+use super::{Class, DNSLayer, Name, OpCode, Question, RCode, ResourceRecord, Type, Zone};
+
+/// A dynamic update message [RFC 2136](https://datatracker.ietf.org/doc/html/rfc2136), whose sections have other meanings
+/// than in a query: the question section names the zone to update, the answer section holds the prerequisites
+/// and the authority section the updates [RFC 2136 §2](https://datatracker.ietf.org/doc/html/rfc2136#section-2).
+///
+/// Prerequisites and updates are added with the builder-style methods, which encode them with the classes and TTLs
+/// that RFC 2136 assigns to them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Update {
+    layer: DNSLayer
+}
+
+impl Update {
+    /// Starts an update of the given zone in the IN class.
+    pub fn new(zone: Name) -> Self {
+        Update::with_class(zone, Class::IN)
+    }
+
+    /// Starts an update of the given zone in the given class.
+    pub fn with_class(zone: Name, class: Class) -> Self {
+        let mut layer = DNSLayer::new();
+        layer.header_mut().set_opcode(OpCode::Update);
+        layer.add_question(Question::new(zone, Type::SOA, class, false));
+
+        Update { layer }
+    }
+
+    /// Views the given message as an update. Fails with FORMERR unless it has the UPDATE opcode and a zone section
+    /// which consists of a single SOA entry [RFC 2136 §3.1.1](https://datatracker.ietf.org/doc/html/rfc2136#section-3.1.1).
+    pub fn from_layer(layer: DNSLayer) -> Result<Self, RCode> {
+        if layer.header().get_opcode() != OpCode::Update || layer.questions().len() != 1 || layer.questions()[0].qtype != Type::SOA {
+            return Err(RCode::FormatError);
+        }
+
+        Ok(Update { layer })
+    }
+
+    pub fn layer(&self) -> &DNSLayer {
+        &self.layer
+    }
+
+    pub fn into_layer(self) -> DNSLayer {
+        self.layer
+    }
+
+    /// Sets the ID of the message.
+    pub fn id(mut self, id: u16) -> Self {
+        self.layer.header_mut().id = id;
+        self
+    }
+
+    /// Returns the name of the zone to update.
+    pub fn zone(&self) -> &Name {
+        &self.layer.questions()[0].name
+    }
+
+    /// Returns the class of the zone to update, which is the class of the records to add.
+    pub fn class(&self) -> Class {
+        self.layer.questions()[0].class()
+    }
+
+    /// Returns the records of the prerequisite section, which is the answer section of the message.
+    pub fn prerequisites(&self) -> &[ResourceRecord] {
+        self.layer.asnwers()
+    }
+
+    /// Returns the records of the update section, which is the authority section of the message.
+    pub fn updates(&self) -> &[ResourceRecord] {
+        self.layer.authority()
+    }
+
+    /// Returns the records of the additional data section.
+    pub fn additional(&self) -> &[ResourceRecord] {
+        self.layer.additional()
+    }
+
+    /// Requires that an RRset of the given name and type exists
+    /// [RFC 2136 §2.4.1](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4.1).
+    pub fn rrset_exists(mut self, name: Name, rtype: Type) -> Self {
+        self.layer.add_answer(empty(name, rtype, Class::ANY));
+        self
+    }
+
+    /// Requires that the RRset of the given records exists with exactly their data, regardless of TTLs
+    /// [RFC 2136 §2.4.2](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4.2).
+    /// The records should all have the same name and type.
+    pub fn rrset_equals(mut self, records: impl IntoIterator<Item = ResourceRecord>) -> Self {
+        let class = self.class();
+        for record in records {
+            self.layer.add_answer(ResourceRecord { class, ttl: 0, ..record });
+        }
+
+        self
+    }
+
+    /// Requires that no RRset of the given name and type exists
+    /// [RFC 2136 §2.4.3](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4.3).
+    pub fn rrset_does_not_exist(mut self, name: Name, rtype: Type) -> Self {
+        self.layer.add_answer(empty(name, rtype, Class::NONE));
+        self
+    }
+
+    /// Requires that the given name owns at least one record
+    /// [RFC 2136 §2.4.4](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4.4).
+    pub fn name_in_use(mut self, name: Name) -> Self {
+        self.layer.add_answer(empty(name, Type::ANY, Class::ANY));
+        self
+    }
+
+    /// Requires that the given name owns no records
+    /// [RFC 2136 §2.4.5](https://datatracker.ietf.org/doc/html/rfc2136#section-2.4.5).
+    pub fn name_not_in_use(mut self, name: Name) -> Self {
+        self.layer.add_answer(empty(name, Type::ANY, Class::NONE));
+        self
+    }
+
+    /// Adds the given record to its RRset [RFC 2136 §2.5.1](https://datatracker.ietf.org/doc/html/rfc2136#section-2.5.1).
+    /// The record takes the class of the zone.
+    pub fn add_record(mut self, record: ResourceRecord) -> Self {
+        let class = self.class();
+        self.layer.add_authority(ResourceRecord { class, ..record });
+        self
+    }
+
+    /// Deletes the RRset of the given name and type [RFC 2136 §2.5.2](https://datatracker.ietf.org/doc/html/rfc2136#section-2.5.2).
+    pub fn delete_rrset(mut self, name: Name, rtype: Type) -> Self {
+        self.layer.add_authority(empty(name, rtype, Class::ANY));
+        self
+    }
+
+    /// Deletes all RRsets of the given name [RFC 2136 §2.5.3](https://datatracker.ietf.org/doc/html/rfc2136#section-2.5.3).
+    pub fn delete_all(mut self, name: Name) -> Self {
+        self.layer.add_authority(empty(name, Type::ANY, Class::ANY));
+        self
+    }
+
+    /// Deletes the record with the name, type and data of the given one from its RRset
+    /// [RFC 2136 §2.5.4](https://datatracker.ietf.org/doc/html/rfc2136#section-2.5.4).
+    pub fn delete_record(mut self, record: ResourceRecord) -> Self {
+        self.layer.add_authority(ResourceRecord { class: Class::NONE, ttl: 0, ..record });
+        self
+    }
+
+    /// Applies the update to the given zone as a primary server would [RFC 2136 §3](https://datatracker.ietf.org/doc/html/rfc2136#section-3):
+    /// the prerequisites are checked and the updates are checked and carried out in order.
+    /// Fails with the response code of the first check which does not pass, in which case the zone is left unchanged.
+    ///
+    /// As the RFC prescribes, some updates are silently ignored: the deletion of the SOA RRset, of the NS RRset at
+    /// the apex or of its last record, the addition of an SOA record with an older or equal serial, and additions which would
+    /// mix a CNAME record with other data.
+    pub fn apply(&self, zone: &mut Zone) -> Result<(), RCode> {
+        if self.zone() != zone.origin() {
+            return Err(RCode::NOTAUTH);
+        }
+
+        self.check_prerequisites(zone)?;
+        self.prescan(zone)?;
+
+        for record in self.updates() {
+            self.apply_record(zone, record);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the prerequisite section against the zone [RFC 2136 §3.2](https://datatracker.ietf.org/doc/html/rfc2136#section-3.2).
+    fn check_prerequisites(&self, zone: &Zone) -> Result<(), RCode> {
+        // The records of value-dependent prerequisites are gathered into RRsets, which are compared as a whole.
+        let mut rrsets: HashMap<(&Name, Type), Vec<&RData>> = HashMap::new();
+
+        for record in self.prerequisites() {
+            if record.ttl != 0 {
+                return Err(RCode::FormatError);
+            }
+            if !record.name.is_subdomain_of(zone.origin()) {
+                return Err(RCode::NOTZONE);
+            }
+
+            let empty = record.rlength() == 0;
+            match (record.class, record.rtype) {
+                (Class::ANY | Class::NONE, _) if !empty => return Err(RCode::FormatError),
+                (Class::ANY, Type::ANY) if !zone.contains_name(&record.name) => return Err(RCode::NameError),
+                (Class::ANY, Type::ANY) => {},
+                (Class::ANY, rtype) if zone.get(&record.name, rtype).is_none() => return Err(RCode::NXRRSET),
+                (Class::ANY, _) => {},
+                (Class::NONE, Type::ANY) if zone.contains_name(&record.name) => return Err(RCode::YXDOMAIN),
+                (Class::NONE, Type::ANY) => {},
+                (Class::NONE, rtype) if zone.get(&record.name, rtype).is_some() => return Err(RCode::YXRRSET),
+                (Class::NONE, _) => {},
+                (class, rtype) if class == self.class() => rrsets.entry((&record.name, rtype)).or_default().push(&record.rdata),
+                _ => return Err(RCode::FormatError)
+            }
+        }
+
+        for ((name, rtype), expected) in rrsets {
+            let found: Vec<&RData> = zone.get(name, rtype).unwrap_or_default().iter().map(|rr| &rr.rdata).collect();
+
+            if !found.iter().all(|rdata| expected.contains(rdata)) || !expected.iter().all(|rdata| found.contains(rdata)) {
+                return Err(RCode::NXRRSET);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the update section before any of it is carried out
+    /// [RFC 2136 §3.4.1](https://datatracker.ietf.org/doc/html/rfc2136#section-3.4.1).
+    fn prescan(&self, zone: &Zone) -> Result<(), RCode> {
+        for record in self.updates() {
+            if !record.name.is_subdomain_of(zone.origin()) {
+                return Err(RCode::NOTZONE);
+            }
+
+            let meta = matches!(record.rtype, Type::ANY | Type::AXFR | Type::IXFR | Type::MAILA | Type::MAILB);
+            let valid = match record.class {
+                Class::ANY => record.ttl == 0 && record.rlength() == 0 && (record.rtype == Type::ANY || !meta),
+                Class::NONE => record.ttl == 0 && !meta,
+                class => class == self.class() && !meta
+            };
+
+            if !valid {
+                return Err(RCode::FormatError);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Carries out a single update [RFC 2136 §3.4.2](https://datatracker.ietf.org/doc/html/rfc2136#section-3.4.2).
+    fn apply_record(&self, zone: &mut Zone, record: &ResourceRecord) {
+        let apex = record.name == *zone.origin();
+
+        match record.class {
+            Class::ANY => {
+                let types = if record.rtype == Type::ANY { zone.types(&record.name) } else { vec![record.rtype] };
+
+                for rtype in types {
+                    if !(apex && matches!(rtype, Type::SOA | Type::NS)) {
+                        zone.remove(&record.name, rtype);
+                    }
+                }
+            },
+            Class::NONE => {
+                let last_ns = apex && record.rtype == Type::NS && zone.get(&record.name, Type::NS).is_some_and(|rrset| rrset.len() <= 1);
+
+                if record.rtype != Type::SOA && !last_ns {
+                    zone.remove_record(&ResourceRecord { class: self.class(), ..record.clone() });
+                }
+            },
+            _ => insert_record(zone, record)
+        }
+    }
+}
+
+/// Constructs a record without data, which stands for a whole RRset or name in prerequisites and updates.
+fn empty(name: Name, rtype: Type, class: Class) -> ResourceRecord {
+    ResourceRecord::new(name, rtype, class, 0, RData::Unknown { rtype, bytes: Vec::new() })
+}
+
+fn insert_record(zone: &mut Zone, record: &ResourceRecord) {
+    let types = zone.types(&record.name);
+    // Only DNSSEC records may share their name with a CNAME record
+    // [RFC 4035 §2.5](https://datatracker.ietf.org/doc/html/rfc4035#section-2.5).
+    let other_data = types.iter().any(|rtype| !matches!(rtype, Type::CNAME | Type::RRSIG | Type::NSEC));
+
+    match record.rtype {
+        Type::CNAME if other_data => return,
+        Type::CNAME => {
+            zone.remove(&record.name, Type::CNAME);
+        },
+        Type::RRSIG | Type::NSEC => {},
+        _ if types.contains(&Type::CNAME) => return,
+        Type::SOA => {
+            // The SOA record is only replaced by one whose serial is newer, in serial number arithmetic
+            // [RFC 1982](https://datatracker.ietf.org/doc/html/rfc1982), [RFC 2136 §3.4.2.2](https://datatracker.ietf.org/doc/html/rfc2136#section-3.4.2.2).
+            match (zone.get(&record.name, Type::SOA).and_then(|rrset| rrset.first()).and_then(ResourceRecord::soa_serial), record.soa_serial()) {
+                (Some(old), Some(new)) if is_newer(new, old) => {
+                    zone.remove(&record.name, Type::SOA);
+                },
+                _ => return
+            }
+        },
+        _ => {}
+    }
+
+    // A record with the same data as one in the zone replaces it, e.g. to change its TTL.
+    zone.remove_record(record);
+    zone.insert(record.clone());
+}
+
+fn is_newer(serial: u32, than: u32) -> bool {
+    let distance = serial.wrapping_sub(than);
+    distance != 0 && distance < 1 << 31
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::{rdata::RData, Class, DNSLayer, Name, OpCode, RCode, ResourceRecord, Type, Zone};
+    use crate::Raw;
+
+    use super::Update;
+
+    const ZONE: &str = "$TTL 300
+@    SOA   ns1 admin 10 7200 3600 604800 300
+@    NS    ns1
+@    NS    ns2
+ns1  A     192.0.2.53
+ns2  A     192.0.2.54
+www  A     192.0.2.1
+www  A     192.0.2.2
+ftp  CNAME www
+";
+
+    fn origin() -> Name {
+        Name::new("example.com")
+    }
+
+    fn record(text: &str) -> ResourceRecord {
+        ResourceRecord::parse_with_origin(text, &origin()).unwrap()
+    }
+
+    fn zone() -> Zone {
+        Zone::parse(ZONE, &origin()).unwrap()
+    }
+
+    fn name(name: &str) -> Name {
+        Name::new(&format!("{}.example.com", name))
+    }
+
+    fn apply(update: Update) -> Result<Zone, RCode> {
+        let mut zone = zone();
+        update.apply(&mut zone).map(|_| zone)
+    }
+
+    #[test]
+    fn test_update_message() {
+        let update = Update::new(origin()).id(42)
+            .name_in_use(name("www"))
+            .rrset_exists(name("www"), Type::A)
+            .rrset_does_not_exist(name("www"), Type::AAAA)
+            .name_not_in_use(name("new"))
+            .rrset_equals(vec![record("ns1 3600 CH A 192.0.2.53")])
+            .add_record(record("new 300 IN A 192.0.2.3"))
+            .delete_rrset(name("www"), Type::A)
+            .delete_all(name("ftp"))
+            .delete_record(record("@ 300 IN NS ns2"));
+
+        assert_eq!(update.zone(), &origin());
+        assert_eq!(update.class(), Class::IN);
+        let classes = |records: &[ResourceRecord]| records.iter().map(|rr| (rr.class, rr.ttl)).collect::<Vec<_>>();
+        assert_eq!(classes(update.prerequisites()), vec![(Class::ANY, 0), (Class::ANY, 0), (Class::NONE, 0), (Class::NONE, 0), (Class::IN, 0)]);
+        assert_eq!(classes(update.updates()), vec![(Class::IN, 300), (Class::ANY, 0), (Class::ANY, 0), (Class::NONE, 0)]);
+        assert_eq!(update.prerequisites()[0].rtype, Type::ANY);
+        assert_eq!(update.prerequisites()[0].rlength(), 0);
+        assert!(update.additional().is_empty());
+
+        // Records without data survive the round trip through the wire format.
+        let layer = DNSLayer::from_bytes(&update.layer().raw()).unwrap();
+        assert_eq!(layer, *update.layer());
+        assert_eq!(layer.asnwers()[1].rdata, RData::Unknown { rtype: Type::A, bytes: vec![] });
+        assert_eq!(Update::from_layer(layer).unwrap(), update);
+        assert_eq!(update.prerequisites()[1].to_string(), "www.example.com. 0 ANY A \\# 0");
+
+        // Empty data is only accepted from records of class ANY or NONE in the prerequisite and update sections of an update.
+        let mut response = update.layer().clone();
+        response.header_mut().set_opcode(OpCode::StandardQuery);
+        response.header_mut().set_query(false);
+        let err = DNSLayer::from_bytes(&response.raw()).unwrap_err();
+        assert_eq!(err.field, "RData.A");
+        let empty = ResourceRecord::new(name("www"), Type::A, Class::IN, 300, RData::Unknown { rtype: Type::A, bytes: vec![] });
+        assert!(DNSLayer::from_bytes(&update.clone().add_record(empty).layer().raw()).is_err());
+        assert!(RData::from_bytes(Type::A, &[]).is_err());
+
+        // The zone section has to consist of a single SOA entry.
+        let query = DNSLayer::query("example.com", Type::SOA).build();
+        assert_eq!(Update::from_layer(query).unwrap_err(), RCode::FormatError);
+        let mut layer = update.into_layer();
+        layer.questions_mut()[0].qtype = Type::A;
+        assert_eq!(Update::from_layer(layer).unwrap_err(), RCode::FormatError);
+    }
+
+    #[test]
+    fn test_update_prerequisites() {
+        let update = || Update::new(origin());
+
+        assert_eq!(apply(update().name_in_use(name("www"))), Ok(zone()));
+        assert_eq!(apply(update().name_in_use(name("new"))), Err(RCode::NameError));
+        assert_eq!(apply(update().rrset_exists(name("www"), Type::A)), Ok(zone()));
+        assert_eq!(apply(update().rrset_exists(name("www"), Type::AAAA)), Err(RCode::NXRRSET));
+        assert_eq!(apply(update().rrset_does_not_exist(name("www"), Type::AAAA)), Ok(zone()));
+        assert_eq!(apply(update().rrset_does_not_exist(name("www"), Type::A)), Err(RCode::YXRRSET));
+        assert_eq!(apply(update().name_not_in_use(name("new"))), Ok(zone()));
+        assert_eq!(apply(update().name_not_in_use(name("WWW"))), Err(RCode::YXDOMAIN));
+
+        // Value-dependent prerequisites compare the whole RRset, regardless of order and TTLs.
+        let www = vec![record("www 60 IN A 192.0.2.2"), record("www 60 IN A 192.0.2.1")];
+        assert_eq!(apply(update().rrset_equals(www.clone())), Ok(zone()));
+        assert_eq!(apply(update().rrset_equals(www[..1].to_vec())), Err(RCode::NXRRSET));
+        assert_eq!(apply(update().rrset_equals(vec![www[0].clone(), record("www 60 IN A 192.0.2.3")])), Err(RCode::NXRRSET));
+        assert_eq!(apply(update().rrset_equals(vec![record("new 60 IN A 192.0.2.3")])), Err(RCode::NXRRSET));
+
+        // A failed prerequisite stops the updates from being carried out.
+        assert_eq!(apply(update().name_in_use(name("new")).delete_all(name("www"))), Err(RCode::NameError));
+
+        assert_eq!(apply(Update::new(name("www")).name_in_use(name("www"))), Err(RCode::NOTAUTH));
+        assert_eq!(apply(update().name_in_use(Name::new("www.example.org"))), Err(RCode::NOTZONE));
+        let mut layer = update().rrset_exists(name("www"), Type::A).into_layer();
+        layer.answers_mut()[0].ttl = 300;
+        assert_eq!(Update::from_layer(layer).unwrap().apply(&mut zone()), Err(RCode::FormatError));
+        let mut layer = update().rrset_exists(name("www"), Type::A).into_layer();
+        layer.answers_mut()[0].rdata = RData::A("192.0.2.1".parse().unwrap());
+        assert_eq!(Update::from_layer(layer).unwrap().apply(&mut zone()), Err(RCode::FormatError));
+    }
+
+    #[test]
+    fn test_update_apply() {
+        let update = || Update::new(origin());
+
+        let mut expected = zone();
+        expected.insert(record("new 300 IN A 192.0.2.3"));
+        expected.remove(&name("www"), Type::A);
+        expected.remove(&name("ftp"), Type::CNAME);
+        expected.remove_record(&record("ns2 300 IN A 192.0.2.54"));
+        let result = apply(update()
+            .add_record(record("new 300 IN A 192.0.2.3"))
+            .delete_rrset(name("www"), Type::A)
+            .delete_all(name("ftp"))
+            .delete_record(record("ns2 300 IN A 192.0.2.54")));
+        assert_eq!(result, Ok(expected));
+
+        // Adding a record which exists replaces it, so that its TTL changes.
+        let zone = apply(update().add_record(record("www 60 IN A 192.0.2.1"))).unwrap();
+        assert_eq!(zone.get(&name("www"), Type::A).unwrap().iter().map(|rr| rr.ttl).collect::<Vec<_>>(), vec![60, 300]);
+
+        // The SOA and NS RRsets at the apex are never deleted, nor is its last NS record.
+        let zone = apply(update().delete_all(origin()).delete_rrset(origin(), Type::NS)).unwrap();
+        assert_eq!(zone, self::zone());
+        let zone = apply(update().delete_record(record("@ 300 IN NS ns1")).delete_record(record("@ 300 IN NS ns2"))).unwrap();
+        assert_eq!(zone.get(&origin(), Type::NS).unwrap().len(), 1);
+        let soa = zone.soa().unwrap().clone();
+        assert_eq!(apply(update().delete_record(soa)).unwrap().len(), 8);
+
+        // The SOA record is only replaced by a newer one.
+        let zone = apply(update().add_record(record("@ 300 IN SOA ns1 admin 11 7200 3600 604800 300"))).unwrap();
+        assert_eq!(zone.soa().unwrap().to_string(), "example.com. 300 IN SOA ns1.example.com. admin.example.com. 11 7200 3600 604800 300");
+        assert_eq!(zone.len(), 8);
+        assert_eq!(apply(update().add_record(record("@ 300 IN SOA ns1 admin 9 7200 3600 604800 300"))), Ok(self::zone()));
+        assert_eq!(apply(update().add_record(record("@ 60 IN SOA ns2 hostmaster 10 1 2 3 4"))), Ok(self::zone()));
+
+        // CNAME records do not mix with other data, but replace each other.
+        assert_eq!(apply(update().add_record(record("ftp 300 IN A 192.0.2.1"))), Ok(self::zone()));
+        assert_eq!(apply(update().add_record(record("www 300 IN CNAME ftp"))), Ok(self::zone()));
+        let zone = apply(update().add_record(record("ftp 300 IN CNAME ns1"))).unwrap();
+        assert_eq!(zone.get(&name("ftp"), Type::CNAME).unwrap(), [record("ftp 300 IN CNAME ns1")]);
+
+        // Malformed updates are rejected before any of them is carried out.
+        assert_eq!(apply(update().delete_all(name("www")).add_record(record("www.example.org. 300 IN A 192.0.2.1"))), Err(RCode::NOTZONE));
+        assert_eq!(apply(update().delete_all(name("www")).delete_rrset(name("www"), Type::AXFR)), Err(RCode::FormatError));
+        assert_eq!(apply(update().delete_all(name("www")).delete_record(ResourceRecord::new(name("www"), Type::ANY, Class::IN, 0, RData::Unknown { rtype: Type::ANY, bytes: vec![] }))), Err(RCode::FormatError));
+        let mut layer = update().delete_all(name("www")).add_record(record("new 300 IN A 192.0.2.3")).into_layer();
+        layer.authority_mut()[1].class = Class::CH;
+        assert_eq!(Update::from_layer(layer).unwrap().apply(&mut self::zone()), Err(RCode::FormatError));
+    }
+}
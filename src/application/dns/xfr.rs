@@ -0,0 +1,357 @@
+use super::{DNSLayer, RCode, ResourceRecord, Type, Zone};
+
+/// Reads the response to a zone transfer, whose answers may be spread over many messages,
+/// and checks that they are framed by SOA records as for AXFR [RFC 5936 §2.2](https://datatracker.ietf.org/doc/html/rfc5936#section-2.2)
+/// or IXFR [RFC 1995 §4](https://datatracker.ietf.org/doc/html/rfc1995#section-4).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XfrReader {
+    /// Whether differences are allowed, i.e. whether the response is to IXFR.
+    incremental: bool,
+    records: Vec<ResourceRecord>,
+    state: State
+}
+
+/// The position of the reader within the response, which is decided by the SOA records read so far.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum State {
+    Start,
+    /// Only the first SOA record has been read.
+    FirstSoa,
+    /// Reading the records of the whole zone.
+    Full,
+    /// Reading the records which a difference deletes, after the SOA record of the old version.
+    Deleting,
+    /// Reading the records which a difference adds, after the SOA record of the new version.
+    Adding,
+    Complete
+}
+
+impl XfrReader {
+    /// Constructs a reader for the response to an AXFR query, which has the whole zone.
+    pub fn axfr() -> Self {
+        XfrReader { incremental: false, records: Vec::new(), state: State::Start }
+    }
+
+    /// Constructs a reader for the response to an IXFR query, which may have the differences to the version of the client,
+    /// the whole zone, or only the SOA record if the client is up to date.
+    pub fn ixfr() -> Self {
+        XfrReader { incremental: true, ..XfrReader::axfr() }
+    }
+
+    /// Reads the answers of the next message of the response. Returns whether the transfer is complete.
+    /// A response to IXFR which only has the SOA record is never complete, since it can only be told apart from
+    /// the start of a longer response once the stream has ended; [XfrReader::finish] accepts it then.
+    pub fn push(&mut self, message: &DNSLayer) -> Result<bool, XfrError> {
+        let rcode = message.get_response_code();
+        if rcode != RCode::NoError {
+            return Err(XfrError::ResponseCode(rcode));
+        }
+
+        for record in message.asnwers() {
+            self.read(record.clone())?;
+        }
+
+        Ok(self.is_complete())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.state == State::Complete
+    }
+
+    fn read(&mut self, record: ResourceRecord) -> Result<(), XfrError> {
+        let serial = record.soa_serial();
+        let first = self.records.first().and_then(ResourceRecord::soa_serial);
+
+        self.state = match (self.state, serial) {
+            (State::Complete, _) => return Err(XfrError::TrailingRecords),
+            (State::Start, None) => return Err(XfrError::MissingSoa),
+            (State::Start, Some(_)) => State::FirstSoa,
+            // The zone only has its SOA record.
+            (State::FirstSoa, Some(serial)) if Some(serial) == first => State::Complete,
+            (State::FirstSoa, Some(_)) if self.incremental => State::Deleting,
+            (State::FirstSoa | State::Full, None) => State::Full,
+            (State::Full | State::Adding, Some(serial)) if Some(serial) == first => State::Complete,
+            (State::FirstSoa | State::Full, Some(serial)) => {
+                return Err(XfrError::SerialMismatch { expected: first.unwrap_or_default(), found: serial });
+            },
+            (State::Deleting, Some(_)) => State::Adding,
+            (State::Adding, Some(_)) => State::Deleting,
+            (state, None) => state
+        };
+
+        self.records.push(record);
+        Ok(())
+    }
+
+    /// Returns the transferred zone or differences, checking that the SOA records of consecutive differences line up.
+    /// Should be called once the response has ended.
+    pub fn finish(self) -> Result<Transfer, XfrError> {
+        // A response to IXFR which consists of a single SOA record means that the client is up to date.
+        if self.incremental && self.state == State::FirstSoa && self.records.len() == 1 {
+            return Ok(Transfer::UpToDate(self.records.into_iter().next().unwrap()));
+        }
+
+        if !self.is_complete() {
+            return Err(XfrError::Incomplete);
+        }
+
+        let records = self.records;
+        let last = records[0].soa_serial().unwrap_or_default();
+
+        // The response has the whole zone unless the second record is the SOA record of an older version.
+        if !matches!(records[1].soa_serial(), Some(serial) if serial != last) {
+            let mut zone = Zone::new(records[0].name.clone());
+            let closing = records.len() - 1;
+            for record in records.into_iter().take(closing) {
+                zone.insert(record);
+            }
+
+            return Ok(Transfer::Full(zone));
+        }
+
+        let mut diffs: Vec<IxfrDiff> = Vec::new();
+        let mut records = records[1..records.len() - 1].iter().cloned().peekable();
+
+        while let Some(old_soa) = records.next() {
+            let deleted = std::iter::from_fn(|| records.next_if(|rr| rr.soa_serial().is_none())).collect();
+            let new_soa = records.next().ok_or(XfrError::Incomplete)?;
+            let added = std::iter::from_fn(|| records.next_if(|rr| rr.soa_serial().is_none())).collect();
+
+            if let Some(previous) = diffs.last() {
+                let expected = previous.new_soa.soa_serial().unwrap_or_default();
+                let found = old_soa.soa_serial().unwrap_or_default();
+
+                if expected != found {
+                    return Err(XfrError::SerialMismatch { expected, found });
+                }
+            }
+
+            diffs.push(IxfrDiff { old_soa, deleted, new_soa, added });
+        }
+
+        // The differences lead up to the version of the opening SOA record.
+        let found = diffs.last().and_then(|diff| diff.new_soa.soa_serial()).unwrap_or_default();
+        if found != last {
+            return Err(XfrError::SerialMismatch { expected: last, found });
+        }
+
+        Ok(Transfer::Incremental(diffs))
+    }
+}
+
+impl Default for XfrReader {
+    fn default() -> Self {
+        XfrReader::axfr()
+    }
+}
+
+/// The contents of a complete zone transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transfer {
+    /// The whole zone, which is sent in response to AXFR, and to IXFR if the server does not send differences.
+    Full(Zone),
+
+    /// The differences from the version of the client to the current version, oldest first.
+    Incremental(Vec<IxfrDiff>),
+
+    /// The SOA record of the current version, which the client already has.
+    UpToDate(ResourceRecord)
+}
+
+/// The difference between two consecutive versions of a zone [RFC 1995 §4](https://datatracker.ietf.org/doc/html/rfc1995#section-4).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IxfrDiff {
+    /// The SOA record of the version the difference applies to.
+    pub old_soa: ResourceRecord,
+
+    pub deleted: Vec<ResourceRecord>,
+
+    /// The SOA record of the version the difference leads to.
+    pub new_soa: ResourceRecord,
+
+    pub added: Vec<ResourceRecord>
+}
+
+/// Constructs the messages of a response to AXFR with the given zone, with at most `max_answers` records in each message.
+/// The SOA record comes first and last, and the other records are in canonical order.
+/// Fails if the zone has no SOA record at its origin.
+pub fn axfr_messages(query: &DNSLayer, zone: &Zone, max_answers: usize) -> Result<Vec<DNSLayer>, XfrError> {
+    let soa = zone.soa().ok_or(XfrError::MissingSoa)?;
+    let records = zone.records().into_iter().filter(|rr| rr.rtype != Type::SOA);
+
+    Ok(messages(query, std::iter::once(soa).chain(records).chain(std::iter::once(soa)), max_answers))
+}
+
+/// Constructs the messages of a response to IXFR with the given differences, which lead up to the version of `soa`,
+/// with at most `max_answers` records in each message. Without differences, the response only has the SOA record.
+pub fn ixfr_messages(query: &DNSLayer, soa: &ResourceRecord, diffs: &[IxfrDiff], max_answers: usize) -> Vec<DNSLayer> {
+    if diffs.is_empty() {
+        return messages(query, std::iter::once(soa), max_answers);
+    }
+
+    let records = diffs.iter().flat_map(|diff| {
+        std::iter::once(&diff.old_soa).chain(&diff.deleted).chain(std::iter::once(&diff.new_soa)).chain(&diff.added)
+    });
+
+    messages(query, std::iter::once(soa).chain(records).chain(std::iter::once(soa)), max_answers)
+}
+
+/// Splits the records over authoritative responses to the query.
+fn messages<'a>(query: &DNSLayer, records: impl Iterator<Item = &'a ResourceRecord>, max_answers: usize) -> Vec<DNSLayer> {
+    let records: Vec<&ResourceRecord> = records.collect();
+
+    records.chunks(max_answers.max(1))
+        .map(|chunk| chunk.iter().fold(DNSLayer::response_to(query).authoritative(true), |builder, &rr| builder.answer(rr.clone())).build())
+        .collect()
+}
+
+/// An error in the response to a zone transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XfrError {
+    /// The server refused or failed the transfer.
+    ResponseCode(RCode),
+
+    /// The first record is not an SOA record.
+    MissingSoa,
+
+    /// The serial of an SOA record does not match the SOA record it has to match.
+    SerialMismatch { expected: u32, found: u32 },
+
+    /// There are records after the closing SOA record.
+    TrailingRecords,
+
+    /// The closing SOA record has not been read yet.
+    Incomplete
+}
+
+impl std::error::Error for XfrError {}
+
+impl std::fmt::Display for XfrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            XfrError::ResponseCode(rcode) => write!(f, "the transfer failed with {}", rcode),
+            XfrError::MissingSoa => write!(f, "the transfer does not start with an SOA record"),
+            XfrError::SerialMismatch { expected, found } => write!(f, "expected an SOA record with serial {} but found {}", expected, found),
+            XfrError::TrailingRecords => write!(f, "records after the end of the transfer"),
+            XfrError::Incomplete => write!(f, "the transfer is incomplete")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::dns::{parse_tcp_stream, DNSLayer, Name, RCode, ResourceRecord, Type, Zone};
+
+    use super::{axfr_messages, ixfr_messages, IxfrDiff, Transfer, XfrError, XfrReader};
+
+    fn record(text: &str) -> ResourceRecord {
+        ResourceRecord::parse_with_origin(text, &Name::new("example.com")).unwrap()
+    }
+
+    fn soa(serial: u32) -> ResourceRecord {
+        record(&format!("@ 3600 IN SOA ns1 admin {} 7200 3600 604800 300", serial))
+    }
+
+    fn read(mut reader: XfrReader, messages: &[DNSLayer]) -> Result<Transfer, XfrError> {
+        for (i, message) in messages.iter().enumerate() {
+            assert_eq!(reader.push(message)?, i == messages.len() - 1);
+        }
+
+        reader.finish()
+    }
+
+    #[test]
+    fn test_axfr() {
+        let zone = Zone::parse("$TTL 300\n\
+                                @ SOA ns1 admin 3 7200 3600 604800 300\n\
+                                @ NS ns1\n\
+                                ns1 A 192.0.2.53\n\
+                                www A 192.0.2.1\n\
+                                www A 192.0.2.2\n", &Name::new("example.com")).unwrap();
+        let query = DNSLayer::query("example.com", Type::AXFR).id(7).build();
+
+        // The messages go over TCP like a real transfer.
+        let messages = axfr_messages(&query, &zone, 2).unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(messages.iter().all(|message| message.header().id == 7 && message.header().is_authoritative_answer()));
+        assert_eq!(messages[0].asnwers()[0].rtype, Type::SOA);
+        assert_eq!(messages[2].asnwers().iter().map(|rr| rr.rtype).collect::<Vec<_>>(), vec![Type::A, Type::SOA]);
+        let stream: Vec<u8> = messages.iter().flat_map(|message| message.raw_tcp()).collect();
+        let messages = parse_tcp_stream(&stream).unwrap();
+
+        assert_eq!(read(XfrReader::axfr(), &messages).unwrap(), Transfer::Full(zone.clone()));
+        // A server may answer IXFR with the whole zone.
+        assert_eq!(read(XfrReader::ixfr(), &messages).unwrap(), Transfer::Full(zone.clone()));
+        // The first message may only hold the opening SOA record.
+        let single = axfr_messages(&DNSLayer::query("example.com", Type::IXFR).build(), &zone, 1).unwrap();
+        assert_eq!(read(XfrReader::ixfr(), &single).unwrap(), Transfer::Full(zone.clone()));
+
+        let mut reader = XfrReader::axfr();
+        assert!(!reader.push(&messages[0]).unwrap());
+        assert_eq!(reader.clone().finish().unwrap_err(), XfrError::Incomplete);
+        for message in &messages[1..] {
+            reader.push(message).unwrap();
+        }
+        assert_eq!(reader.push(&messages[0]).unwrap_err(), XfrError::TrailingRecords);
+
+        // A zone with only its SOA record
+        let mut apex = Zone::new(Name::new("example.com"));
+        apex.insert(soa(1));
+        assert_eq!(read(XfrReader::axfr(), &axfr_messages(&query, &apex, 10).unwrap()).unwrap(), Transfer::Full(apex));
+        assert_eq!(axfr_messages(&query, &Zone::new(Name::new("example.com")), 10).unwrap_err(), XfrError::MissingSoa);
+
+        let response = |records: Vec<ResourceRecord>| records.into_iter().fold(DNSLayer::response_to(&query), |builder, rr| builder.answer(rr)).build();
+        let www = record("www 300 IN A 192.0.2.1");
+        assert_eq!(XfrReader::axfr().push(&response(vec![www.clone(), soa(1)])).unwrap_err(), XfrError::MissingSoa);
+        assert_eq!(XfrReader::axfr().push(&response(vec![soa(1), www.clone(), soa(2)])).unwrap_err(), XfrError::SerialMismatch { expected: 1, found: 2 });
+        assert_eq!(XfrReader::axfr().push(&response(vec![soa(1), soa(2)])).unwrap_err(), XfrError::SerialMismatch { expected: 1, found: 2 });
+        assert_eq!(XfrReader::axfr().push(&response(vec![soa(1), www, soa(1), soa(1)])).unwrap_err(), XfrError::TrailingRecords);
+        let refused = DNSLayer::response_to(&query).response_code(RCode::Refused).build();
+        assert_eq!(XfrReader::axfr().push(&refused).unwrap_err(), XfrError::ResponseCode(RCode::Refused));
+    }
+
+    #[test]
+    fn test_ixfr() {
+        // The example of RFC 1995 §7, in which the client has version 1 and the server version 3.
+        let diffs = vec![
+            IxfrDiff {
+                old_soa: soa(1),
+                deleted: vec![record("nezu.jain.ad.jp. 3600 IN A 133.69.136.5")],
+                new_soa: soa(2),
+                added: vec![record("jain-bb.jain.ad.jp. 3600 IN A 133.69.136.4"), record("jain-bb.jain.ad.jp. 3600 IN A 192.41.197.2")]
+            },
+            IxfrDiff {
+                old_soa: soa(2),
+                deleted: vec![record("jain-bb.jain.ad.jp. 3600 IN A 133.69.136.4")],
+                new_soa: soa(3),
+                added: vec![]
+            },
+        ];
+        let query = DNSLayer::query("example.com", Type::IXFR).build();
+
+        let messages = ixfr_messages(&query, &soa(3), &diffs, 3);
+        assert_eq!(messages.iter().map(|message| message.asnwers().len()).sum::<usize>(), 10);
+        assert_eq!(read(XfrReader::ixfr(), &messages).unwrap(), Transfer::Incremental(diffs.clone()));
+        assert_eq!(read(XfrReader::ixfr(), &ixfr_messages(&query, &soa(3), &diffs, 1)).unwrap(), Transfer::Incremental(diffs.clone()));
+        // Differences are only allowed in response to IXFR.
+        assert_eq!(XfrReader::axfr().push(&messages[0]).unwrap_err(), XfrError::SerialMismatch { expected: 3, found: 1 });
+
+        let messages = ixfr_messages(&query, &soa(3), &[], 3);
+        assert_eq!(messages.len(), 1);
+        // Whether the client is up to date is only known once the response has ended.
+        let mut reader = XfrReader::ixfr();
+        assert!(!reader.push(&messages[0]).unwrap());
+        assert_eq!(reader.finish().unwrap(), Transfer::UpToDate(soa(3)));
+        let mut reader = XfrReader::axfr();
+        assert!(!reader.push(&messages[0]).unwrap());
+        assert_eq!(reader.finish().unwrap_err(), XfrError::Incomplete);
+
+        // The SOA records of consecutive differences have to line up, and the last one has to be the current version.
+        let gap = vec![diffs[0].clone(), IxfrDiff { old_soa: soa(4), ..diffs[1].clone() }];
+        let err = read(XfrReader::ixfr(), &ixfr_messages(&query, &soa(3), &gap, 100));
+        assert_eq!(err.unwrap_err(), XfrError::SerialMismatch { expected: 2, found: 4 });
+        let short = vec![diffs[0].clone()];
+        let err = read(XfrReader::ixfr(), &ixfr_messages(&query, &soa(3), &short, 100));
+        assert_eq!(err.unwrap_err(), XfrError::SerialMismatch { expected: 3, found: 2 });
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::ParseError;
+use crate::Raw;
+
+use super::presentation::{invalid, unescape, Entry, Tokenizer, Tokens};
+use super::{Class, Name, ResourceRecord, Type};
+
+/// The maximum depth of nested `$INCLUDE` directives, which stops files from including each other forever.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// The records of a zone held in memory, grouped into RRsets by owner name and type.
+/// Owner names are compared without regard to case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zone {
+    origin: Name,
+    rrsets: HashMap<Name, HashMap<Type, Vec<ResourceRecord>>>
+}
+
+impl Zone {
+    /// Constructs an empty zone with the given origin, which is the name of its apex.
+    pub fn new(origin: Name) -> Self {
+        Zone { origin, rrsets: HashMap::new() }
+    }
+
+    /// Parses a master file [RFC 1035 §5](https://datatracker.ietf.org/doc/html/rfc1035#section-5), with `origin` as the
+    /// initial origin for relative names. Files named by `$INCLUDE` are looked up relative to the working directory.
+    pub fn parse(text: &str, origin: &Name) -> Result<Self, ZoneError> {
+        let mut zone = Zone::new(origin.clone());
+        zone.load(text, None, &mut Context::new(origin.clone()), 0)?;
+
+        Ok(zone)
+    }
+
+    /// Reads and parses the master file at `path`, see [Zone::parse].
+    /// Files named by `$INCLUDE` are looked up relative to the directory of the file which includes them.
+    pub fn read(path: impl AsRef<Path>, origin: &Name) -> Result<Self, ZoneError> {
+        let mut zone = Zone::new(origin.clone());
+        zone.load_file(path.as_ref(), &mut Context::new(origin.clone()), 0)?;
+
+        Ok(zone)
+    }
+
+    /// Writes the zone to a master file at `path`, in the format of the [Display](std::fmt::Display) implementation.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ZoneError> {
+        std::fs::write(path.as_ref(), self.to_string()).map_err(|error| ZoneError::Io { path: path.as_ref().to_path_buf(), error })
+    }
+
+    pub fn origin(&self) -> &Name {
+        &self.origin
+    }
+
+    /// Adds a record to the RRset of its name and type, which is kept sorted by record data.
+    /// Returns false without adding it if the RRset already has a record of the same class with the same data.
+    pub fn insert(&mut self, record: ResourceRecord) -> bool {
+        let rrset = self.rrsets.entry(record.name.clone()).or_default().entry(record.rtype).or_default();
+
+        if rrset.iter().any(|rr| rr.class == record.class && rr.rdata == record.rdata) {
+            return false;
+        }
+
+        let rdata = record.rdata.raw();
+        let position = rrset.partition_point(|rr| rr.rdata.raw() <= rdata);
+        rrset.insert(position, record);
+
+        true
+    }
+
+    /// Returns the RRset of the given name and type, if the zone has one.
+    pub fn get(&self, name: &Name, rtype: Type) -> Option<&[ResourceRecord]> {
+        self.rrsets.get(name).and_then(|rrsets| rrsets.get(&rtype)).map(Vec::as_slice)
+    }
+
+    /// Removes the RRset of the given name and type and returns its records.
+    pub fn remove(&mut self, name: &Name, rtype: Type) -> Option<Vec<ResourceRecord>> {
+        let rrsets = self.rrsets.get_mut(name)?;
+        let rrset = rrsets.remove(&rtype);
+
+        if rrsets.is_empty() {
+            self.rrsets.remove(name);
+        }
+
+        rrset
+    }
+
+    /// Removes the record of the same name, type, class and data as the given one, ignoring its TTL.
+    /// Returns whether the zone had such a record.
+    pub fn remove_record(&mut self, record: &ResourceRecord) -> bool {
+        let Some(rrset) = self.rrsets.get_mut(&record.name).and_then(|rrsets| rrsets.get_mut(&record.rtype)) else {
+            return false;
+        };
+        let Some(position) = rrset.iter().position(|rr| rr.class == record.class && rr.rdata == record.rdata) else {
+            return false;
+        };
+
+        rrset.remove(position);
+        if rrset.is_empty() {
+            self.remove(&record.name, record.rtype);
+        }
+
+        true
+    }
+
+    /// Whether the zone has any records with the given owner name.
+    pub fn contains_name(&self, name: &Name) -> bool {
+        self.rrsets.contains_key(name)
+    }
+
+    /// Returns the types of the RRsets of the given name, in no particular order.
+    pub fn types(&self, name: &Name) -> Vec<Type> {
+        self.rrsets.get(name).map(|rrsets| rrsets.keys().copied().collect()).unwrap_or_default()
+    }
+
+    /// Returns the SOA record at the apex of the zone.
+    pub fn soa(&self) -> Option<&ResourceRecord> {
+        self.get(&self.origin, Type::SOA).and_then(|rrset| rrset.first())
+    }
+
+    /// Returns the number of records in the zone.
+    pub fn len(&self) -> usize {
+        self.rrsets.values().flat_map(HashMap::values).map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rrsets.is_empty()
+    }
+
+    /// Returns all records of the zone sorted by owner name in the canonical order of DNSSEC
+    /// [RFC 4034 §6.1](https://datatracker.ietf.org/doc/html/rfc4034#section-6.1), then by type code and then by record data.
+    pub fn records(&self) -> Vec<&ResourceRecord> {
+        let mut records: Vec<&ResourceRecord> = self.rrsets.values().flat_map(HashMap::values).flatten().collect();
+
+        // The sort is stable and RRsets are already sorted by their data.
+        records.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| u16::from(a.rtype).cmp(&u16::from(b.rtype))));
+
+        records
+    }
+
+    fn load_file(&mut self, path: &Path, context: &mut Context, depth: usize) -> Result<(), ZoneError> {
+        let text = std::fs::read_to_string(path).map_err(|error| ZoneError::Io { path: path.to_path_buf(), error })?;
+        self.load(&text, Some(path), context, depth)
+    }
+
+    /// Adds the records of a master file, whose contents are `text`.
+    /// `path` is the file the text was read from, if any, against which included files are resolved.
+    fn load(&mut self, text: &str, path: Option<&Path>, context: &mut Context, depth: usize) -> Result<(), ZoneError> {
+        let error = |error: ParseError| ZoneError::Parse { path: path.map(Path::to_path_buf), line: line_of(text, error.offset), error };
+        let mut tokenizer = Tokenizer::new(text);
+
+        while let Some(entry) = tokenizer.next_entry() {
+            let entry = entry.map_err(error)?;
+            let directive = &entry.tokens[0];
+
+            if entry.indented || !directive.text.starts_with('$') {
+                let record = context.record(&entry).map_err(error)?;
+                self.insert(record);
+                continue;
+            }
+
+            let mut tokens = Tokens::new(&entry.tokens[1..], entry.end, &context.origin);
+
+            match directive.text.to_ascii_uppercase().as_str() {
+                "$ORIGIN" => {
+                    let origin = tokens.name("Zone.origin").map_err(error)?;
+                    tokens.finish().map_err(error)?;
+                    context.origin = origin;
+                },
+                "$TTL" => {
+                    let ttl = tokens.ttl("Zone.ttl").map_err(error)?;
+                    tokens.finish().map_err(error)?;
+                    context.default_ttl = Some(ttl);
+                },
+                "$INCLUDE" => {
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        return Err(error(invalid("Zone.include", directive, "too many nested includes")));
+                    }
+
+                    let file = tokens.next("Zone.include").map_err(error)?;
+                    let file = unescape(&file.text, "Zone.include").map_err(|e| error(e.offset_by(file.offset)))?;
+                    let file = PathBuf::from(String::from_utf8_lossy(&file).as_ref());
+
+                    // The origin of the included file may be given, the origin of the including file is not changed by it
+                    // [RFC 1035 §5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+                    let origin = if tokens.is_empty() { context.origin.clone() } else { tokens.name("Zone.include").map_err(error)? };
+                    tokens.finish().map_err(error)?;
+
+                    let file = match path.and_then(Path::parent) {
+                        Some(directory) => directory.join(file),
+                        None => file
+                    };
+                    let mut included = Context { origin, last_owner: None, ..context.clone() };
+                    self.load_file(&file, &mut included, depth + 1)?;
+                },
+                _ => return Err(error(invalid("Zone", directive, "unknown directive")))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Zone {
+    /// Formats the zone as a master file with one record per line in the order of [Zone::records].
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "$ORIGIN {}", self.origin.to_fqdn())?;
+
+        for record in self.records() {
+            writeln!(f, "{}", record)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The state which carries over from one entry of a master file to the next.
+#[derive(Debug, Clone)]
+struct Context {
+    origin: Name,
+
+    /// The TTL set by `$TTL` [RFC 2308 §4](https://datatracker.ietf.org/doc/html/rfc2308#section-4).
+    default_ttl: Option<u32>,
+
+    /// The owner name of the previous record, which is used by entries that start with whitespace.
+    last_owner: Option<Name>,
+
+    /// The TTL of the previous record, which is used if there is no `$TTL`.
+    last_ttl: Option<u32>,
+
+    /// The class of the previous record.
+    last_class: Class
+}
+
+impl Context {
+    fn new(origin: Name) -> Self {
+        Context { origin, default_ttl: None, last_owner: None, last_ttl: None, last_class: Class::IN }
+    }
+
+    /// Reads the record of an entry, taking the fields which are left out from the previous records.
+    fn record(&mut self, entry: &Entry) -> Result<ResourceRecord, ParseError> {
+        let mut tokens = Tokens::new(&entry.tokens, entry.end, &self.origin);
+
+        let name = match entry.indented {
+            true => self.last_owner.clone().ok_or_else(|| invalid("ResourceRecord.name", &entry.tokens[0], "no previous owner name"))?,
+            false => tokens.name("ResourceRecord.name")?
+        };
+        let record = ResourceRecord::from_tokens(name, &mut tokens, self.default_ttl.or(self.last_ttl), self.last_class)?;
+        tokens.finish()?;
+
+        self.last_owner = Some(record.name.clone());
+        self.last_ttl = Some(record.ttl);
+        self.last_class = record.class;
+
+        Ok(record)
+    }
+}
+
+/// Returns the number of the line which contains the given byte offset, starting at 1.
+fn line_of(text: &str, offset: usize) -> usize {
+    text.as_bytes()[..offset.min(text.len())].iter().filter(|&&byte| byte == b'\n').count() + 1
+}
+
+/// An error produced when reading a master file.
+#[derive(Debug)]
+pub enum ZoneError {
+    /// A file could not be read or written.
+    Io { path: PathBuf, error: std::io::Error },
+
+    /// A file is malformed, or the text given to [Zone::parse] if `path` is None.
+    /// The offset of `error` is the byte offset in that file, and `line` the line it is on, starting at 1.
+    Parse { path: Option<PathBuf>, line: usize, error: ParseError }
+}
+
+impl std::error::Error for ZoneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZoneError::Io { error, .. } => Some(error),
+            ZoneError::Parse { error, .. } => Some(error)
+        }
+    }
+}
+
+impl std::fmt::Display for ZoneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ZoneError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ZoneError::Parse { path: Some(path), line, error } => write!(f, "{}, line {}: {}", path.display(), line, error),
+            ZoneError::Parse { path: None, line, error } => write!(f, "line {}: {}", line, error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::application::dns::{rdata::{RData, MX}, Class, Name, Type};
+    use crate::error::ParseErrorKind;
+
+    use super::{Zone, ZoneError};
+
+    const ZONE: &str = "$ORIGIN example.com.
+$TTL 1h
+@   IN  SOA ns1 hostmaster (
+            2024010101 ; serial
+            1d 2h 4w 1h )
+    IN  NS  ns1
+    IN  MX  10 mail       ; the owner is inherited
+www 300 A   192.0.2.2
+WWW     A   192.0.2.1
+        TXT \"v=spf1 -all\"
+ns1     A   192.0.2.53
+$ORIGIN sub
+host    A   192.0.2.3
+mail.example.com. A 192.0.2.25
+";
+
+    #[test]
+    fn test_zone() {
+        let zone = Zone::parse(ZONE, &Name::root()).unwrap();
+        assert_eq!(zone.len(), 9);
+        assert_eq!(zone.origin(), &Name::root());
+
+        let soa = zone.get(&Name::new("example.com"), Type::SOA).unwrap();
+        assert_eq!(soa[0].to_string(), "example.com. 3600 IN SOA ns1.example.com. hostmaster.example.com. 2024010101 86400 7200 2419200 3600");
+        assert_eq!(zone.get(&Name::new("example.com"), Type::MX).unwrap()[0].rdata, RData::MX(MX::new(10, Name::new("mail.example.com"))));
+
+        // Names are grouped without regard to case, RRsets are sorted by data and records without a TTL get the one of $TTL.
+        let www = zone.get(&Name::new("www.example.com"), Type::A).unwrap();
+        assert_eq!(www.iter().map(|rr| rr.ttl).collect::<Vec<_>>(), vec![3600, 300]);
+        assert_eq!(zone.get(&Name::new("WWW.example.com"), Type::TXT).unwrap()[0].name, Name::new("WWW.example.com"));
+        assert!(zone.get(&Name::new("host.sub.example.com"), Type::A).is_some());
+        assert_eq!(zone.get(&Name::new("host.sub.example.com"), Type::A).unwrap()[0].class, Class::IN);
+
+        // Records are written in canonical order, and the output parses to the same zone.
+        let text = zone.to_string();
+        assert_eq!(text.lines().take(5).collect::<Vec<_>>(), vec![
+            "$ORIGIN .",
+            "example.com. 3600 IN NS ns1.example.com.",
+            "example.com. 3600 IN SOA ns1.example.com. hostmaster.example.com. 2024010101 86400 7200 2419200 3600",
+            "example.com. 3600 IN MX 10 mail.example.com.",
+            "mail.example.com. 3600 IN A 192.0.2.25",
+        ]);
+        assert_eq!(text.lines().last(), Some("WWW.example.com. 3600 IN TXT \"v=spf1 -all\""));
+        assert_eq!(Zone::parse(&text, &Name::root()).unwrap(), zone);
+
+        let mut zone = zone;
+        assert!(!zone.insert(zone.get(&Name::new("ns1.example.com"), Type::A).unwrap()[0].clone()));
+        assert_eq!(zone.remove(&Name::new("ns1.example.com"), Type::A).unwrap().len(), 1);
+        assert!(zone.remove(&Name::new("ns1.example.com"), Type::A).is_none());
+        assert_eq!(zone.len(), 8);
+
+        // Without $TTL, records take the TTL of the record before them.
+        let zone = Zone::parse("a 60 A 192.0.2.1\nb A 192.0.2.2\n", &Name::new("example")).unwrap();
+        assert_eq!(zone.get(&Name::new("b.example"), Type::A).unwrap()[0].ttl, 60);
+        assert!(zone.soa().is_none());
+    }
+
+    #[test]
+    fn test_zone_errors() {
+        let parse_error = |text: &str| match Zone::parse(text, &Name::new("example")) {
+            Err(ZoneError::Parse { path: None, line, error }) => (line, error.offset, error.kind),
+            other => panic!("unexpected result {:?}", other)
+        };
+
+        assert_eq!(parse_error("a 60 A 192.0.2.1\n\n; comment\nb 60 A 192.0.2\n"), (4, 35, ParseErrorKind::Invalid("invalid value")));
+        assert_eq!(parse_error("  60 A 192.0.2.1\n"), (1, 2, ParseErrorKind::Invalid("no previous owner name")));
+        assert_eq!(parse_error("a A 192.0.2.1\n").2, ParseErrorKind::Invalid("missing TTL"));
+        assert_eq!(parse_error("$GENERATE 1-2 a$ A 192.0.2.$\n"), (1, 0, ParseErrorKind::Invalid("unknown directive")));
+        assert_eq!(parse_error("$TTL 1 2\n"), (1, 7, ParseErrorKind::Invalid("unexpected trailing data")));
+        assert_eq!(parse_error("a 60 A 192.0.2.1\nb 60 TXT ( \"a\"\n").0, 2);
+
+        let missing = PathBuf::from("/nonexistent/zone");
+        assert!(matches!(Zone::read(&missing, &Name::root()), Err(ZoneError::Io { path, .. }) if path == missing));
+    }
+
+    #[test]
+    fn test_zone_include() {
+        let directory = std::env::temp_dir().join(format!("lrpp-zone-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        std::fs::write(directory.join("hosts.zone"), "host A 192.0.2.1\n").unwrap();
+        std::fs::write(directory.join("example.zone"), "$TTL 300\n\
+                                                        @ SOA ns1 hostmaster 1 2 3 4 5\n\
+                                                        $INCLUDE hosts.zone sub ; hosts are below sub\n\
+                                                        www A 192.0.2.2\n\
+                                                        \\$INCLUDE A 192.0.2.3\n").unwrap();
+        std::fs::write(directory.join("loop.zone"), "$INCLUDE loop.zone\n").unwrap();
+
+        let zone = Zone::read(directory.join("example.zone"), &Name::new("example.com")).unwrap();
+        assert_eq!(zone.get(&Name::new("host.sub.example.com"), Type::A).unwrap()[0].ttl, 300);
+        // The included origin does not carry over to the including file.
+        assert!(zone.get(&Name::new("www.example.com"), Type::A).is_some());
+        assert_eq!(zone.soa().unwrap().name, Name::new("example.com"));
+        assert_eq!(zone.len(), 4);
+
+        zone.write(directory.join("written.zone")).unwrap();
+        assert_eq!(Zone::read(directory.join("written.zone"), &Name::new("example.com")).unwrap(), zone);
+
+        match Zone::read(directory.join("loop.zone"), &Name::root()) {
+            Err(ZoneError::Parse { path: Some(path), line: 1, error }) => {
+                assert_eq!(path, directory.join("loop.zone"));
+                assert_eq!(error.kind, ParseErrorKind::Invalid("too many nested includes"));
+            },
+            other => panic!("unexpected result {:?}", other)
+        }
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
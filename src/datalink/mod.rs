@@ -1,5 +1,4 @@
-
-use regex::Regex;
+use crate::error::{ParseError, ParseErrorKind};
 
 pub mod ethernet;
 // pub use ethernet::EthernetLayer;
@@ -9,21 +8,36 @@ pub struct MACAddr {
     addr: [u8; 6]
 }
 
-const MAC_REGEX: &str = r"^([0-9A-Fa-f]{2}[:]){5}([0-9A-Fa-f]{2})$";
+/// The length of a MAC address in the form of XX:XX:XX:XX:XX:XX.
+const MAC_STR_LEN: usize = 17;
 
 impl MACAddr {
     pub fn new(addr: [u8; 6]) -> Self {
-        MACAddr { addr: addr }
+        MACAddr { addr }
     }
 
     /// Takes a string in the form of XX:XX:XX:XX:XX:XX and produces a MAC address.
-    pub fn from_str(str: &str) -> Self {
-        let re = Regex::new(MAC_REGEX).unwrap();
-        if !re.is_match(str) { panic!("Specified string is not a valid MAC address!"); }
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, ParseError> {
+        if s.len() != MAC_STR_LEN {
+            return Err(ParseError::new("Ethernet", "MACAddr", 0, ParseErrorKind::BadLength(s.len())));
+        }
+
+        let mut addr = [0u8; 6];
+        for (i, c) in s.char_indices() {
+            // Every third character is a separator, the rest are hex digits.
+            if i % 3 == 2 {
+                if c != ':' {
+                    return Err(ParseError::new("Ethernet", "MACAddr", i, ParseErrorKind::InvalidCharacter(c)));
+                }
+                continue;
+            }
+
+            let digit = c.to_digit(16).ok_or(ParseError::new("Ethernet", "MACAddr", i, ParseErrorKind::InvalidCharacter(c)))?;
+            addr[i / 3] = (addr[i / 3] << 4) | digit as u8;
+        }
 
-        let bytes = hex::decode(str.replace(':', "")).unwrap();
-        
-        MACAddr { addr: bytes.try_into().unwrap() }
+        Ok(MACAddr { addr })
     }
 
     /// Returns a reference to the bytes of the MAC address.
@@ -39,14 +53,23 @@ impl MACAddr {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::ParseErrorKind;
+
     use super::MACAddr;
 
     #[test]
     fn test_mac_addr() {
         let mut mac = MACAddr::new([0x23, 0xAC, 0x12, 0, 0xAF, 0]);
-        assert_eq!(mac, MACAddr::from_str("23:AC:12:00:AF:00"));
+        assert_eq!(mac, MACAddr::from_str("23:AC:12:00:AF:00").unwrap());
+
+        mac.set(*MACAddr::from_str("aa:ab:ac:ad:ae:af").unwrap().get());
+        assert_eq!(mac, MACAddr::from_str("aa:ab:ac:ad:ae:af").unwrap());
 
-        mac.set(*MACAddr::from_str("aa:ab:ac:ad:ae:af").get());
-        assert_eq!(mac, MACAddr::from_str("aa:ab:ac:ad:ae:af"));
+        // Malformed addresses
+        assert_eq!(MACAddr::from_str("aa:ab:ac:ad:ae").unwrap_err().kind, ParseErrorKind::BadLength(14));
+        let err = MACAddr::from_str("aa:ab-ac:ad:ae:af").unwrap_err();
+        assert_eq!((err.offset, err.kind), (5, ParseErrorKind::InvalidCharacter('-')));
+        let err = MACAddr::from_str("aa:ab:ac:ad:ae:ag").unwrap_err();
+        assert_eq!((err.offset, err.kind), (16, ParseErrorKind::InvalidCharacter('g')));
     }
-}
\ No newline at end of file
+}
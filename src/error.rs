@@ -0,0 +1,79 @@
+/// An error produced when parsing malformed or truncated input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    /// The layer which was being parsed, e.g. "DNS".
+    pub layer: &'static str,
+    /// The structure and field which failed to parse, e.g. "Question.qtype".
+    pub field: &'static str,
+    /// The byte offset into the parsed input at which the error occurred.
+    pub offset: usize,
+    /// The reason for the failure.
+    pub kind: ParseErrorKind
+}
+
+impl ParseError {
+    /// Constructs a parse error from the given values.
+    pub fn new(layer: &'static str, field: &'static str, offset: usize, kind: ParseErrorKind) -> Self {
+        ParseError { layer, field, offset, kind }
+    }
+
+    /// Constructs an error for input which ended before `needed` bytes could be read at `offset`.
+    pub fn truncated(layer: &'static str, field: &'static str, offset: usize, needed: usize, available: usize) -> Self {
+        ParseError::new(layer, field, offset, ParseErrorKind::Truncated { needed, available })
+    }
+
+    /// Shifts the offset of the error by `base` bytes.
+    /// Used when a parser is handed a subslice of a bigger input.
+    pub(crate) fn offset_by(mut self, base: usize) -> Self {
+        self.offset += base;
+        self
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} at byte {}: {}", self.layer, self.field, self.offset, self.kind)
+    }
+}
+
+/// The possible reasons for a parse failure.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// The input ended before the field could be read completely.
+    Truncated { needed: usize, available: usize },
+    /// A length has a value which is invalid in its context.
+    BadLength(usize),
+    /// A type code which is not known.
+    UnknownType(u16),
+    /// A class code which is not known.
+    UnknownClass(u16),
+    /// A DNS label starts with a length byte whose upper bits denote an unsupported label type.
+    BadLabelType(u8),
+    /// A DNS name compression pointer to the given offset, which is not before the pointer itself.
+    ForwardPointer(usize),
+    /// A DNS name compression pointer to the given offset, which would make the name loop.
+    PointerLoop(usize),
+    /// A character which is not valid in the textual representation being parsed.
+    InvalidCharacter(char),
+    /// The input violates a rule of the protocol, described by the message.
+    Invalid(&'static str)
+}
+
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use ParseErrorKind::*;
+        match self {
+            Truncated { needed, available } => write!(f, "truncated input, needed {} bytes but only {} are available", needed, available),
+            BadLength(length) => write!(f, "invalid length {}", length),
+            UnknownType(code) => write!(f, "unknown type {}", code),
+            UnknownClass(code) => write!(f, "unknown class {}", code),
+            BadLabelType(byte) => write!(f, "unsupported label type {:#04x}", byte),
+            ForwardPointer(target) => write!(f, "compression pointer to later offset {}", target),
+            PointerLoop(target) => write!(f, "compression pointer to offset {} forms a loop", target),
+            InvalidCharacter(c) => write!(f, "invalid character {:?}", c),
+            Invalid(message) => write!(f, "{}", message)
+        }
+    }
+}
@@ -1,9 +1,11 @@
 pub mod datalink;
 pub mod application;
 pub mod packet;
+pub mod error;
 
 pub use packet::Packet;
 pub use datalink::MACAddr;
+pub use error::{ParseError, ParseErrorKind};
 
 pub trait Raw {
     fn raw(&self) -> Vec<u8>;
@@ -1,7 +1,3 @@
-use num_enum::TryFromPrimitive;
-
-use crate::{datalink, application};
-
 use std::any::Any;
 
 pub const MAX_LAYER_COUNT: u8 = 7;
@@ -11,20 +7,17 @@ pub struct Packet {
     layers: Vec<Box<dyn Layer>>
 }
 
+// Packets are not assembled by the library yet, so these are only used by the tests for now.
+#[allow(dead_code)]
 impl Packet {
     fn new() -> Self {
         Packet { layers: Vec::with_capacity(7) }
     }
 
     /// If the layer is present in the packet, then it is safe do downcast the trait object into the underlying type
+    #[allow(clippy::borrowed_box)]
     fn get_layer(&self, name: &str) -> Option<&Box<dyn Layer>> {
-        for layer in &self.layers {
-            if layer.get_name() == name {
-                return Some(layer);
-            }
-        }
-
-        None
+        self.layers.iter().find(|layer| layer.get_name() == name)
     }
 
     fn add_layer(&mut self, layer: Box<dyn Layer>) -> Result<(), Box<dyn std::error::Error>> {
@@ -35,7 +28,7 @@ impl Packet {
         }
 
         self.layers.push(layer);
-        return Ok(());
+        Ok(())
     }
 }
 
@@ -73,7 +66,10 @@ mod tests {
         use crate::Packet;
         
         let mut packet = Packet::new();
+        assert!(packet.get_layer("DNS").is_none());
 
-        
+        packet.add_layer(Box::new(DNSLayer::new())).unwrap();
+        assert!(packet.add_layer(Box::new(DNSLayer::new())).is_err());
+        assert!(packet.get_layer("DNS").unwrap().as_any().downcast_ref::<DNSLayer>().is_some());
     }
 }
\ No newline at end of file